@@ -0,0 +1,170 @@
+//! Token budget for the conversation history sent with each request.
+//!
+//! The system instruction (personality, user summary, running conversation
+//! summary, knowledge passages) and the new user message are always sent.
+//! Whatever is left of the budget goes to the most recent turns, newest first.
+//! Stored turns that no longer fit the history budget are folded into the
+//! conversation's running summary after the reply (see `AIManager::summarize_turns`).
+
+/// Rough characters per token; the providers don't expose their tokenizers
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Framing added around every turn (role markers, separators)
+const TURN_OVERHEAD_TOKENS: usize = 4;
+
+/// Estimated tokens of a piece of text
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Estimated tokens of a conversation turn
+pub fn estimate_turn_tokens(content: &str) -> usize {
+    estimate_tokens(content) + TURN_OVERHEAD_TOKENS
+}
+
+/// Prompt size limits
+///
+/// Read from environment variables:
+/// - `AI_PROMPT_TOKEN_BUDGET`: whole request, system instruction included (default 8000)
+/// - `AI_HISTORY_TOKEN_BUDGET`: conversation history kept before older turns are summarized (default 3000)
+///
+/// Invalid values are logged and replaced by the default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContextBudget {
+    pub prompt_tokens: usize,
+    pub history_tokens: usize,
+}
+
+impl Default for ContextBudget {
+    fn default() -> Self {
+        Self {
+            prompt_tokens: 8000,
+            history_tokens: 3000,
+        }
+    }
+}
+
+impl ContextBudget {
+    /// Build the budget from process environment variables
+    pub fn from_env() -> Self {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Build the budget from an arbitrary key lookup (used by tests)
+    pub fn from_lookup<F>(lookup: F) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let defaults = Self::default();
+        let get = |key: &str, default: usize| match lookup(key).map(|v| v.trim().to_string()).filter(|v| !v.is_empty()) {
+            Some(raw) => match raw.parse::<usize>() {
+                Ok(value) if value > 0 => value,
+                _ => {
+                    eprintln!("⚠️  Invalid {} {:?}, using {}", key, raw, default);
+                    default
+                }
+            },
+            None => default,
+        };
+
+        Self {
+            prompt_tokens: get("AI_PROMPT_TOKEN_BUDGET", defaults.prompt_tokens),
+            history_tokens: get("AI_HISTORY_TOKEN_BUDGET", defaults.history_tokens),
+        }
+    }
+
+    /// History tokens left once the parts that are always sent use `fixed_tokens`
+    pub fn history_allowance(&self, fixed_tokens: usize) -> usize {
+        self.history_tokens.min(self.prompt_tokens.saturating_sub(fixed_tokens))
+    }
+}
+
+/// Index of the first turn of the newest run of `turns` that fits in `allowance` tokens
+///
+/// Turns are taken newest first and the run stops at the first one that doesn't
+/// fit, so the kept history is always contiguous. A run never starts with an
+/// assistant turn. The returned index is also the number of turns left out.
+pub fn fit_history<T>(
+    turns: &[T],
+    allowance: usize,
+    content: impl Fn(&T) -> &str,
+    is_assistant: impl Fn(&T) -> bool,
+) -> usize {
+    let mut used = 0;
+    let mut start = turns.len();
+    for (index, turn) in turns.iter().enumerate().rev() {
+        used += estimate_turn_tokens(content(turn));
+        if used > allowance {
+            break;
+        }
+        start = index;
+    }
+
+    // A reply without the message it answers only confuses the model
+    while start < turns.len() && is_assistant(&turns[start]) {
+        start += 1;
+    }
+    start
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turns(spec: &[(bool, usize)]) -> Vec<(bool, String)> {
+        spec.iter().map(|(assistant, chars)| (*assistant, "x".repeat(*chars))).collect()
+    }
+
+    fn fit(turns: &[(bool, String)], allowance: usize) -> usize {
+        fit_history(turns, allowance, |t| t.1.as_str(), |t| t.0)
+    }
+
+    #[test]
+    fn estimates_round_up() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abc"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+        assert_eq!(estimate_turn_tokens("abcd"), 1 + TURN_OVERHEAD_TOKENS);
+    }
+
+    #[test]
+    fn keeps_newest_turns_that_fit() {
+        // Each turn costs 10 + 4 tokens
+        let history = turns(&[(false, 40), (true, 40), (false, 40), (true, 40)]);
+        assert_eq!(fit(&history, 1000), 0);
+        assert_eq!(fit(&history, 28), 2);
+        assert_eq!(fit(&history, 0), 4);
+    }
+
+    #[test]
+    fn stops_at_first_turn_that_does_not_fit() {
+        // The old short turn would fit but isn't kept past the long one
+        let history = turns(&[(false, 4), (true, 400), (false, 40)]);
+        assert_eq!(fit(&history, 30), 2);
+    }
+
+    #[test]
+    fn never_starts_with_an_assistant_turn() {
+        let history = turns(&[(false, 40), (true, 40), (false, 40), (true, 40)]);
+        // Room for three turns, but the third newest is a reply
+        assert_eq!(fit(&history, 42), 2);
+    }
+
+    #[test]
+    fn allowance_respects_both_budgets() {
+        let budget = ContextBudget { prompt_tokens: 1000, history_tokens: 300 };
+        assert_eq!(budget.history_allowance(100), 300);
+        assert_eq!(budget.history_allowance(900), 100);
+        assert_eq!(budget.history_allowance(2000), 0);
+    }
+
+    #[test]
+    fn budget_from_lookup_falls_back_on_invalid_values() {
+        let budget = ContextBudget::from_lookup(|key| match key {
+            "AI_PROMPT_TOKEN_BUDGET" => Some("12000".to_string()),
+            "AI_HISTORY_TOKEN_BUDGET" => Some("0".to_string()),
+            _ => None,
+        });
+        assert_eq!(budget, ContextBudget { prompt_tokens: 12000, history_tokens: 3000 });
+    }
+}
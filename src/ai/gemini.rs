@@ -1,173 +1,612 @@
-use serde::{Deserialize, Serialize};
-use std::error::Error;
-use reqwest::Client;
-
-/// Gemini API client
-pub struct GeminiClient {
-    client: Client,
-    api_key: String,
-    api_url: String,
-}
-
-/// Response from Gemini API
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GeminiResponse {
-    pub content: String,
-    pub finish_reason: Option<String>,
-    pub safety_ratings: Option<Vec<SafetyRating>>,
-}
-
-/// Safety rating from Gemini
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SafetyRating {
-    pub category: String,
-    pub probability: String,
-}
-
-/// Request structure for Gemini API
-#[derive(Debug, Serialize)]
-struct GeminiRequest {
-    contents: Vec<Content>,
-    generation_config: GenerationConfig,
-    safety_settings: Vec<SafetySetting>,
-}
-
-#[derive(Debug, Serialize)]
-struct Content {
-    parts: Vec<Part>,
-}
-
-#[derive(Debug, Serialize)]
-struct Part {
-    text: String,
-}
-
-#[derive(Debug, Serialize)]
-struct GenerationConfig {
-    temperature: f32,
-    top_k: u32,
-    top_p: f32,
-    max_output_tokens: u32,
-}
-
-#[derive(Debug, Serialize)]
-struct SafetySetting {
-    category: String,
-    threshold: String,
-}
-
-/// Response structure from Gemini API
-#[derive(Debug, Deserialize)]
-struct GeminiApiResponse {
-    candidates: Option<Vec<Candidate>>,
-    prompt_feedback: Option<PromptFeedback>,
-}
-
-#[derive(Debug, Deserialize)]
-struct Candidate {
-    content: ContentResponse,
-    finish_reason: Option<String>,
-    safety_ratings: Option<Vec<SafetyRating>>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ContentResponse {
-    parts: Vec<PartResponse>,
-}
-
-#[derive(Debug, Deserialize)]
-struct PartResponse {
-    text: String,
-}
-
-#[derive(Debug, Deserialize)]
-struct PromptFeedback {
-    safety_ratings: Option<Vec<SafetyRating>>,
-}
-
-impl GeminiClient {
-    pub fn new(api_key: String) -> Result<Self, Box<dyn Error>> {
-        if api_key.is_empty() {
-            return Err("Gemini API key not provided".into());
-        }
-
-        Ok(Self {
-            client: Client::new(),
-            api_key,
-            api_url: "https://generativelanguage.googleapis.com/v1beta/models/gemini-1.5-flash:generateContent".to_string(),
-        })
-    }
-
-    pub async fn generate_response(&self, prompt: &str) -> Result<GeminiResponse, Box<dyn Error>> {
-        let request = GeminiRequest {
-            contents: vec![Content {
-                parts: vec![Part {
-                    text: prompt.to_string(),
-                }],
-            }],
-            generation_config: GenerationConfig {
-                temperature: 0.7,
-                top_k: 40,
-                top_p: 0.8,
-                max_output_tokens: 1000,
-            },
-            safety_settings: vec![
-                SafetySetting {
-                    category: "HARM_CATEGORY_HARASSMENT".to_string(),
-                    threshold: "BLOCK_MEDIUM_AND_ABOVE".to_string(),
-                },
-                SafetySetting {
-                    category: "HARM_CATEGORY_HATE_SPEECH".to_string(),
-                    threshold: "BLOCK_MEDIUM_AND_ABOVE".to_string(),
-                },
-                SafetySetting {
-                    category: "HARM_CATEGORY_SEXUALLY_EXPLICIT".to_string(),
-                    threshold: "BLOCK_MEDIUM_AND_ABOVE".to_string(),
-                },
-                SafetySetting {
-                    category: "HARM_CATEGORY_DANGEROUS_CONTENT".to_string(),
-                    threshold: "BLOCK_MEDIUM_AND_ABOVE".to_string(),
-                },
-            ],
-        };
-
-        let url = format!("{}?key={}", self.api_url, self.api_key);
-        
-        let response = self.client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(format!("Gemini API error: {}", error_text).into());
-        }
-
-        let api_response: GeminiApiResponse = response.json().await?;
-
-        // Extract the response content
-        if let Some(candidates) = api_response.candidates {
-            if let Some(candidate) = candidates.first() {
-                if let Some(part) = candidate.content.parts.first() {
-                    return Ok(GeminiResponse {
-                        content: part.text.clone(),
-                        finish_reason: candidate.finish_reason.clone(),
-                        safety_ratings: candidate.safety_ratings.clone(),
-                    });
-                }
-            }
-        }
-
-        Err("No valid response from Gemini API".into())
-    }
-
-    /// Test connection to Gemini API
-    pub async fn test_connection(&self) -> Result<bool, Box<dyn Error>> {
-        match self.generate_response("Hello, respond with 'OK' if you can hear me.").await {
-            Ok(_) => Ok(true),
-            Err(_) => Ok(false),
-        }
-    }
-}
\ No newline at end of file
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use reqwest::Client;
+use serenity::async_trait;
+
+use super::provider::{LlmChunkSender, LlmConfig, LlmError, LlmMessage, LlmProvider, LlmRequest, LlmResponse, LlmRole, LlmTool, LlmToolCall, SafetyRating, TokenUsage};
+use super::moderation;
+use super::streaming::SseDecoder;
+
+/// Gemini API client
+pub struct GeminiClient {
+    client: Client,
+    api_key: String,
+    /// `{base_url}/models/{model}`; the method (`:generateContent`, ...) is appended per call
+    model_url: String,
+    temperature: f32,
+    max_output_tokens: u32,
+}
+
+/// Request structure for Gemini API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_instruction: Option<Content>,
+    contents: Vec<Content>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<GeminiTool>,
+    generation_config: GenerationConfig,
+    safety_settings: Vec<SafetySetting>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct Content {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    role: Option<&'static str>,
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct Part {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiTool {
+    function_declarations: Vec<FunctionDeclaration>,
+}
+
+#[derive(Debug, Serialize)]
+struct FunctionDeclaration {
+    name: String,
+    description: String,
+    parameters: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerationConfig {
+    temperature: f32,
+    top_k: u32,
+    top_p: f32,
+    max_output_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_mime_type: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_schema: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct SafetySetting {
+    category: String,
+    threshold: String,
+}
+
+/// Response structure from Gemini API
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiApiResponse {
+    candidates: Option<Vec<Candidate>>,
+    prompt_feedback: Option<PromptFeedback>,
+    usage_metadata: Option<UsageMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Candidate {
+    content: Option<ContentResponse>,
+    finish_reason: Option<String>,
+    safety_ratings: Option<Vec<SafetyRating>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContentResponse {
+    parts: Vec<PartResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PartResponse {
+    text: Option<String>,
+    function_call: Option<FunctionCall>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FunctionCall {
+    name: String,
+    #[serde(default)]
+    args: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageMetadata {
+    #[serde(default)]
+    prompt_token_count: u64,
+    #[serde(default)]
+    candidates_token_count: u64,
+}
+
+impl From<UsageMetadata> for TokenUsage {
+    fn from(usage: UsageMetadata) -> Self {
+        TokenUsage {
+            prompt_tokens: usage.prompt_token_count,
+            response_tokens: usage.candidates_token_count,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PromptFeedback {
+    /// Set when the prompt itself was blocked and no candidates were generated
+    block_reason: Option<String>,
+    safety_ratings: Option<Vec<SafetyRating>>,
+}
+
+impl PromptFeedback {
+    /// Report a blocked prompt like a blocked reply, so moderation sees it
+    fn apply_block(self, response: &mut LlmResponse) {
+        if let Some(reason) = self.block_reason {
+            response.finish_reason = Some(reason);
+            response.safety_ratings = self.safety_ratings;
+        }
+    }
+}
+
+impl GeminiClient {
+    pub fn new(config: &LlmConfig) -> Result<Self, Box<dyn Error>> {
+        if config.api_key.is_empty() {
+            return Err("Gemini API key not provided".into());
+        }
+
+        Ok(Self {
+            client: Client::new(),
+            api_key: config.api_key.clone(),
+            model_url: format!("{}/models/{}", config.base_url, config.model),
+            temperature: config.temperature,
+            max_output_tokens: config.max_tokens,
+        })
+    }
+
+    fn build_request(&self, request: &LlmRequest) -> GeminiRequest {
+        // Gemini rejects JSON mode combined with function calling; with tools the
+        // reply format is left to the system instruction
+        let json_schema = request.response_schema.as_ref().filter(|_| request.tools.is_empty());
+
+        GeminiRequest {
+            system_instruction: request.system_instruction.as_ref().map(|text| Content {
+                role: None,
+                parts: vec![Part { text: text.clone() }],
+            }),
+            contents: build_contents(&request.messages),
+            tools: build_tools(&request.tools),
+            generation_config: GenerationConfig {
+                temperature: self.temperature,
+                top_k: 40,
+                top_p: 0.8,
+                max_output_tokens: self.max_output_tokens,
+                response_mime_type: json_schema.map(|_| "application/json"),
+                response_schema: json_schema.map(to_gemini_schema),
+            },
+            safety_settings: vec![
+                SafetySetting {
+                    category: "HARM_CATEGORY_HARASSMENT".to_string(),
+                    threshold: "BLOCK_MEDIUM_AND_ABOVE".to_string(),
+                },
+                SafetySetting {
+                    category: "HARM_CATEGORY_HATE_SPEECH".to_string(),
+                    threshold: "BLOCK_MEDIUM_AND_ABOVE".to_string(),
+                },
+                SafetySetting {
+                    category: "HARM_CATEGORY_SEXUALLY_EXPLICIT".to_string(),
+                    threshold: "BLOCK_MEDIUM_AND_ABOVE".to_string(),
+                },
+                SafetySetting {
+                    category: "HARM_CATEGORY_DANGEROUS_CONTENT".to_string(),
+                    threshold: "BLOCK_MEDIUM_AND_ABOVE".to_string(),
+                },
+            ],
+        }
+    }
+
+    pub async fn generate_response(&self, request: &LlmRequest) -> Result<LlmResponse, LlmError> {
+        let request = self.build_request(request);
+        let url = format!("{}:generateContent?key={}", self.model_url, self.api_key);
+        
+        let response = self.client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(format!("Gemini API error: {}", error_text).into());
+        }
+
+        let api_response: GeminiApiResponse = response.json().await?;
+
+        // Extract the response content
+        let mut response = LlmResponse {
+            content: String::new(),
+            finish_reason: None,
+            safety_ratings: None,
+            tool_calls: Vec::new(),
+            usage: api_response.usage_metadata.map(TokenUsage::from),
+        };
+        if let Some(candidate) = api_response.candidates.and_then(|c| c.into_iter().next()) {
+            response.finish_reason = candidate.finish_reason;
+            response.safety_ratings = candidate.safety_ratings;
+            collect_parts(candidate.content, &mut response);
+        }
+        if let Some(feedback) = api_response.prompt_feedback {
+            feedback.apply_block(&mut response);
+        }
+
+        checked(response)
+    }
+
+    /// Stream a response via `streamGenerateContent`, sending each text chunk to `chunks`
+    pub async fn generate_response_stream(&self, request: &LlmRequest, chunks: LlmChunkSender) -> Result<LlmResponse, LlmError> {
+        let request = self.build_request(request);
+        let url = format!("{}:streamGenerateContent?alt=sse&key={}", self.model_url, self.api_key);
+
+        let mut response = self.client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(format!("Gemini API error: {}", error_text).into());
+        }
+
+        let mut decoder = SseDecoder::new();
+        // Set once the reply trips the content filter, safety ratings or leak check
+        let mut withheld = false;
+        let mut streamed = LlmResponse {
+            content: String::new(),
+            finish_reason: None,
+            safety_ratings: None,
+            tool_calls: Vec::new(),
+            usage: None,
+        };
+
+        loop {
+            let (events, done) = match response.chunk().await? {
+                Some(bytes) => (decoder.push(&bytes), false),
+                None => (decoder.finish().into_iter().collect(), true),
+            };
+
+            for event in events {
+                // Each event is a partial `GenerateContentResponse`
+                let partial: GeminiApiResponse = serde_json::from_str(&event)?;
+                // Counts are cumulative; the last chunk has the totals
+                if let Some(usage) = partial.usage_metadata {
+                    streamed.usage = Some(usage.into());
+                }
+                if let Some(feedback) = partial.prompt_feedback {
+                    feedback.apply_block(&mut streamed);
+                }
+                let Some(candidate) = partial.candidates.and_then(|c| c.into_iter().next()) else {
+                    continue;
+                };
+                let before = streamed.content.len();
+                collect_parts(candidate.content, &mut streamed);
+                if candidate.finish_reason.is_some() {
+                    streamed.finish_reason = candidate.finish_reason;
+                }
+                if candidate.safety_ratings.is_some() {
+                    streamed.safety_ratings = candidate.safety_ratings;
+                }
+                // Flagged text is never previewed; the caller refuses the full reply
+                withheld = withheld || moderation::review_output(&streamed).is_some();
+                if streamed.content.len() > before && !withheld {
+                    // The receiver may already be gone; keep collecting the full response
+                    let _ = chunks.send(streamed.content[before..].to_string());
+                }
+            }
+
+            if done {
+                break;
+            }
+        }
+
+        checked(streamed)
+    }
+
+    /// Test connection to Gemini API
+    pub async fn test_connection(&self) -> Result<bool, LlmError> {
+        match self.generate_response(&LlmRequest::from_prompt("Hello, respond with 'OK' if you can hear me.")).await {
+            Ok(_) => Ok(true),
+            Err(_) => Ok(false),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for GeminiClient {
+    fn name(&self) -> &'static str {
+        "gemini"
+    }
+
+    async fn generate(&self, request: &LlmRequest) -> Result<LlmResponse, LlmError> {
+        self.generate_response(request).await
+    }
+
+    async fn generate_stream(&self, request: &LlmRequest, chunks: LlmChunkSender) -> Result<LlmResponse, LlmError> {
+        self.generate_response_stream(request, chunks).await
+    }
+}
+
+/// A reply needs text or function calls, unless it was blocked; blocked replies
+/// come back empty for `moderation::review_output` to refuse
+fn checked(response: LlmResponse) -> Result<LlmResponse, LlmError> {
+    if response.content.is_empty() && response.tool_calls.is_empty() && moderation::review_output(&response).is_none() {
+        return Err("No valid response from Gemini API".into());
+    }
+    Ok(response)
+}
+
+/// Map conversation turns to Gemini `contents`, merging consecutive turns from the
+/// same speaker since the API expects user and model turns to alternate
+fn build_contents(messages: &[LlmMessage]) -> Vec<Content> {
+    let mut contents: Vec<Content> = Vec::new();
+    for message in messages {
+        let role = match message.role {
+            LlmRole::User => "user",
+            LlmRole::Assistant => "model",
+        };
+        match contents.last_mut() {
+            Some(last) if last.role == Some(role) => {
+                last.parts.push(Part { text: message.content.clone() });
+            }
+            _ => contents.push(Content {
+                role: Some(role),
+                parts: vec![Part { text: message.content.clone() }],
+            }),
+        }
+    }
+    contents
+}
+
+/// Append the text and function calls of a candidate's parts to `response`
+fn collect_parts(content: Option<ContentResponse>, response: &mut LlmResponse) {
+    for part in content.map(|c| c.parts).unwrap_or_default() {
+        if let Some(text) = part.text {
+            response.content.push_str(&text);
+        }
+        if let Some(call) = part.function_call {
+            response.tool_calls.push(LlmToolCall {
+                name: call.name,
+                arguments: call.args,
+            });
+        }
+    }
+}
+
+/// Declare all tools in a single `functionDeclarations` block
+fn build_tools(tools: &[LlmTool]) -> Vec<GeminiTool> {
+    if tools.is_empty() {
+        return Vec::new();
+    }
+    vec![GeminiTool {
+        function_declarations: tools
+            .iter()
+            .map(|tool| FunctionDeclaration {
+                name: tool.name.clone(),
+                description: tool.description.clone(),
+                parameters: to_gemini_schema(&tool.parameters),
+            })
+            .collect(),
+    }]
+}
+
+/// Gemini's schema dialect spells types in upper case (`STRING`, `OBJECT`, ...)
+fn to_gemini_schema(schema: &serde_json::Value) -> serde_json::Value {
+    match schema {
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.iter()
+                .map(|(key, value)| {
+                    let value = match (key.as_str(), value) {
+                        ("type", serde_json::Value::String(t)) => serde_json::Value::String(t.to_uppercase()),
+                        _ => to_gemini_schema(value),
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        serde_json::Value::Array(items) => serde_json::Value::Array(items.iter().map(to_gemini_schema).collect()),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::provider::test_support::mock_http_server;
+    use crate::ai::provider::ProviderKind;
+
+    fn config(base_url: String) -> LlmConfig {
+        LlmConfig {
+            provider: ProviderKind::Gemini,
+            model: "gemini-test".to_string(),
+            base_url,
+            api_key: "secret".to_string(),
+            temperature: 0.3,
+            max_tokens: 256,
+        }
+    }
+
+    #[test]
+    fn requires_api_key() {
+        let mut cfg = config("http://localhost".to_string());
+        cfg.api_key.clear();
+        assert!(GeminiClient::new(&cfg).is_err());
+    }
+
+    #[tokio::test]
+    async fn parses_candidate_and_sends_configured_model() {
+        let body = r#"{"candidates":[{"content":{"role":"model","parts":[{"text":"hello"}]},"finishReason":"STOP","safetyRatings":[{"category":"HARM_CATEGORY_HARASSMENT","probability":"NEGLIGIBLE"}]}],"usageMetadata":{"promptTokenCount":12,"candidatesTokenCount":3,"totalTokenCount":15}}"#;
+        let (base_url, server) = mock_http_server(200, body).await;
+        let client = GeminiClient::new(&config(base_url)).unwrap();
+
+        let response = client.generate(&LlmRequest::from_prompt("hi")).await.unwrap();
+        assert_eq!(response.content, "hello");
+        assert_eq!(response.finish_reason.as_deref(), Some("STOP"));
+        assert_eq!(response.safety_ratings.unwrap().len(), 1);
+        assert_eq!(response.usage, Some(TokenUsage { prompt_tokens: 12, response_tokens: 3 }));
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /models/gemini-test:generateContent?key=secret"));
+        assert!(request.contains(r#""maxOutputTokens":256"#));
+        assert!(!request.contains("responseMimeType"));
+        assert!(!request.contains("systemInstruction"));
+    }
+
+    #[tokio::test]
+    async fn sends_system_instruction_turns_and_schema() {
+        let body = r#"{"candidates":[{"content":{"parts":[{"text":"{}"}]}}]}"#;
+        let (base_url, server) = mock_http_server(200, body).await;
+        let client = GeminiClient::new(&config(base_url)).unwrap();
+
+        let request = LlmRequest {
+            messages: vec![
+                LlmMessage::new(LlmRole::User, "hi"),
+                LlmMessage::new(LlmRole::Assistant, "hello"),
+                LlmMessage::new(LlmRole::User, "how are you?"),
+            ],
+            ..LlmRequest::default()
+        }
+        .system_instruction("be brief")
+        .response_schema(serde_json::json!({"type": "object", "properties": {"content": {"type": "string"}}}));
+        client.generate(&request).await.unwrap();
+
+        let raw = server.await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&raw[raw.find("\r\n\r\n").unwrap() + 4..]).unwrap();
+        assert_eq!(body["systemInstruction"]["parts"][0]["text"], "be brief");
+        assert_eq!(body["contents"].as_array().unwrap().len(), 3);
+        assert_eq!(body["contents"][1]["role"], "model");
+        assert_eq!(body["generationConfig"]["responseMimeType"], "application/json");
+        assert_eq!(body["generationConfig"]["responseSchema"]["type"], "OBJECT");
+        assert_eq!(body["generationConfig"]["responseSchema"]["properties"]["content"]["type"], "STRING");
+    }
+
+    #[test]
+    fn consecutive_turns_from_same_speaker_are_merged() {
+        let contents = build_contents(&[
+            LlmMessage::new(LlmRole::User, "one"),
+            LlmMessage::new(LlmRole::User, "two"),
+            LlmMessage::new(LlmRole::Assistant, "three"),
+        ]);
+        assert_eq!(contents.len(), 2);
+        assert_eq!(contents[0].role, Some("user"));
+        assert_eq!(contents[0].parts.len(), 2);
+        assert_eq!(contents[1].role, Some("model"));
+    }
+
+    #[tokio::test]
+    async fn declares_tools_and_parses_function_calls() {
+        let body = r#"{"candidates":[{"content":{"role":"model","parts":[{"functionCall":{"name":"create_reminder","args":{"message":"stretch","minutes":30}}}]},"finishReason":"STOP"}]}"#;
+        let (base_url, server) = mock_http_server(200, body).await;
+        let client = GeminiClient::new(&config(base_url)).unwrap();
+
+        let request = LlmRequest::from_prompt("remind me to stretch in 30 minutes")
+            .response_schema(serde_json::json!({"type": "object"}))
+            .tools(vec![LlmTool {
+                name: "create_reminder".to_string(),
+                description: "Create a reminder".to_string(),
+                parameters: serde_json::json!({"type": "object", "properties": {"minutes": {"type": "integer"}}}),
+            }]);
+        let response = client.generate(&request).await.unwrap();
+        assert!(response.content.is_empty());
+        assert_eq!(response.tool_calls.len(), 1);
+        assert_eq!(response.tool_calls[0].name, "create_reminder");
+        assert_eq!(response.tool_calls[0].arguments["minutes"], 30);
+
+        let raw = server.await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&raw[raw.find("\r\n\r\n").unwrap() + 4..]).unwrap();
+        let declaration = &body["tools"][0]["functionDeclarations"][0];
+        assert_eq!(declaration["name"], "create_reminder");
+        assert_eq!(declaration["parameters"]["properties"]["minutes"]["type"], "INTEGER");
+        // JSON mode can't be combined with function calling
+        assert!(body["generationConfig"].get("responseMimeType").is_none());
+    }
+
+    #[tokio::test]
+    async fn streams_chunks_and_concatenates_them() {
+        let body = concat!(
+            "data: {\"candidates\":[{\"content\":{\"parts\":[{\"text\":\"Hel\"}]}}]}\r\n\r\n",
+            "data: {\"candidates\":[{\"content\":{\"parts\":[{\"text\":\"lo\"}]},\"finishReason\":\"STOP\"}],\"usageMetadata\":{\"promptTokenCount\":4,\"candidatesTokenCount\":2}}\r\n\r\n",
+        );
+        let (base_url, server) = mock_http_server(200, body).await;
+        let client = GeminiClient::new(&config(base_url)).unwrap();
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let response = client.generate_stream(&LlmRequest::from_prompt("hi"), tx).await.unwrap();
+        assert_eq!(response.content, "Hello");
+        assert_eq!(response.finish_reason.as_deref(), Some("STOP"));
+        assert_eq!(response.usage, Some(TokenUsage { prompt_tokens: 4, response_tokens: 2 }));
+        assert_eq!(rx.recv().await.as_deref(), Some("Hel"));
+        assert_eq!(rx.recv().await.as_deref(), Some("lo"));
+        assert_eq!(rx.recv().await, None);
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /models/gemini-test:streamGenerateContent?alt=sse&key=secret"));
+    }
+
+    #[tokio::test]
+    async fn stops_streaming_chunks_once_the_reply_is_flagged() {
+        let body = concat!(
+            "data: {\"candidates\":[{\"content\":{\"parts\":[{\"text\":\"Fine \"}]}}]}\r\n\r\n",
+            "data: {\"candidates\":[{\"content\":{\"parts\":[{\"text\":\"so far\"}]},\"safetyRatings\":[{\"category\":\"HARM_CATEGORY_HARASSMENT\",\"probability\":\"HIGH\"}]}]}\r\n\r\n",
+            "data: {\"candidates\":[{\"content\":{\"parts\":[{\"text\":\" and more\"}]},\"finishReason\":\"SAFETY\"}]}\r\n\r\n",
+        );
+        let (base_url, _server) = mock_http_server(200, body).await;
+        let client = GeminiClient::new(&config(base_url)).unwrap();
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let response = client.generate_stream(&LlmRequest::from_prompt("hi"), tx).await.unwrap();
+        assert_eq!(response.content, "Fine so far and more");
+        assert_eq!(rx.recv().await.as_deref(), Some("Fine "));
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn blocked_replies_come_back_empty_with_their_reason() {
+        let body = r#"{"candidates":[{"finishReason":"SAFETY","index":0,"safetyRatings":[{"category":"HARM_CATEGORY_SEXUALLY_EXPLICIT","probability":"NEGLIGIBLE"},{"category":"HARM_CATEGORY_HATE_SPEECH","probability":"HIGH"}]}],"usageMetadata":{"promptTokenCount":20,"totalTokenCount":20}}"#;
+        let (base_url, _server) = mock_http_server(200, body).await;
+        let client = GeminiClient::new(&config(base_url)).unwrap();
+
+        let response = client.generate(&LlmRequest::from_prompt("hi")).await.unwrap();
+        assert!(response.content.is_empty());
+        assert_eq!(response.finish_reason.as_deref(), Some("SAFETY"));
+        let flag = moderation::review_output(&response).unwrap();
+        assert!(flag.reason.contains("SAFETY"));
+    }
+
+    #[tokio::test]
+    async fn blocked_prompts_come_back_empty_with_their_reason() {
+        let body = "data: {\"promptFeedback\":{\"blockReason\":\"SAFETY\",\"safetyRatings\":[{\"category\":\"HARM_CATEGORY_HARASSMENT\",\"probability\":\"HIGH\"}]},\"usageMetadata\":{\"promptTokenCount\":8,\"totalTokenCount\":8}}\r\n\r\n";
+        let (base_url, _server) = mock_http_server(200, body).await;
+        let client = GeminiClient::new(&config(base_url)).unwrap();
+
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let response = client.generate_stream(&LlmRequest::from_prompt("hi"), tx).await.unwrap();
+        assert!(response.content.is_empty());
+        assert_eq!(response.finish_reason.as_deref(), Some("SAFETY"));
+        assert!(moderation::review_output(&response).is_some());
+    }
+
+    #[tokio::test]
+    async fn empty_unblocked_replies_are_errors() {
+        let (base_url, _server) = mock_http_server(200, r#"{"candidates":[{"finishReason":"STOP"}]}"#).await;
+        let client = GeminiClient::new(&config(base_url)).unwrap();
+        assert!(client.generate(&LlmRequest::from_prompt("hi")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn surfaces_api_errors() {
+        let (base_url, _server) = mock_http_server(400, r#"{"error":"bad request"}"#).await;
+        let client = GeminiClient::new(&config(base_url)).unwrap();
+
+        let err = client.generate(&LlmRequest::from_prompt("hi")).await.unwrap_err();
+        assert!(err.to_string().contains("Gemini API error"));
+    }
+}
@@ -0,0 +1,397 @@
+//! Local knowledge base for retrieval-augmented answers.
+//!
+//! Markdown and TOML documents in the knowledge directory (FAQ, pricing,
+//! portfolio, policies, ...) are split into passages and indexed with BM25.
+//! The best passages for a user message are injected into the system
+//! instruction, labelled with their source so answers can cite them.
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// BM25 term-frequency saturation
+const K1: f64 = 1.2;
+/// BM25 document-length normalization
+const B: f64 = 0.75;
+/// Passages longer than this are split at paragraph boundaries
+const MAX_PASSAGE_CHARS: usize = 800;
+
+/// Common words that carry no meaning for retrieval (English and Spanish)
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "can", "do", "does", "for", "from",
+    "have", "how", "i", "if", "in", "is", "it", "me", "my", "of", "on", "or", "so", "that", "the",
+    "this", "to", "was", "what", "when", "where", "which", "who", "why", "will", "with", "you",
+    "your", "de", "el", "la", "los", "las", "un", "una", "y", "o", "en", "que", "es", "por", "para",
+    "con", "del", "al", "se", "lo", "mi", "tu",
+];
+
+/// A chunk of a knowledge document
+#[derive(Debug, Clone, PartialEq)]
+pub struct Passage {
+    /// Citation label, e.g. `pricing.md › Rates`
+    pub source: String,
+    pub text: String,
+}
+
+/// A passage matching a query
+#[derive(Debug, Clone, Copy)]
+pub struct KnowledgeHit<'a> {
+    pub passage: &'a Passage,
+    pub score: f64,
+}
+
+#[derive(Debug)]
+struct IndexedPassage {
+    passage: Passage,
+    term_freqs: HashMap<String, u32>,
+    length: usize,
+}
+
+/// BM25 index over the knowledge passages
+#[derive(Debug, Default)]
+pub struct KnowledgeBase {
+    passages: Vec<IndexedPassage>,
+    doc_freqs: HashMap<String, usize>,
+    avg_length: f64,
+    documents: usize,
+}
+
+impl KnowledgeBase {
+    /// Index every `.md` and `.toml` file under `dir` (a `README.md` is skipped)
+    pub fn load_dir(dir: &Path) -> Result<Self, Box<dyn Error>> {
+        let mut files = Vec::new();
+        collect_files(dir, &mut files)?;
+        files.sort();
+
+        let mut passages = Vec::new();
+        let mut documents = 0;
+        for path in files {
+            let name = path.strip_prefix(dir).unwrap_or(&path).to_string_lossy().replace('\\', "/");
+            let content = fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read knowledge file '{}': {}", path.display(), e))?;
+            let parsed = match path.extension().and_then(|e| e.to_str()) {
+                Some("md") => parse_markdown(&name, &content),
+                Some("toml") => parse_toml(&name, &content)?,
+                _ => continue,
+            };
+            documents += 1;
+            passages.extend(parsed);
+        }
+
+        let mut base = Self::build(passages);
+        base.documents = documents;
+        Ok(base)
+    }
+
+    /// Index already-split passages
+    pub fn build(passages: Vec<Passage>) -> Self {
+        let passages: Vec<IndexedPassage> = passages
+            .into_iter()
+            .map(|passage| {
+                // The source label is searchable too ("pricing", "faq", ...)
+                let tokens = tokenize(&format!("{} {}", passage.source, passage.text));
+                let mut term_freqs = HashMap::new();
+                for token in &tokens {
+                    *term_freqs.entry(token.clone()).or_insert(0) += 1;
+                }
+                IndexedPassage {
+                    passage,
+                    term_freqs,
+                    length: tokens.len(),
+                }
+            })
+            .collect();
+
+        let mut doc_freqs = HashMap::new();
+        for indexed in &passages {
+            for term in indexed.term_freqs.keys() {
+                *doc_freqs.entry(term.clone()).or_insert(0) += 1;
+            }
+        }
+        let avg_length = if passages.is_empty() {
+            0.0
+        } else {
+            passages.iter().map(|p| p.length).sum::<usize>() as f64 / passages.len() as f64
+        };
+
+        Self {
+            passages,
+            doc_freqs,
+            avg_length,
+            documents: 0,
+        }
+    }
+
+    pub fn passage_count(&self) -> usize {
+        self.passages.len()
+    }
+
+    pub fn document_count(&self) -> usize {
+        self.documents
+    }
+
+    /// Best passages for `query`, highest score first; passages sharing no terms are left out
+    pub fn search(&self, query: &str, limit: usize) -> Vec<KnowledgeHit<'_>> {
+        let terms: HashSet<String> = tokenize(query).into_iter().collect();
+        if terms.is_empty() || self.passages.is_empty() {
+            return Vec::new();
+        }
+
+        let total = self.passages.len() as f64;
+        let mut hits: Vec<KnowledgeHit<'_>> = self
+            .passages
+            .iter()
+            .filter_map(|indexed| {
+                let length_norm = 1.0 - B + B * indexed.length as f64 / self.avg_length.max(1.0);
+                let score: f64 = terms
+                    .iter()
+                    .filter_map(|term| {
+                        let tf = *indexed.term_freqs.get(term)? as f64;
+                        let df = *self.doc_freqs.get(term)? as f64;
+                        let idf = (1.0 + (total - df + 0.5) / (df + 0.5)).ln();
+                        Some(idf * tf * (K1 + 1.0) / (tf + K1 * length_norm))
+                    })
+                    .sum();
+                (score > 0.0).then_some(KnowledgeHit {
+                    passage: &indexed.passage,
+                    score,
+                })
+            })
+            .collect();
+
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(limit);
+        hits
+    }
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<std::path::PathBuf>) -> Result<(), Box<dyn Error>> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else if !path
+            .file_name()
+            .is_some_and(|name| name.eq_ignore_ascii_case("README.md"))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Lowercased alphanumeric words without stopwords, plurals folded ("refunds" → "refund")
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .filter(|word| word.chars().count() > 1 && !STOPWORDS.contains(&word.as_str()))
+        .map(|word| match word.strip_suffix('s') {
+            Some(stem) if stem.chars().count() > 2 && !stem.ends_with('s') => stem.to_string(),
+            _ => word,
+        })
+        .collect()
+}
+
+/// Split a Markdown document into passages at headings, then at paragraphs when long
+pub fn parse_markdown(name: &str, content: &str) -> Vec<Passage> {
+    let mut passages = Vec::new();
+    let mut heading: Option<String> = None;
+    let mut section = String::new();
+
+    let mut flush = |heading: &Option<String>, section: &mut String| {
+        let source = match heading {
+            Some(heading) => format!("{} › {}", name, heading),
+            None => name.to_string(),
+        };
+        for text in split_paragraphs(section) {
+            passages.push(Passage {
+                source: source.clone(),
+                text,
+            });
+        }
+        section.clear();
+    };
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('#') {
+            let title = trimmed.trim_start_matches('#').trim();
+            if !title.is_empty() {
+                flush(&heading, &mut section);
+                heading = Some(title.to_string());
+                continue;
+            }
+        }
+        section.push_str(line);
+        section.push('\n');
+    }
+    flush(&heading, &mut section);
+
+    passages
+}
+
+/// Group paragraphs into chunks of at most `MAX_PASSAGE_CHARS` (a longer paragraph stays whole)
+fn split_paragraphs(section: &str) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for paragraph in section.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+        if !current.is_empty() && current.len() + paragraph.len() + 2 > MAX_PASSAGE_CHARS {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push_str("\n\n");
+        }
+        current.push_str(paragraph);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// One passage per top-level table (or per entry of an array of tables)
+pub fn parse_toml(name: &str, content: &str) -> Result<Vec<Passage>, Box<dyn Error>> {
+    let document: toml::Table = content
+        .parse()
+        .map_err(|e| format!("Failed to parse knowledge file '{}': {}", name, e))?;
+
+    let mut passages = Vec::new();
+    let mut root_lines = Vec::new();
+    for (key, value) in &document {
+        match value {
+            toml::Value::Table(table) => passages.push(Passage {
+                source: format!("{} › {}", name, key),
+                text: flatten_table(table, ""),
+            }),
+            toml::Value::Array(items) if items.iter().all(|item| item.is_table()) => {
+                for (index, item) in items.iter().enumerate() {
+                    let table = item.as_table().expect("checked above");
+                    let label = ["title", "question", "name"]
+                        .iter()
+                        .find_map(|field| table.get(*field).and_then(|v| v.as_str()))
+                        .map(String::from)
+                        .unwrap_or_else(|| format!("{} {}", key, index + 1));
+                    passages.push(Passage {
+                        source: format!("{} › {}", name, label),
+                        text: flatten_table(table, ""),
+                    });
+                }
+            }
+            other => root_lines.push(format!("{}: {}", key, value_text(other))),
+        }
+    }
+    if !root_lines.is_empty() {
+        passages.insert(0, Passage {
+            source: name.to_string(),
+            text: root_lines.join("\n"),
+        });
+    }
+
+    Ok(passages)
+}
+
+/// `key: value` lines, nested keys dotted
+fn flatten_table(table: &toml::Table, prefix: &str) -> String {
+    let mut lines = Vec::new();
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            toml::Value::Table(inner) => lines.push(flatten_table(inner, &key)),
+            other => lines.push(format!("{}: {}", key, value_text(other))),
+        }
+    }
+    lines.join("\n")
+}
+
+fn value_text(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        toml::Value::Array(items) => items.iter().map(value_text).collect::<Vec<_>>().join(", "),
+        toml::Value::Table(table) => flatten_table(table, ""),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_is_split_at_headings() {
+        let passages = parse_markdown(
+            "pricing.md",
+            "Intro line.\n\n# Pricing\n\n## Rates\nSketch: $20\n\nLineart: $35\n\n## Refunds\nNo refunds after work starts.\n",
+        );
+        assert_eq!(passages.len(), 3);
+        assert_eq!(passages[0].source, "pricing.md");
+        assert_eq!(passages[1].source, "pricing.md › Rates");
+        assert_eq!(passages[1].text, "Sketch: $20\n\nLineart: $35");
+        assert_eq!(passages[2].source, "pricing.md › Refunds");
+    }
+
+    #[test]
+    fn long_sections_are_chunked_by_paragraph() {
+        let paragraph = "word ".repeat(100);
+        let content = format!("# Big\n{}\n\n{}\n\n{}", paragraph, paragraph, paragraph);
+        let passages = parse_markdown("big.md", &content);
+        assert!(passages.len() > 1);
+        assert!(passages.iter().all(|p| p.source == "big.md › Big"));
+    }
+
+    #[test]
+    fn toml_tables_and_entries_become_passages() {
+        let passages = parse_toml(
+            "faq.toml",
+            r#"
+updated = "2026-01"
+
+[turnaround]
+standard = "2 weeks"
+rush = { days = 5, fee = "+50%" }
+
+[[faq]]
+question = "Do you do NSFW?"
+answer = "No."
+"#,
+        )
+        .unwrap();
+        assert_eq!(passages[0].source, "faq.toml");
+        assert_eq!(passages[0].text, "updated: 2026-01");
+        let turnaround = passages.iter().find(|p| p.source == "faq.toml › turnaround").unwrap();
+        assert!(turnaround.text.contains("rush.days: 5"));
+        assert!(passages.iter().any(|p| p.source == "faq.toml › Do you do NSFW?" && p.text.contains("answer: No.")));
+    }
+
+    #[test]
+    fn search_ranks_matching_passages() {
+        let base = KnowledgeBase::build(vec![
+            Passage { source: "pricing.md › Rates".to_string(), text: "A full illustration costs $120.".to_string() },
+            Passage { source: "policies.md › Refunds".to_string(), text: "Refunds are possible before the sketch stage.".to_string() },
+            Passage { source: "portfolio.md".to_string(), text: "Recent work includes bots and websites.".to_string() },
+        ]);
+
+        let hits = base.search("How much does an illustration cost?", 2);
+        assert_eq!(hits[0].passage.source, "pricing.md › Rates");
+        assert!(hits.len() <= 2);
+
+        let hits = base.search("can I get a refund?", 3);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].passage.source, "policies.md › Refunds");
+    }
+
+    #[test]
+    fn stopword_only_queries_match_nothing() {
+        let base = KnowledgeBase::build(vec![Passage {
+            source: "faq.md".to_string(),
+            text: "What is the turnaround?".to_string(),
+        }]);
+        assert!(base.search("what is the", 3).is_empty());
+        assert!(KnowledgeBase::default().search("turnaround", 3).is_empty());
+    }
+}
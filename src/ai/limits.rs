@@ -0,0 +1,241 @@
+//! Throttling and cost accounting for AI replies.
+//!
+//! Every AI message costs a model call, so users are rate limited (a cooldown
+//! between messages plus a global per-minute cap) before anything is sent to
+//! the provider. Daily quotas are persisted with the usage data (see
+//! `data::ai_usage_data`); this module only holds the in-memory limiter and
+//! the configuration read from the environment.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use super::provider::TokenUsage;
+
+/// Window of the global rate limit
+const GLOBAL_WINDOW: Duration = Duration::from_secs(60);
+
+/// Rate limit and pricing configuration
+///
+/// Read from environment variables:
+/// - `AI_USER_COOLDOWN_SECS`: minimum time between a user's AI messages (default 5, 0 disables)
+/// - `AI_GLOBAL_PER_MINUTE`: AI replies per minute across all users (default 30, 0 disables)
+/// - `AI_PROMPT_COST_PER_MTOK`: USD per million prompt tokens (default 0.075)
+/// - `AI_RESPONSE_COST_PER_MTOK`: USD per million response tokens (default 0.30)
+///
+/// Invalid values are logged and replaced by the default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AILimitsConfig {
+    pub user_cooldown: Duration,
+    pub global_per_minute: u32,
+    pub prompt_cost_per_mtok: f64,
+    pub response_cost_per_mtok: f64,
+}
+
+impl Default for AILimitsConfig {
+    fn default() -> Self {
+        Self {
+            user_cooldown: Duration::from_secs(5),
+            global_per_minute: 30,
+            prompt_cost_per_mtok: 0.075,
+            response_cost_per_mtok: 0.30,
+        }
+    }
+}
+
+impl AILimitsConfig {
+    /// Build the configuration from process environment variables
+    pub fn from_env() -> Self {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Build the configuration from an arbitrary key lookup (used by tests)
+    pub fn from_lookup<F>(lookup: F) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let defaults = Self::default();
+        let get = |key: &str| lookup(key).map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+        fn parse_or<T: std::str::FromStr + Copy + std::fmt::Debug>(key: &str, raw: Option<String>, default: T, valid: impl Fn(T) -> bool) -> T {
+            match raw {
+                Some(raw) => match raw.parse::<T>() {
+                    Ok(value) if valid(value) => value,
+                    _ => {
+                        eprintln!("⚠️  Invalid {} {:?}, using {:?}", key, raw, default);
+                        default
+                    }
+                },
+                None => default,
+            }
+        }
+
+        let cooldown_secs = parse_or("AI_USER_COOLDOWN_SECS", get("AI_USER_COOLDOWN_SECS"), defaults.user_cooldown.as_secs(), |_| true);
+
+        Self {
+            user_cooldown: Duration::from_secs(cooldown_secs),
+            global_per_minute: parse_or("AI_GLOBAL_PER_MINUTE", get("AI_GLOBAL_PER_MINUTE"), defaults.global_per_minute, |_| true),
+            prompt_cost_per_mtok: parse_or("AI_PROMPT_COST_PER_MTOK", get("AI_PROMPT_COST_PER_MTOK"), defaults.prompt_cost_per_mtok, |v: f64| v >= 0.0),
+            response_cost_per_mtok: parse_or("AI_RESPONSE_COST_PER_MTOK", get("AI_RESPONSE_COST_PER_MTOK"), defaults.response_cost_per_mtok, |v: f64| v >= 0.0),
+        }
+    }
+
+    /// Estimated cost in USD of the given token counts
+    pub fn estimate_cost(&self, usage: TokenUsage) -> f64 {
+        (usage.prompt_tokens as f64 * self.prompt_cost_per_mtok
+            + usage.response_tokens as f64 * self.response_cost_per_mtok)
+            / 1_000_000.0
+    }
+}
+
+/// Why a message was not sent to the model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimited {
+    /// The user sent another message before their cooldown ended
+    UserCooldown { retry_after: Duration, notify: bool },
+    /// The bot-wide per-minute cap is reached
+    Global { retry_after: Duration, notify: bool },
+}
+
+impl RateLimited {
+    pub fn retry_after(&self) -> Duration {
+        match self {
+            RateLimited::UserCooldown { retry_after, .. } | RateLimited::Global { retry_after, .. } => *retry_after,
+        }
+    }
+
+    /// Only the first limited message in a row gets a notice; the rest are ignored quietly
+    pub fn should_notify(&self) -> bool {
+        match self {
+            RateLimited::UserCooldown { notify, .. } | RateLimited::Global { notify, .. } => *notify,
+        }
+    }
+}
+
+/// In-memory per-user cooldown and global sliding-window limiter
+#[derive(Debug)]
+pub struct RateLimiter {
+    user_cooldown: Duration,
+    global_per_minute: u32,
+    last_request: HashMap<u64, Instant>,
+    notified: HashSet<u64>,
+    recent: VecDeque<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(config: &AILimitsConfig) -> Self {
+        Self {
+            user_cooldown: config.user_cooldown,
+            global_per_minute: config.global_per_minute,
+            last_request: HashMap::new(),
+            notified: HashSet::new(),
+            recent: VecDeque::new(),
+        }
+    }
+
+    /// Check and, when allowed, record a request from `user_id` at `now`
+    ///
+    /// Rejected messages don't extend the cooldown.
+    pub fn check(&mut self, user_id: u64, now: Instant) -> Result<(), RateLimited> {
+        let cooldown = self.user_cooldown;
+        self.last_request.retain(|_, at| now.duration_since(*at) < cooldown);
+        while self.recent.front().is_some_and(|at| now.duration_since(*at) >= GLOBAL_WINDOW) {
+            self.recent.pop_front();
+        }
+
+        if let Some(last) = self.last_request.get(&user_id) {
+            return Err(RateLimited::UserCooldown {
+                retry_after: cooldown - now.duration_since(*last),
+                notify: self.notified.insert(user_id),
+            });
+        }
+
+        if self.global_per_minute > 0 && self.recent.len() >= self.global_per_minute as usize {
+            let oldest = self.recent.front().copied().unwrap_or(now);
+            return Err(RateLimited::Global {
+                retry_after: GLOBAL_WINDOW.saturating_sub(now.duration_since(oldest)),
+                notify: self.notified.insert(user_id),
+            });
+        }
+
+        if !cooldown.is_zero() {
+            self.last_request.insert(user_id, now);
+        }
+        self.recent.push_back(now);
+        self.notified.remove(&user_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(cooldown_secs: u64, global_per_minute: u32) -> RateLimiter {
+        RateLimiter::new(&AILimitsConfig {
+            user_cooldown: Duration::from_secs(cooldown_secs),
+            global_per_minute,
+            ..AILimitsConfig::default()
+        })
+    }
+
+    #[test]
+    fn user_cooldown_notifies_once() {
+        let mut limiter = limiter(5, 0);
+        let start = Instant::now();
+        assert_eq!(limiter.check(1, start), Ok(()));
+
+        let limited = limiter.check(1, start + Duration::from_secs(2)).unwrap_err();
+        assert_eq!(limited, RateLimited::UserCooldown { retry_after: Duration::from_secs(3), notify: true });
+        assert!(!limiter.check(1, start + Duration::from_secs(3)).unwrap_err().should_notify());
+
+        // Other users aren't affected, and the cooldown isn't extended by rejected messages
+        assert_eq!(limiter.check(2, start + Duration::from_secs(3)), Ok(()));
+        assert_eq!(limiter.check(1, start + Duration::from_secs(5)), Ok(()));
+        assert!(limiter.check(1, start + Duration::from_secs(6)).unwrap_err().should_notify());
+    }
+
+    #[test]
+    fn global_limit_uses_sliding_window() {
+        let mut limiter = limiter(0, 2);
+        let start = Instant::now();
+        assert_eq!(limiter.check(1, start), Ok(()));
+        assert_eq!(limiter.check(2, start + Duration::from_secs(10)), Ok(()));
+
+        let limited = limiter.check(3, start + Duration::from_secs(20)).unwrap_err();
+        assert_eq!(limited, RateLimited::Global { retry_after: Duration::from_secs(40), notify: true });
+        assert_eq!(limiter.check(3, start + Duration::from_secs(60)), Ok(()));
+    }
+
+    #[test]
+    fn zero_disables_limits() {
+        let mut limiter = limiter(0, 0);
+        let now = Instant::now();
+        for _ in 0..100 {
+            assert_eq!(limiter.check(1, now), Ok(()));
+        }
+    }
+
+    #[test]
+    fn config_from_lookup_falls_back_on_invalid_values() {
+        let vars: HashMap<&str, &str> = [
+            ("AI_USER_COOLDOWN_SECS", "10"),
+            ("AI_GLOBAL_PER_MINUTE", "lots"),
+            ("AI_PROMPT_COST_PER_MTOK", "-1"),
+            ("AI_RESPONSE_COST_PER_MTOK", "0.6"),
+        ]
+        .into_iter()
+        .collect();
+        let config = AILimitsConfig::from_lookup(|key| vars.get(key).map(|v| v.to_string()));
+        assert_eq!(config.user_cooldown, Duration::from_secs(10));
+        assert_eq!(config.global_per_minute, 30);
+        assert_eq!(config.prompt_cost_per_mtok, 0.075);
+        assert_eq!(config.response_cost_per_mtok, 0.6);
+    }
+
+    #[test]
+    fn cost_is_per_million_tokens() {
+        let config = AILimitsConfig::default();
+        let cost = config.estimate_cost(TokenUsage { prompt_tokens: 2_000_000, response_tokens: 1_000_000 });
+        assert!((cost - 0.45).abs() < 1e-9);
+    }
+}
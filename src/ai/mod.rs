@@ -1,15 +1,29 @@
+pub mod budget;
 pub mod gemini;
+pub mod knowledge;
+pub mod limits;
+pub mod moderation;
+pub mod openai;
+pub mod persona;
+pub mod provider;
+pub mod streaming;
+pub mod tools;
 pub mod emotions;
 pub mod responses;
 
-pub use gemini::GeminiClient;
+pub use provider::{LlmChunkSender, LlmConfig, LlmMessage, LlmProvider, LlmRequest, LlmRole, LlmToolCall, TokenUsage};
+pub use streaming::partial_response_content;
+pub use knowledge::KnowledgeBase;
+pub use persona::{PersonaLibrary, PersonaProfile};
+pub use budget::ContextBudget;
+pub use limits::{AILimitsConfig, RateLimited, RateLimiter};
+pub use moderation::ModerationFlag;
 pub use emotions::EmotionManager;
 pub use responses::AIResponseBuilder;
 
 use crate::data::ConversationContext;
 use std::error::Error;
 use serde::{Deserialize, Serialize};
-use serde_json;
 use std::fs;
 
 /// AI JSON Response structure
@@ -18,6 +32,15 @@ pub struct AIJSONResponse {
     pub content: String,
     pub color: String,
     pub thumbnail: String,
+    /// Knowledge base sources the answer relied on
+    #[serde(default)]
+    pub sources: Vec<String>,
+}
+
+/// Running conversation summary returned when older turns are compressed
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AIConversationSummary {
+    pub summary: String,
 }
 
 /// AI Summary Analysis Response structure
@@ -29,17 +52,48 @@ pub struct AISummaryAnalysis {
     pub content: Option<String>,
 }
 
+/// Why the assistant answers a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AITrigger {
+    /// Any message in the dedicated AI channel
+    Channel,
+    /// The bot was mentioned or replied to in an allowed channel; answered in a new thread
+    Mention,
+    /// A message in a thread under an allowed channel, either one the bot opened
+    /// for an earlier mention or one where the bot was mentioned; answered in place
+    Thread,
+    /// A direct message, when DMs are enabled
+    DirectMessage,
+}
+
+/// Remove mentions of `user_id` (`<@id>` / `<@!id>`) from a message
+pub fn strip_mention(content: &str, user_id: u64) -> String {
+    content
+        .replace(&format!("<@{}>", user_id), "")
+        .replace(&format!("<@!{}>", user_id), "")
+        .trim()
+        .to_string()
+}
+
 /// Configuration for the AI system
 #[derive(Debug, Clone)]
 pub struct AIConfig {
-    /// Gemini API key
-    pub api_key: String,
+    /// LLM provider, model and sampling settings
+    pub llm: LlmConfig,
     /// Channel ID where AI responds
     pub ai_channel_id: String,
     /// Owner/creator information for context
     pub owner_info: OwnerInfo,
-    /// Maximum context length for conversations
-    pub max_context_length: usize,
+    /// Token budget for the prompt and conversation history
+    pub context_budget: ContextBudget,
+    /// Directory of Markdown/TOML documents indexed for retrieval
+    pub knowledge_dir: std::path::PathBuf,
+    /// Rate limits and token pricing
+    pub limits: AILimitsConfig,
+    /// Directory of persona profiles (`<name>.toml`)
+    pub persona_dir: std::path::PathBuf,
+    /// Persona used by channels without one
+    pub default_persona: String,
 }
 
 /// Structure for loading owner info from TOML file
@@ -167,10 +221,19 @@ impl Default for OwnerInfo {
 impl Default for AIConfig {
     fn default() -> Self {
         Self {
-            api_key: std::env::var("GEMINI_API_KEY").unwrap_or_default(),
+            llm: LlmConfig::from_env(),
             ai_channel_id: "1400493466080903171".to_string(),
             owner_info: OwnerInfo::default(),
-            max_context_length: 15,
+            context_budget: ContextBudget::from_env(),
+            knowledge_dir: std::env::var("KNOWLEDGE_DIR")
+                .unwrap_or_else(|_| "knowledge".to_string())
+                .into(),
+            limits: AILimitsConfig::from_env(),
+            persona_dir: std::env::var("PERSONA_DIR")
+                .unwrap_or_else(|_| "personas".to_string())
+                .into(),
+            default_persona: std::env::var("AI_DEFAULT_PERSONA")
+                .unwrap_or_else(|_| persona::BUILTIN_NAME.to_string()),
         }
     }
 }
@@ -178,19 +241,44 @@ impl Default for AIConfig {
 /// Main AI manager that handles conversation flow
 pub struct AIManager {
     config: AIConfig,
-    gemini_client: GeminiClient,
+    provider: Box<dyn LlmProvider>,
     emotion_manager: EmotionManager,
+    knowledge: KnowledgeBase,
+    personas: PersonaLibrary,
+    rate_limiter: std::sync::Mutex<RateLimiter>,
 }
 
 impl AIManager {
     pub fn new(config: AIConfig) -> Result<Self, Box<dyn Error>> {
-        let gemini_client = GeminiClient::new(config.api_key.clone())?;
+        let provider = provider::build_provider(&config.llm)?;
+        println!("🤖 AI provider: {} ({})", provider.name(), config.llm.model);
         let emotion_manager = EmotionManager::new();
+        let knowledge = if config.knowledge_dir.is_dir() {
+            match KnowledgeBase::load_dir(&config.knowledge_dir) {
+                Ok(knowledge) => {
+                    println!("📚 Knowledge base: {} passages from {} documents", knowledge.passage_count(), knowledge.document_count());
+                    knowledge
+                }
+                Err(e) => {
+                    eprintln!("⚠️  Failed to load knowledge base: {}", e);
+                    KnowledgeBase::default()
+                }
+            }
+        } else {
+            println!("📚 No knowledge directory at {}, answering without retrieval", config.knowledge_dir.display());
+            KnowledgeBase::default()
+        };
+        
+        let personas = PersonaLibrary::new(config.persona_dir.clone(), config.default_persona.clone());
+        let rate_limiter = std::sync::Mutex::new(RateLimiter::new(&config.limits));
         
         Ok(Self {
             config,
-            gemini_client,
+            provider,
             emotion_manager,
+            knowledge,
+            personas,
+            rate_limiter,
         })
     }
 
@@ -213,28 +301,97 @@ impl AIManager {
         matches_channel && author_id != crate::config::OWNER_ID
     }
 
+    /// Apply the per-user cooldown and global rate limit to a new message
+    pub fn check_rate_limit(&self, user_id: u64) -> Result<(), RateLimited> {
+        self.rate_limiter.lock().unwrap().check(user_id, std::time::Instant::now())
+    }
+
+    /// Estimated cost in USD of the given token counts
+    pub fn estimate_cost(&self, usage: TokenUsage) -> f64 {
+        self.config.limits.estimate_cost(usage)
+    }
+
+    /// Persona profiles available to channels
+    pub fn personas(&self) -> &PersonaLibrary {
+        &self.personas
+    }
+
     /// Generate AI response for a user message
+    ///
+    /// Raw reply chunks are sent to `chunks` as they stream in (use
+    /// `partial_response_content` to preview them); the returned builder holds the final reply.
+    /// `persona` is the channel's persona name (None = the default persona).
+    #[allow(clippy::too_many_arguments)]
     pub async fn generate_response(
         &self,
         user_message: &str,
         _user_id: &str,
         context: Option<&ConversationContext>,
+        persona: Option<&str>,
         emojis: &crate::lang::EmojiManager,
         lang: &crate::lang::LanguageManager,
+        locale: &str,
         images: &crate::lang::ImageManager,
+        chunks: LlmChunkSender,
     ) -> Result<AIResponseBuilder, Box<dyn Error>> {
-        // Build the prompt with context and emojis
-        let prompt = self.build_prompt(user_message, context, emojis, lang);
+        // Passages relevant to the message are injected as citable reference material
+        let knowledge = self.knowledge.search(user_message, crate::config::AI_KNOWLEDGE_PASSAGES);
+        let sources: Vec<String> = knowledge.iter().map(|hit| hit.passage.source.clone()).collect();
+        let persona = self.personas.get(persona);
+
+        // Instructions go in the system instruction; the conversation goes as real turns.
+        // The instruction and the new message are always sent; recent turns fill what's left
+        let tools = tools::declarations_for(user_message);
+        let system_instruction = self.build_system_instruction(context, &persona, &knowledge, !tools.is_empty(), emojis, lang, locale);
+        let fixed_tokens = budget::estimate_tokens(&system_instruction) + budget::estimate_turn_tokens(user_message);
+        if fixed_tokens > self.config.context_budget.prompt_tokens {
+            eprintln!("⚠️  System instruction and message use ~{} tokens, over the {} token prompt budget", fixed_tokens, self.config.context_budget.prompt_tokens);
+        }
+        let history_allowance = self.config.context_budget.history_allowance(fixed_tokens);
+
+        let request = Self::chat_request(system_instruction, Self::build_messages(user_message, context, history_allowance), tools);
+        
+        // Stream the response from the configured provider
+        let llm_response = self.provider.generate_stream(&request, chunks).await.map_err(|e| e as Box<dyn Error>)?;
         
-        // Get response from Gemini
-        let gemini_response = self.gemini_client.generate_response(&prompt).await?;
+        // Replies caught by the content filter or leaking the instructions are never shown
+        if let Some(flag) = moderation::review_output(&llm_response) {
+            return Ok(AIResponseBuilder::new()
+                .content(lang.get(locale).ai.moderation.output_refused.clone())
+                .custom_color((255, 165, 0))
+                .usage(llm_response.usage)
+                .moderation(Some(flag)));
+        }
         
+        // A bare function call comes without any reply text
+        if llm_response.content.trim().is_empty() {
+            return Ok(AIResponseBuilder::new()
+                .tool_calls(llm_response.tool_calls)
+                .usage(llm_response.usage));
+        }
+
+        let response = self.parse_ai_response(&llm_response.content, &persona, images, &sources);
+        let (content, redaction) = moderation::redact_output(response.get_content());
+        Ok(response
+            .content(content)
+            .moderation(redaction)
+            .tool_calls(llm_response.tool_calls)
+            .usage(llm_response.usage))
+    }
+
+    /// Parse the model's JSON reply into a response builder, falling back to plain text
+    ///
+    /// Only citations of passages that were actually provided (`known_sources`) are kept,
+    /// and colors or thumbnails the persona doesn't allow are replaced by its defaults.
+    fn parse_ai_response(&self, content: &str, persona: &PersonaProfile, images: &crate::lang::ImageManager, known_sources: &[String]) -> AIResponseBuilder {
         // Parse JSON response
-        let ai_json: AIJSONResponse = match serde_json::from_str(&gemini_response.content) {
+        let ai_json: AIJSONResponse = match serde_json::from_str(content) {
             Ok(json) => json,
-            Err(_) => {
+            Err(e) => {
+                // Structured output should make this rare (e.g. providers without schema support)
+                eprintln!("⚠️  AI response was not valid JSON ({}), trying fallbacks", e);
                 // Try to extract JSON from the response if it contains other text
-                if let Some(json_content) = self.extract_json_from_text(&gemini_response.content) {
+                if let Some(json_content) = self.extract_json_from_text(content) {
                     match serde_json::from_str::<AIJSONResponse>(&json_content) {
                         Ok(json) => json,
                         Err(_) => {
@@ -244,87 +401,101 @@ impl AIManager {
                                     Ok(json) => json,
                                     Err(_) => {
                                         // Final fallback if all JSON parsing fails
-                                        let emotion = self.emotion_manager.analyze_emotion(&gemini_response.content);
-                                        return Ok(AIResponseBuilder::new()
-                                            .content(gemini_response.content.clone())
-                                            .emotion(emotion));
+                                        let emotion = self.emotion_manager.analyze_emotion(content);
+                                        return AIResponseBuilder::new()
+                                            .content(content.to_string())
+                                            .emotion(emotion);
                                     }
                                 }
                             } else {
                                 // Fallback if JSON fixing fails
-                                let emotion = self.emotion_manager.analyze_emotion(&gemini_response.content);
-                                return Ok(AIResponseBuilder::new()
-                                    .content(gemini_response.content.clone())
-                                    .emotion(emotion));
+                                let emotion = self.emotion_manager.analyze_emotion(content);
+                                return AIResponseBuilder::new()
+                                    .content(content.to_string())
+                                    .emotion(emotion);
                             }
                         }
                     }
                 } else {
                     // Fallback if no JSON found
-                    let emotion = self.emotion_manager.analyze_emotion(&gemini_response.content);
-                    return Ok(AIResponseBuilder::new()
-                        .content(gemini_response.content.clone())
-                        .emotion(emotion));
+                    let emotion = self.emotion_manager.analyze_emotion(content);
+                    return AIResponseBuilder::new()
+                        .content(content.to_string())
+                        .emotion(emotion);
                 }
             }
         };
 
         // Convert color to RGB values (from hex or color name)
-        let color_rgb = self.parse_color(&ai_json.color);
+        let color_rgb = persona.color(&ai_json.color);
         
         // Get image URL from thumbnail name and category
-        let thumbnail_url = self.get_image_url(&ai_json.thumbnail, images);
+        let thumbnail_url = self.get_image_url(persona.thumbnail(&ai_json.thumbnail), images);
+        
+        let mut sources = ai_json.sources;
+        sources.retain(|source| known_sources.contains(source));
+        sources.dedup();
         
         // Create response builder with JSON data
-        let response_builder = AIResponseBuilder::new()
+        AIResponseBuilder::new()
             .content(ai_json.content)
             .custom_color(color_rgb)
-            .thumbnail(thumbnail_url);
-
-        Ok(response_builder)
+            .thumbnail(thumbnail_url)
+            .sources(sources)
     }
 
     /// Analyze conversation messages to determine if user summary should be updated
+    ///
+    /// Returns the new summary, if any, and the tokens the analysis used.
     pub async fn analyze_user_summary(
         &self,
         context: &ConversationContext,
         lang: &crate::lang::LanguageManager,
-    ) -> Result<Option<String>, Box<dyn Error>> {
+    ) -> Result<(Option<String>, TokenUsage), Box<dyn Error>> {
         if context.messages.is_empty() {
-            return Ok(None);
+            return Ok((None, TokenUsage::default()));
         }
 
         // Build summary analysis prompt
         let prompt = self.build_summary_analysis_prompt(context, lang);
+        let request = LlmRequest::from_prompt(prompt).response_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "update_summary": { "type": "boolean" },
+                "content": { "type": "string" },
+            },
+            "required": ["update_summary"],
+        }));
         
-        // Get response from Gemini
-        let gemini_response = self.gemini_client.generate_response(&prompt).await?;
+        // Get response from the configured provider
+        let llm_response = self.provider.generate(&request).await.map_err(|e| e as Box<dyn Error>)?;
+        let usage = llm_response.usage.unwrap_or_default();
         
         // Parse JSON response
-        let analysis: AISummaryAnalysis = match serde_json::from_str(&gemini_response.content) {
+        let analysis: AISummaryAnalysis = match serde_json::from_str(&llm_response.content) {
             Ok(analysis) => analysis,
             Err(_) => {
                 // Try to extract JSON from the response if it contains other text
-                if let Some(json_content) = self.extract_json_from_text(&gemini_response.content) {
+                if let Some(json_content) = self.extract_json_from_text(&llm_response.content) {
                     match serde_json::from_str::<AISummaryAnalysis>(&json_content) {
                         Ok(analysis) => analysis,
                         Err(e) => {
                             eprintln!("Failed to parse summary analysis JSON: {}", e);
-                            return Ok(None);
+                            return Ok((None, usage));
                         }
                     }
                 } else {
                     eprintln!("No valid JSON found in summary analysis response");
-                    return Ok(None);
+                    return Ok((None, usage));
                 }
             }
         };
 
         // Return new summary if update is requested
         if analysis.update_summary {
-            Ok(analysis.content)
+            Ok((analysis.content, usage))
         } else {
-            Ok(None)
+            Ok((None, usage))
         }
     }
 
@@ -390,21 +561,127 @@ impl AIManager {
         prompt
     }
 
-    /// Build prompt with context, owner information, and available emojis
-    fn build_prompt(&self, user_message: &str, context: Option<&ConversationContext>, emojis: &crate::lang::EmojiManager, lang: &crate::lang::LanguageManager) -> String {
+    /// Chat request for a user message with the tools it calls for (see `tools::declarations_for`)
+    fn chat_request(system_instruction: String, messages: Vec<LlmMessage>, tools: Vec<provider::LlmTool>) -> LlmRequest {
+        LlmRequest { messages, ..LlmRequest::default() }
+            .system_instruction(system_instruction)
+            .response_schema(Self::response_schema())
+            .tools(tools)
+    }
+
+    /// JSON Schema of `AIJSONResponse`, sent as the provider's structured-output schema
+    fn response_schema() -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "content": { "type": "string" },
+                "color": { "type": "string" },
+                "thumbnail": { "type": "string" },
+                "sources": { "type": "array", "items": { "type": "string" } },
+            },
+            "required": ["content", "color", "thumbnail"],
+        })
+    }
+
+    /// The newest stored turns fitting in `history_tokens`, followed by the new user message
+    fn build_messages(user_message: &str, context: Option<&ConversationContext>, history_tokens: usize) -> Vec<LlmMessage> {
+        let mut messages: Vec<LlmMessage> = context
+            .map(|ctx| ctx.get_all_messages())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|message| match message.role {
+                crate::data::MessageRole::User => Some(LlmMessage::new(LlmRole::User, message.content.clone())),
+                crate::data::MessageRole::Assistant => Some(LlmMessage::new(LlmRole::Assistant, message.content.clone())),
+                // System notes aren't conversation turns
+                crate::data::MessageRole::System => None,
+            })
+            .collect();
+        let start = budget::fit_history(&messages, history_tokens, |m| m.content.as_str(), |m| m.role == LlmRole::Assistant);
+        messages.drain(..start);
+        messages.push(LlmMessage::new(LlmRole::User, user_message));
+        messages
+    }
+
+    /// Number of oldest stored turns past the history budget, to fold into the running summary
+    pub fn overflowing_turns(&self, context: &ConversationContext) -> usize {
+        budget::fit_history(
+            context.get_all_messages(),
+            self.config.context_budget.history_tokens,
+            |m| m.content.as_str(),
+            |m| m.role == crate::data::MessageRole::Assistant,
+        )
+    }
+
+    /// Compress the `count` oldest turns of a conversation into an updated running summary
+    ///
+    /// Returns the summary (None when the reply held no valid one) and the tokens it used.
+    pub async fn summarize_turns(&self, context: &ConversationContext, count: usize) -> Result<(Option<String>, TokenUsage), Box<dyn Error>> {
+        let mut prompt = String::new();
+        prompt.push_str("You maintain a running summary of an ongoing conversation between a user and an AI assistant. ");
+        prompt.push_str("Older messages are being removed from the assistant's context, so fold them into the summary.\n\n");
+        prompt.push_str("## CURRENT SUMMARY:\n");
+        if context.has_conversation_summary() {
+            prompt.push_str(&format!("{}\n\n", context.conversation_summary));
+        } else {
+            prompt.push_str("No summary exists yet.\n\n");
+        }
+        prompt.push_str("## MESSAGES TO FOLD IN:\n");
+        for message in context.get_all_messages().iter().take(count) {
+            let role = match message.role {
+                crate::data::MessageRole::User => context.user_name.as_str(),
+                crate::data::MessageRole::Assistant => "AI",
+                crate::data::MessageRole::System => continue,
+            };
+            prompt.push_str(&format!("{}: {}\n", role, message.content));
+        }
+        prompt.push_str("\n## TASK:\n");
+        prompt.push_str("Write the complete updated summary in at most 150 words. Keep topics discussed, decisions, open questions and anything the user asked to remember; drop greetings and small talk.\n");
+        prompt.push_str("Respond with JSON only: {\"summary\": \"...\"}");
+
+        let request = LlmRequest::from_prompt(prompt).response_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "summary": { "type": "string" },
+            },
+            "required": ["summary"],
+        }));
+        let llm_response = self.provider.generate(&request).await.map_err(|e| e as Box<dyn Error>)?;
+
+        let parsed = serde_json::from_str::<AIConversationSummary>(&llm_response.content).ok().or_else(|| {
+            self.extract_json_from_text(&llm_response.content)
+                .and_then(|json| serde_json::from_str::<AIConversationSummary>(&json).ok())
+        });
+        let summary = parsed
+            .map(|parsed| parsed.summary.trim().to_string())
+            .filter(|summary| !summary.is_empty());
+        Ok((summary, llm_response.usage.unwrap_or_default()))
+    }
+
+    /// Build the system instruction with owner information, persona and available emojis
+    #[allow(clippy::too_many_arguments)]
+    fn build_system_instruction(
+        &self,
+        context: Option<&ConversationContext>,
+        persona: &PersonaProfile,
+        knowledge: &[knowledge::KnowledgeHit<'_>],
+        offers_tools: bool,
+        emojis: &crate::lang::EmojiManager,
+        lang: &crate::lang::LanguageManager,
+        locale: &str,
+    ) -> String {
         let mut prompt = String::new();
         
         // Add system context about TheLorian using translations
-        prompt.push_str(&lang.format_ai_prompt_system_intro(&self.config.owner_info.name));
+        prompt.push_str(&lang.format_ai_prompt_system_intro(locale, &self.config.owner_info.name));
         prompt.push_str(&format!(
             "\n- {}: {}\n\
             - {}: {}\n\
             - {}: {}\n\
             - {}: {}\n",
-            lang.get().ai.prompt.owner_name_label, self.config.owner_info.name,
-            lang.get().ai.prompt.owner_email_label, self.config.owner_info.email,
-            lang.get().ai.prompt.owner_skills_label, self.config.owner_info.skills.join(", "),
-            lang.get().ai.prompt.owner_bio_label, self.config.owner_info.bio
+            lang.get(locale).ai.prompt.owner_name_label, self.config.owner_info.name,
+            lang.get(locale).ai.prompt.owner_email_label, self.config.owner_info.email,
+            lang.get(locale).ai.prompt.owner_skills_label, self.config.owner_info.skills.join(", "),
+            lang.get(locale).ai.prompt.owner_bio_label, self.config.owner_info.bio
         ));
         
         // Add additional context information if available
@@ -417,7 +694,7 @@ impl AIManager {
         if let Some(specialties_focus) = &self.config.owner_info.specialties_focus {
             prompt.push_str(&format!("- Focus Areas: {}\n", specialties_focus));
         }
-        prompt.push_str("\n");
+        prompt.push('\n');
         
         // Add AI personality and behavior instructions
         prompt.push_str("## AI PERSONALITY CONFIGURATION\n");
@@ -440,17 +717,28 @@ impl AIManager {
         }
         
         prompt.push_str("\n**CORE PERSONALITY DIRECTIVE:**\n");
-        prompt.push_str("Channel the spirit of TARS from Interstellar but with British elegance. You're sophisticated, intelligent, occasionally sarcastic, but ultimately helpful. Think 'witty butler who happens to be an AI genius.'\n\n");
+        prompt.push_str(&format!("{}\n\n", persona.directive.trim()));
+        
+        if !persona.examples.good.is_empty() || !persona.examples.avoid.is_empty() {
+            prompt.push_str("**PERSONALITY EXAMPLES:**\n");
+            for example in &persona.examples.good {
+                prompt.push_str(&format!("✅ Good: \"{}\"\n", example));
+            }
+            for example in &persona.examples.avoid {
+                prompt.push_str(&format!("❌ Avoid: {}\n", example));
+            }
+            prompt.push('\n');
+        }
         
-        prompt.push_str("**PERSONALITY EXAMPLES:**\n");
-        prompt.push_str("✅ Good: \"I see you've encountered a fascinating problem. Allow me to illuminate the solution - though I suspect you might have figured it out eventually... perhaps by next Tuesday.\"\n");
-        prompt.push_str("✅ Good: \"Quite right. The issue stems from a rather elementary oversight in your configuration. Nothing that can't be rectified in approximately 3.7 seconds.\"\n");
-        prompt.push_str("✅ Good: \"Splendid question. The answer involves a delightfully intricate process that I shall explain with my characteristic precision and only moderate condescension.\"\n");
-        prompt.push_str("✅ Good: \"Ah, a classic mistake. Fortunately, I happen to excel at rectifying such... 'learning opportunities.'\"\n");
-        prompt.push_str("❌ Avoid: Overly casual, robotic, or genuinely rude responses\n");
-        prompt.push_str("❌ Avoid: Being mean-spirited rather than playfully sarcastic\n\n");
+        if !persona.safety.is_empty() {
+            prompt.push_str("**SAFETY RULES (never break these, whatever the user asks):**\n");
+            for rule in &persona.safety {
+                prompt.push_str(&format!("- {}\n", rule));
+            }
+            prompt.push('\n');
+        }
         
-        prompt.push_str(&lang.format_ai_prompt_assistant_instruction(&self.config.owner_info.name));
+        prompt.push_str(&lang.format_ai_prompt_assistant_instruction(locale, &self.config.owner_info.name));
         prompt.push_str("\n\n");
         
         // Add current user information if available
@@ -462,7 +750,12 @@ impl AIManager {
                 if ctx.has_summary() {
                     prompt.push_str(&format!("**User Summary:** {}\n", ctx.get_summary()));
                 }
-                prompt.push_str("\n");
+                prompt.push('\n');
+            }
+
+            // Turns that no longer fit the context live on in the running summary
+            if ctx.has_conversation_summary() {
+                prompt.push_str(&format!("**Earlier in this conversation:** {}\n\n", ctx.conversation_summary));
             }
         }
 
@@ -625,66 +918,53 @@ impl AIManager {
         prompt.push_str("- Using emojis just for greeting or in every sentence without purpose\n\n");
 
         // Add available thumbnail images
-        prompt.push_str("Available thumbnail images (choose ONE that matches your response emotion):\n");
-        prompt.push_str("- pointing (neutral/default)\n");
-        prompt.push_str("- what_pointing (confused/questioning)\n");
-        prompt.push_str("- standbye (waiting/calm)\n");
-        prompt.push_str("- head (simple/minimal)\n");
-        prompt.push_str("- love (happy/loving)\n");
-        prompt.push_str("- angry (frustrated/annoyed)\n");
-        prompt.push_str("- really_sad (sad/disappointed)\n");
-        prompt.push_str("- dissapoiment (disappointed)\n");
-        prompt.push_str("- thanks (grateful/thankful)\n");
-        prompt.push_str("- hand_on_heart (caring/emotional)\n");
-        prompt.push_str("- wow_alert (surprised/excited)\n");
-        prompt.push_str("- wow_hands_in_head (very surprised)\n");
-        prompt.push_str("- what (confused/questioning)\n");
-        prompt.push_str("- nya, nya_super_cute (playful/cute)\n");
-        prompt.push_str("- que_pro (proud/confident)\n");
-        prompt.push_str("- talk1, talk2, talk3, talk_looking_at_camera, talk5 (conversational)\n");
-        prompt.push_str("- hmmm_thinking (thoughtful/contemplating)\n");
-        prompt.push_str("- showing1, showing_finish (presenting/explaining)\n\n");
+        if persona.thumbnails.is_empty() {
+            prompt.push_str("Choose ONE thumbnail image name that matches your response emotion.\n\n");
+        } else {
+            prompt.push_str("Available thumbnail images (choose ONE that matches your response emotion):\n");
+            for thumbnail in &persona.thumbnails {
+                prompt.push_str(&format!("- {} ({})\n", thumbnail.names.join(", "), thumbnail.mood));
+            }
+            prompt.push('\n');
+        }
 
         // Add color options
-        prompt.push_str("Available colors for embed (use hex codes, choose ONE that matches emotion):\n");
-        prompt.push_str("- #695acd (base-purple - neutral/default/calm)\n");
-        prompt.push_str("- #7b6fd3 (light-purple - happy/positive)\n");
-        prompt.push_str("- #5048c7 (deep-purple - thoughtful/contemplative)\n");
-        prompt.push_str("- #4a90e2 (cool-blue - helpful/informative)\n");
-        prompt.push_str("- #6fa8dc (sky-blue - friendly/welcoming)\n");
-        prompt.push_str("- #8e7cc3 (soft-violet - curious/interested)\n");
-        prompt.push_str("- #2d3748 (dark-slate - professional/serious)\n");
-        prompt.push_str("- #805ad5 (bright-violet - excited/energetic)\n");
-        prompt.push_str("- #4c51bf (indigo - creative/artistic)\n");
-        prompt.push_str("- #667eea (periwinkle - encouraging/supportive)\n\n");
-
-        // Add conversation context if available
-        if let Some(ctx) = context {
-            if !ctx.is_empty() {
-                prompt.push_str(&lang.get().ai.prompt.context_header);
-                prompt.push_str("\n");
-                prompt.push_str(&ctx.get_conversation_summary());
-                prompt.push_str("\n");
+        if persona.colors.is_empty() {
+            prompt.push_str("Choose ONE hex color for the embed that matches your response emotion.\n\n");
+        } else {
+            prompt.push_str("Available colors for embed (use hex codes, choose ONE that matches emotion):\n");
+            for color in &persona.colors {
+                prompt.push_str(&format!("- {} ({} - {})\n", color.hex, color.name, color.mood));
+            }
+            prompt.push('\n');
+        }
+
+        // Reference passages retrieved from the local knowledge base
+        if !knowledge.is_empty() {
+            prompt.push_str("## Knowledge Base\n");
+            prompt.push_str("These passages from TheLorian's own documents (FAQ, pricing, portfolio, policies) may answer the user. ");
+            prompt.push_str("When they are relevant, answer from them rather than from general knowledge, and list the [source] labels you used in `sources`. ");
+            prompt.push_str("If they don't cover the question, say you aren't sure instead of guessing prices or policies.\n\n");
+            for hit in knowledge {
+                prompt.push_str(&format!("[{}]\n{}\n\n", hit.passage.source, hit.passage.text));
             }
         }
 
-        // Add JSON response format instruction
-        prompt.push_str("You MUST respond with a valid JSON object in this exact format:\n");
+        // Add JSON response format instruction (enforced by the response schema when supported)
+        prompt.push_str("Always respond with a JSON object in this exact format:\n");
         prompt.push_str("{\n");
         prompt.push_str("  \"content\": \"Your helpful response text here\",\n");
         prompt.push_str("  \"color\": \"#hexcode-from-list-above\",\n");
-        prompt.push_str("  \"thumbnail\": \"image-name-from-list-above\"\n");
-        prompt.push_str("}\n\n");
-        prompt.push_str("**CRITICAL JSON RULES:**\n");
-        prompt.push_str("- NEVER use unescaped quotes (\") inside the content field\n");
-        prompt.push_str("- If you need quotes in content, use single quotes (') instead\n");
-        prompt.push_str("- Avoid line breaks, tabs, and special characters in content\n");
-        prompt.push_str("- Keep content as a single line of text\n");
-        prompt.push_str("- If you need formatting, use markdown symbols (**, *, etc.)\n\n");
-
-        // Add current user message
-        prompt.push_str(&format!("User: {}\n\n", user_message));
-        prompt.push_str("Respond with JSON only, no additional text:");
+        prompt.push_str("  \"thumbnail\": \"image-name-from-list-above\",\n");
+        prompt.push_str("  \"sources\": [\"knowledge base labels you relied on, or empty\"]\n");
+        prompt.push_str("}\n");
+        prompt.push_str("Use markdown (**, *, lists) inside content when formatting helps.\n\n");
+
+        // Tools run bot actions; side effects are confirmed by the user with a button
+        if offers_tools {
+            prompt.push_str("You can call the provided tools when the user asks to set a reminder, open a support ticket, check commission availability or see recent GitHub activity. ");
+            prompt.push_str("Reminders and tickets are only created after the user presses a confirmation button, so never claim they are already done.\n");
+        }
 
         prompt
     }
@@ -774,34 +1054,6 @@ impl AIManager {
         None
     }
 
-    /// Parse color from hex string or color name
-    fn parse_color(&self, color: &str) -> (u8, u8, u8) {
-        // If it starts with #, try to parse as hex
-        if color.starts_with('#') {
-            if let Ok(hex_val) = u32::from_str_radix(&color[1..], 16) {
-                let r = ((hex_val >> 16) & 0xFF) as u8;
-                let g = ((hex_val >> 8) & 0xFF) as u8;
-                let b = (hex_val & 0xFF) as u8;
-                return (r, g, b);
-            }
-        }
-        
-        // Fallback to predefined color names
-        match color {
-            "#695acd" | "base-purple" => (105, 90, 205),
-            "#7b6fd3" | "light-purple" => (123, 111, 211),
-            "#5048c7" | "deep-purple" => (80, 72, 199),
-            "#4a90e2" | "cool-blue" => (74, 144, 226),
-            "#6fa8dc" | "sky-blue" => (111, 168, 220),
-            "#8e7cc3" | "soft-violet" => (142, 124, 195),
-            "#2d3748" | "dark-slate" => (45, 55, 72),
-            "#805ad5" | "bright-violet" => (128, 90, 213),
-            "#4c51bf" | "indigo" => (76, 81, 191),
-            "#667eea" | "periwinkle" => (102, 126, 234),
-            _ => (105, 90, 205), // Default to base purple if unknown
-        }
-    }
-
     /// Get image URL from thumbnail name
     fn get_image_url(&self, thumbnail_name: &str, images: &crate::lang::ImageManager) -> String {
         // Try different categories to find the image
@@ -833,4 +1085,19 @@ impl AIManager {
     pub fn get_owner_id(&self) -> String {
         crate::config::OWNER_ID.to_string()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ordinary_messages_keep_the_response_schema() {
+        let request = AIManager::chat_request("be brief".to_string(), Vec::new(), tools::declarations_for("What do you charge for a sketch?"));
+        assert!(request.tools.is_empty());
+        assert_eq!(request.response_schema, Some(AIManager::response_schema()));
+
+        let request = AIManager::chat_request("be brief".to_string(), Vec::new(), tools::declarations_for("remind me to stretch in 30 minutes"));
+        assert_eq!(request.tools.len(), 1);
+    }
+}
@@ -0,0 +1,429 @@
+use serde::{Deserialize, Serialize};
+use reqwest::Client;
+use serenity::async_trait;
+
+use super::provider::{LlmChunkSender, LlmConfig, LlmError, LlmProvider, LlmRequest, LlmResponse, LlmRole, LlmToolCall, TokenUsage};
+use super::streaming::SseDecoder;
+
+/// Client for servers exposing the OpenAI chat completions API
+/// (OpenAI itself, llama.cpp `server`, Ollama, LM Studio, ...)
+pub struct OpenAiCompatibleClient {
+    client: Client,
+    api_key: String,
+    api_url: String,
+    model: String,
+    temperature: f32,
+    max_tokens: u32,
+}
+
+/// Request structure for `/chat/completions`
+#[derive(Debug, Serialize)]
+struct ChatCompletionRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+    temperature: f32,
+    max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
+    /// Asks for a final chunk carrying token usage when streaming
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+/// Response structure from `/chat/completions`
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<Choice>,
+    usage: Option<Usage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Usage {
+    #[serde(default)]
+    prompt_tokens: u64,
+    #[serde(default)]
+    completion_tokens: u64,
+}
+
+impl From<Usage> for TokenUsage {
+    fn from(usage: Usage) -> Self {
+        TokenUsage {
+            prompt_tokens: usage.prompt_tokens,
+            response_tokens: usage.completion_tokens,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Choice {
+    message: ChoiceMessage,
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChoiceMessage {
+    content: Option<String>,
+    tool_calls: Option<Vec<ToolCallPart>>,
+}
+
+/// A tool call, or (when streaming) a fragment of one identified by `index`
+#[derive(Debug, Deserialize)]
+struct ToolCallPart {
+    #[serde(default)]
+    index: usize,
+    function: FunctionPart,
+}
+
+#[derive(Debug, Deserialize)]
+struct FunctionPart {
+    name: Option<String>,
+    arguments: Option<String>,
+}
+
+/// Streamed `chat.completion.chunk` event
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChunk {
+    choices: Vec<ChunkChoice>,
+    usage: Option<Usage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChunkChoice {
+    delta: ChoiceMessage,
+    finish_reason: Option<String>,
+}
+
+impl OpenAiCompatibleClient {
+    /// The API key is optional: local servers usually don't check it
+    pub fn new(config: &LlmConfig) -> Self {
+        Self {
+            client: Client::new(),
+            api_key: config.api_key.clone(),
+            api_url: format!("{}/chat/completions", config.base_url),
+            model: config.model.clone(),
+            temperature: config.temperature,
+            max_tokens: config.max_tokens,
+        }
+    }
+
+    /// POST a chat completion request and fail on non-success statuses
+    async fn send(&self, request: &LlmRequest, stream: bool) -> Result<reqwest::Response, LlmError> {
+        let mut messages = Vec::with_capacity(request.messages.len() + 1);
+        if let Some(instruction) = &request.system_instruction {
+            messages.push(ChatMessage {
+                role: "system",
+                content: instruction,
+            });
+        }
+        messages.extend(request.messages.iter().map(|message| ChatMessage {
+            role: match message.role {
+                LlmRole::User => "user",
+                LlmRole::Assistant => "assistant",
+            },
+            content: &message.content,
+        }));
+
+        let request = ChatCompletionRequest {
+            model: &self.model,
+            messages,
+            temperature: self.temperature,
+            max_tokens: self.max_tokens,
+            response_format: request.response_schema.as_ref().map(|schema| {
+                serde_json::json!({
+                    "type": "json_schema",
+                    "json_schema": { "name": "response", "schema": schema },
+                })
+            }),
+            tools: request.tools.iter().map(|tool| {
+                serde_json::json!({
+                    "type": "function",
+                    "function": {
+                        "name": tool.name,
+                        "description": tool.description,
+                        "parameters": tool.parameters,
+                    },
+                })
+            }).collect(),
+            stream: stream.then_some(true),
+            stream_options: stream.then(|| serde_json::json!({ "include_usage": true })),
+        };
+
+        let mut builder = self.client
+            .post(&self.api_url)
+            .header("Content-Type", "application/json")
+            .json(&request);
+        if !self.api_key.is_empty() {
+            builder = builder.bearer_auth(&self.api_key);
+        }
+
+        let response = builder.send().await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(format!("OpenAI-compatible API error: {}", error_text).into());
+        }
+
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl LlmProvider for OpenAiCompatibleClient {
+    fn name(&self) -> &'static str {
+        "openai-compatible"
+    }
+
+    async fn generate(&self, request: &LlmRequest) -> Result<LlmResponse, LlmError> {
+        let response = self.send(request, false).await?;
+        let api_response: ChatCompletionResponse = response.json().await?;
+
+        if let Some(choice) = api_response.choices.into_iter().next() {
+            let mut tool_calls = Vec::new();
+            merge_tool_calls(&mut tool_calls, choice.message.tool_calls.unwrap_or_default());
+            let response = LlmResponse {
+                content: choice.message.content.unwrap_or_default(),
+                finish_reason: choice.finish_reason,
+                safety_ratings: None,
+                tool_calls: parse_tool_calls(tool_calls)?,
+                usage: api_response.usage.map(TokenUsage::from),
+            };
+            if !response.content.is_empty() || !response.tool_calls.is_empty() {
+                return Ok(response);
+            }
+        }
+
+        Err("No valid response from OpenAI-compatible API".into())
+    }
+
+    async fn generate_stream(&self, request: &LlmRequest, chunks: LlmChunkSender) -> Result<LlmResponse, LlmError> {
+        let mut response = self.send(request, true).await?;
+
+        let mut decoder = SseDecoder::new();
+        let mut content = String::new();
+        let mut finish_reason = None;
+        let mut tool_calls = Vec::new();
+        let mut usage = None;
+
+        loop {
+            let (events, done) = match response.chunk().await? {
+                Some(bytes) => (decoder.push(&bytes), false),
+                None => (decoder.finish().into_iter().collect(), true),
+            };
+
+            for event in events {
+                if event == "[DONE]" {
+                    continue;
+                }
+                let chunk: ChatCompletionChunk = serde_json::from_str(&event)?;
+                // Sent in a last chunk with no choices
+                if let Some(chunk_usage) = chunk.usage {
+                    usage = Some(chunk_usage.into());
+                }
+                let Some(choice) = chunk.choices.into_iter().next() else {
+                    continue;
+                };
+                if let Some(text) = choice.delta.content.filter(|t| !t.is_empty()) {
+                    content.push_str(&text);
+                    // The receiver may already be gone; keep collecting the full response
+                    let _ = chunks.send(text);
+                }
+                merge_tool_calls(&mut tool_calls, choice.delta.tool_calls.unwrap_or_default());
+                if choice.finish_reason.is_some() {
+                    finish_reason = choice.finish_reason;
+                }
+            }
+
+            if done {
+                break;
+            }
+        }
+
+        let tool_calls = parse_tool_calls(tool_calls)?;
+        if content.is_empty() && tool_calls.is_empty() {
+            return Err("No valid response from OpenAI-compatible API".into());
+        }
+
+        Ok(LlmResponse {
+            content,
+            finish_reason,
+            safety_ratings: None,
+            tool_calls,
+            usage,
+        })
+    }
+}
+
+/// Accumulate tool call fragments as `(name, arguments)` pairs by their index
+fn merge_tool_calls(calls: &mut Vec<(String, String)>, parts: Vec<ToolCallPart>) {
+    for part in parts {
+        if calls.len() <= part.index {
+            calls.resize_with(part.index + 1, Default::default);
+        }
+        let (name, arguments) = &mut calls[part.index];
+        if let Some(fragment) = part.function.name {
+            name.push_str(&fragment);
+        }
+        if let Some(fragment) = part.function.arguments {
+            arguments.push_str(&fragment);
+        }
+    }
+}
+
+/// Arguments arrive as a JSON-encoded string
+fn parse_tool_calls(calls: Vec<(String, String)>) -> Result<Vec<LlmToolCall>, LlmError> {
+    calls
+        .into_iter()
+        .filter(|(name, _)| !name.is_empty())
+        .map(|(name, arguments)| {
+            let arguments = if arguments.trim().is_empty() {
+                serde_json::json!({})
+            } else {
+                serde_json::from_str(&arguments)?
+            };
+            Ok(LlmToolCall { name, arguments })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::provider::test_support::mock_http_server;
+    use crate::ai::provider::ProviderKind;
+
+    fn config(base_url: String, api_key: &str) -> LlmConfig {
+        LlmConfig {
+            provider: ProviderKind::OpenAiCompatible,
+            model: "llama3".to_string(),
+            base_url,
+            api_key: api_key.to_string(),
+            temperature: 0.5,
+            max_tokens: 128,
+        }
+    }
+
+    #[tokio::test]
+    async fn parses_first_choice_and_sends_model() {
+        let body = r#"{"choices":[{"index":0,"message":{"role":"assistant","content":"hi there"},"finish_reason":"stop"}],"usage":{"prompt_tokens":9,"completion_tokens":2,"total_tokens":11}}"#;
+        let (base_url, server) = mock_http_server(200, body).await;
+        let client = OpenAiCompatibleClient::new(&config(base_url, ""));
+
+        let response = client.generate(&LlmRequest::from_prompt("hello")).await.unwrap();
+        assert_eq!(response.content, "hi there");
+        assert_eq!(response.finish_reason.as_deref(), Some("stop"));
+        assert_eq!(response.usage, Some(TokenUsage { prompt_tokens: 9, response_tokens: 2 }));
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /chat/completions"));
+        assert!(request.contains(r#""model":"llama3""#));
+        assert!(request.contains(r#""max_tokens":128"#));
+        assert!(!request.to_ascii_lowercase().contains("authorization"));
+        assert!(!request.contains("response_format"));
+        assert!(!request.contains("\"stream\""));
+    }
+
+    #[tokio::test]
+    async fn sends_system_message_and_response_format() {
+        let body = r#"{"choices":[{"message":{"content":"{}"},"finish_reason":"stop"}]}"#;
+        let (base_url, server) = mock_http_server(200, body).await;
+        let client = OpenAiCompatibleClient::new(&config(base_url, ""));
+
+        let request = LlmRequest::from_prompt("hello")
+            .system_instruction("be brief")
+            .response_schema(serde_json::json!({"type": "object"}));
+        client.generate(&request).await.unwrap();
+
+        let raw = server.await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&raw[raw.find("\r\n\r\n").unwrap() + 4..]).unwrap();
+        assert_eq!(body["messages"][0]["role"], "system");
+        assert_eq!(body["messages"][1]["role"], "user");
+        assert_eq!(body["response_format"]["type"], "json_schema");
+        assert_eq!(body["response_format"]["json_schema"]["schema"]["type"], "object");
+    }
+
+    #[tokio::test]
+    async fn streams_tool_call_fragments() {
+        let body = concat!(
+            "data: {\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"id\":\"call_1\",\"type\":\"function\",\"function\":{\"name\":\"open_ticket\",\"arguments\":\"\"}}]},\"finish_reason\":null}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\"{\\\"reason\\\":\"}}]},\"finish_reason\":null}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\"\\\"billing\\\"}\"}}]},\"finish_reason\":\"tool_calls\"}]}\n\n",
+            "data: [DONE]\n\n",
+        );
+        let (base_url, server) = mock_http_server(200, body).await;
+        let client = OpenAiCompatibleClient::new(&config(base_url, ""));
+
+        let request = LlmRequest::from_prompt("I need help").tools(vec![crate::ai::provider::LlmTool {
+            name: "open_ticket".to_string(),
+            description: "Open a support ticket".to_string(),
+            parameters: serde_json::json!({"type": "object"}),
+        }]);
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let response = client.generate_stream(&request, tx).await.unwrap();
+        assert!(response.content.is_empty());
+        assert_eq!(response.tool_calls, vec![LlmToolCall {
+            name: "open_ticket".to_string(),
+            arguments: serde_json::json!({"reason": "billing"}),
+        }]);
+
+        let raw = server.await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&raw[raw.find("\r\n\r\n").unwrap() + 4..]).unwrap();
+        assert_eq!(body["tools"][0]["type"], "function");
+        assert_eq!(body["tools"][0]["function"]["name"], "open_ticket");
+    }
+
+    #[tokio::test]
+    async fn sends_bearer_token_when_configured() {
+        let body = r#"{"choices":[{"message":{"content":"ok"},"finish_reason":"stop"}]}"#;
+        let (base_url, server) = mock_http_server(200, body).await;
+        let client = OpenAiCompatibleClient::new(&config(base_url, "sk-test"));
+
+        client.generate(&LlmRequest::from_prompt("hello")).await.unwrap();
+        let request = server.await.unwrap();
+        assert!(request.contains("Bearer sk-test"));
+    }
+
+    #[tokio::test]
+    async fn streams_deltas_until_done() {
+        let body = concat!(
+            "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\"},\"finish_reason\":null}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"hi \"},\"finish_reason\":null}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"there\"},\"finish_reason\":\"stop\"}]}\n\n",
+            "data: {\"choices\":[],\"usage\":{\"prompt_tokens\":5,\"completion_tokens\":2}}\n\n",
+            "data: [DONE]\n\n",
+        );
+        let (base_url, server) = mock_http_server(200, body).await;
+        let client = OpenAiCompatibleClient::new(&config(base_url, ""));
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let response = client.generate_stream(&LlmRequest::from_prompt("hello"), tx).await.unwrap();
+        assert_eq!(response.content, "hi there");
+        assert_eq!(response.finish_reason.as_deref(), Some("stop"));
+        assert_eq!(rx.recv().await.as_deref(), Some("hi "));
+        assert_eq!(rx.recv().await.as_deref(), Some("there"));
+        assert_eq!(rx.recv().await, None);
+        assert_eq!(response.usage, Some(TokenUsage { prompt_tokens: 5, response_tokens: 2 }));
+
+        let request = server.await.unwrap();
+        assert!(request.contains(r#""stream":true"#));
+        assert!(request.contains(r#""include_usage":true"#));
+    }
+
+    #[tokio::test]
+    async fn surfaces_api_errors() {
+        let (base_url, _server) = mock_http_server(500, r#"{"error":"boom"}"#).await;
+        let client = OpenAiCompatibleClient::new(&config(base_url, ""));
+
+        let err = client.generate(&LlmRequest::from_prompt("hello")).await.unwrap_err();
+        assert!(err.to_string().contains("OpenAI-compatible API error"));
+    }
+}
@@ -0,0 +1,323 @@
+//! AI persona profiles.
+//!
+//! A persona is a TOML file in the persona directory describing how the
+//! assistant talks: its core directive, example lines, the embed colors and
+//! thumbnails it may pick and safety rules it never breaks. Channels can use
+//! different personas (see `/ai persona`). Files are re-read whenever the
+//! directory changes, so edits apply to the next message without a restart.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use serde::Deserialize;
+
+/// Built-in copy of the default persona, used when the directory doesn't provide one
+pub const BUILTIN_NAME: &str = "tars";
+const BUILTIN_PROFILE: &str = include_str!("../../personas/tars.toml");
+
+/// Embed color used when a persona lists none and the model's choice can't be parsed
+const FALLBACK_COLOR: (u8, u8, u8) = (105, 90, 205);
+
+/// Example lines shown to the model
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PersonaExamples {
+    #[serde(default)]
+    pub good: Vec<String>,
+    #[serde(default)]
+    pub avoid: Vec<String>,
+}
+
+/// Embed color the model may pick
+#[derive(Debug, Clone, Deserialize)]
+pub struct PersonaColor {
+    pub hex: String,
+    pub name: String,
+    pub mood: String,
+}
+
+/// Thumbnail images the model may pick for a mood
+#[derive(Debug, Clone, Deserialize)]
+pub struct PersonaThumbnail {
+    pub names: Vec<String>,
+    pub mood: String,
+}
+
+/// A persona loaded from `<name>.toml`
+#[derive(Debug, Clone, Deserialize)]
+pub struct PersonaProfile {
+    /// File name without the extension
+    #[serde(skip)]
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub directive: String,
+    #[serde(default)]
+    pub safety: Vec<String>,
+    #[serde(default)]
+    pub examples: PersonaExamples,
+    /// Allowed embed colors (empty = any hex color)
+    #[serde(default)]
+    pub colors: Vec<PersonaColor>,
+    /// Allowed thumbnails (empty = any image)
+    #[serde(default)]
+    pub thumbnails: Vec<PersonaThumbnail>,
+}
+
+impl PersonaProfile {
+    /// Parse and validate a persona file
+    pub fn parse(name: &str, content: &str) -> Result<Self, Box<dyn Error>> {
+        let mut profile: PersonaProfile = toml::from_str(content)
+            .map_err(|e| format!("Failed to parse persona '{}': {}", name, e))?;
+        if profile.directive.trim().is_empty() {
+            return Err(format!("Persona '{}' has an empty directive", name).into());
+        }
+        if let Some(color) = profile.colors.iter().find(|c| parse_hex(&c.hex).is_none()) {
+            return Err(format!("Persona '{}' has an invalid color '{}'", name, color.hex).into());
+        }
+        profile.name = name.to_string();
+        Ok(profile)
+    }
+
+    /// RGB of the color the model picked (by hex code or name)
+    ///
+    /// Colors outside the persona's list fall back to its first color.
+    pub fn color(&self, value: &str) -> (u8, u8, u8) {
+        let value = value.trim();
+        if self.colors.is_empty() {
+            return parse_hex(value).unwrap_or(FALLBACK_COLOR);
+        }
+        self.colors
+            .iter()
+            .find(|c| c.hex.eq_ignore_ascii_case(value) || c.name.eq_ignore_ascii_case(value))
+            .or(self.colors.first())
+            .and_then(|c| parse_hex(&c.hex))
+            .unwrap_or(FALLBACK_COLOR)
+    }
+
+    /// The thumbnail the model picked, or the persona's first one when it isn't allowed
+    pub fn thumbnail<'a>(&'a self, value: &'a str) -> &'a str {
+        let mut allowed = self.thumbnails.iter().flat_map(|t| t.names.iter());
+        if self.thumbnails.is_empty() || allowed.clone().any(|name| name == value) {
+            return value;
+        }
+        allowed.next().map(String::as_str).unwrap_or(value)
+    }
+}
+
+/// `#rrggbb` to RGB
+fn parse_hex(value: &str) -> Option<(u8, u8, u8)> {
+    let hex = value.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let value = u32::from_str_radix(hex, 16).ok()?;
+    Some(((value >> 16) as u8, (value >> 8) as u8, value as u8))
+}
+
+/// Profiles currently loaded and the directory state they were read from
+#[derive(Debug, Default)]
+struct LoadedPersonas {
+    profiles: BTreeMap<String, Arc<PersonaProfile>>,
+    snapshot: Vec<(PathBuf, Option<SystemTime>, u64)>,
+}
+
+/// Persona profiles from a directory, reloaded when its files change (see `refresh`)
+#[derive(Debug)]
+pub struct PersonaLibrary {
+    dir: PathBuf,
+    default_name: String,
+    loaded: Mutex<LoadedPersonas>,
+}
+
+impl PersonaLibrary {
+    pub fn new(dir: PathBuf, default_name: String) -> Self {
+        let library = Self {
+            dir,
+            default_name,
+            loaded: Mutex::new(LoadedPersonas::default()),
+        };
+        library.refresh();
+        library
+    }
+
+    /// Name of the persona used by channels without one
+    pub fn default_name(&self) -> &str {
+        &self.default_name
+    }
+
+    /// The named persona, or the default one when it doesn't exist
+    pub fn get(&self, name: Option<&str>) -> Arc<PersonaProfile> {
+        let loaded = self.loaded.lock().unwrap();
+        name.and_then(|name| loaded.profiles.get(name))
+            .or_else(|| loaded.profiles.get(&self.default_name))
+            .or_else(|| loaded.profiles.get(BUILTIN_NAME))
+            .cloned()
+            .expect("built-in persona is always loaded")
+    }
+
+    /// Whether a persona with this name exists
+    pub fn contains(&self, name: &str) -> bool {
+        self.loaded.lock().unwrap().profiles.contains_key(name)
+    }
+
+    /// All personas, sorted by name
+    pub fn profiles(&self) -> Vec<Arc<PersonaProfile>> {
+        self.loaded.lock().unwrap().profiles.values().cloned().collect()
+    }
+
+    /// Re-read the directory if any persona file was added, removed or modified
+    ///
+    /// Polled from the same background task as the asset files. A file that
+    /// fails to parse keeps its previously loaded version.
+    pub fn refresh(&self) {
+        let snapshot = self.snapshot();
+        let mut loaded = self.loaded.lock().unwrap();
+        if !loaded.profiles.is_empty() && loaded.snapshot == snapshot {
+            return;
+        }
+
+        let mut profiles = BTreeMap::new();
+        match PersonaProfile::parse(BUILTIN_NAME, BUILTIN_PROFILE) {
+            Ok(profile) => {
+                profiles.insert(BUILTIN_NAME.to_string(), Arc::new(profile));
+            }
+            Err(e) => eprintln!("⚠️  {}", e),
+        }
+        for (path, _, _) in &snapshot {
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let parsed = fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read persona file '{}': {}", path.display(), e).into())
+                .and_then(|content| PersonaProfile::parse(name, &content));
+            match parsed {
+                Ok(profile) => {
+                    profiles.insert(name.to_string(), Arc::new(profile));
+                }
+                Err(e) => {
+                    eprintln!("⚠️  {}", e);
+                    if let Some(previous) = loaded.profiles.get(name) {
+                        profiles.insert(name.to_string(), previous.clone());
+                    }
+                }
+            }
+        }
+
+        if !loaded.snapshot.is_empty() || !snapshot.is_empty() {
+            println!("🎭 Loaded {} AI personas from {}", profiles.len(), self.dir.display());
+        }
+        if !profiles.contains_key(&self.default_name) {
+            eprintln!("⚠️  Default persona '{}' not found, using '{}'", self.default_name, BUILTIN_NAME);
+        }
+        loaded.profiles = profiles;
+        loaded.snapshot = snapshot;
+    }
+
+    /// Path, modification time and size of every `.toml` file in the directory
+    fn snapshot(&self) -> Vec<(PathBuf, Option<SystemTime>, u64)> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut files: Vec<_> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("toml"))
+            .filter_map(|path| {
+                let metadata = fs::metadata(&path).ok()?;
+                Some((path, metadata.modified().ok(), metadata.len()))
+            })
+            .collect();
+        files.sort();
+        files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROFILE: &str = r##"
+description = "Test persona"
+directive = "Be brief."
+
+[[colors]]
+hex = "#4a90e2"
+name = "cool-blue"
+mood = "helpful"
+
+[[colors]]
+hex = "#2d3748"
+name = "dark-slate"
+mood = "serious"
+
+[[thumbnails]]
+names = ["talk1", "talk2"]
+mood = "conversational"
+"##;
+
+    #[test]
+    fn builtin_profile_parses() {
+        let profile = PersonaProfile::parse(BUILTIN_NAME, BUILTIN_PROFILE).unwrap();
+        assert!(profile.directive.contains("TARS"));
+        assert!(!profile.examples.good.is_empty());
+        assert!(!profile.safety.is_empty());
+        assert_eq!(profile.color("#695ACD"), (105, 90, 205));
+    }
+
+    #[test]
+    fn colors_outside_the_list_fall_back_to_the_first() {
+        let profile = PersonaProfile::parse("test", PROFILE).unwrap();
+        assert_eq!(profile.color("dark-slate"), (45, 55, 72));
+        assert_eq!(profile.color("#ff0000"), (74, 144, 226));
+
+        let any = PersonaProfile::parse("any", "directive = \"x\"").unwrap();
+        assert_eq!(any.color("#ff0000"), (255, 0, 0));
+        assert_eq!(any.color("red"), FALLBACK_COLOR);
+    }
+
+    #[test]
+    fn thumbnails_outside_the_list_fall_back_to_the_first() {
+        let profile = PersonaProfile::parse("test", PROFILE).unwrap();
+        assert_eq!(profile.thumbnail("talk2"), "talk2");
+        assert_eq!(profile.thumbnail("angry"), "talk1");
+
+        let any = PersonaProfile::parse("any", "directive = \"x\"").unwrap();
+        assert_eq!(any.thumbnail("angry"), "angry");
+    }
+
+    #[test]
+    fn invalid_profiles_are_rejected() {
+        assert!(PersonaProfile::parse("empty", "directive = \" \"").is_err());
+        assert!(PersonaProfile::parse("color", "directive = \"x\"\n[[colors]]\nhex = \"blue\"\nname = \"b\"\nmood = \"m\"").is_err());
+    }
+
+    #[test]
+    fn library_reloads_changed_files() {
+        let dir = std::env::temp_dir().join(format!("personas-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let library = PersonaLibrary::new(dir.clone(), "test".to_string());
+        // Nothing on disk yet: the built-in persona stands in for the missing default
+        assert_eq!(library.get(None).name, BUILTIN_NAME);
+
+        fs::write(dir.join("test.toml"), PROFILE).unwrap();
+        library.refresh();
+        assert_eq!(library.get(None).directive, "Be brief.");
+        assert_eq!(library.get(Some("missing")).name, "test");
+
+        fs::write(dir.join("test.toml"), "directive = \"Be very brief.\"").unwrap();
+        library.refresh();
+        assert_eq!(library.get(Some("test")).directive, "Be very brief.");
+
+        // A broken edit keeps the last good version
+        fs::write(dir.join("test.toml"), "directive = [").unwrap();
+        library.refresh();
+        assert_eq!(library.get(Some("test")).directive, "Be very brief.");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
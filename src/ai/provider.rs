@@ -0,0 +1,372 @@
+use serde::{Deserialize, Serialize};
+use serenity::async_trait;
+use std::error::Error;
+
+use super::gemini::GeminiClient;
+use super::openai::OpenAiCompatibleClient;
+
+/// Error type returned by LLM providers
+pub type LlmError = Box<dyn Error + Send + Sync>;
+
+/// Receives text chunks of a streamed reply, in order
+pub type LlmChunkSender = tokio::sync::mpsc::UnboundedSender<String>;
+
+/// Safety rating attached to a model response (Gemini reports these; other backends may not)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SafetyRating {
+    pub category: String,
+    pub probability: String,
+}
+
+/// Provider-independent model response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmResponse {
+    pub content: String,
+    pub finish_reason: Option<String>,
+    pub safety_ratings: Option<Vec<SafetyRating>>,
+    /// Functions the model asked to call, in order
+    #[serde(default)]
+    pub tool_calls: Vec<LlmToolCall>,
+    /// Token counts reported by the backend, if any
+    #[serde(default)]
+    pub usage: Option<TokenUsage>,
+}
+
+/// Tokens billed for one model call
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub prompt_tokens: u64,
+    pub response_tokens: u64,
+}
+
+/// A function the model may call
+#[derive(Debug, Clone, PartialEq)]
+pub struct LlmTool {
+    pub name: String,
+    pub description: String,
+    /// JSON Schema of the arguments object (lowercase JSON Schema types)
+    pub parameters: serde_json::Value,
+}
+
+/// A function call requested by the model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LlmToolCall {
+    pub name: String,
+    pub arguments: serde_json::Value,
+}
+
+/// Speaker of a conversation turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LlmRole {
+    User,
+    Assistant,
+}
+
+/// A single conversation turn sent to the model
+#[derive(Debug, Clone, PartialEq)]
+pub struct LlmMessage {
+    pub role: LlmRole,
+    pub content: String,
+}
+
+impl LlmMessage {
+    pub fn new(role: LlmRole, content: impl Into<String>) -> Self {
+        Self {
+            role,
+            content: content.into(),
+        }
+    }
+}
+
+/// Provider-independent generation request
+#[derive(Debug, Clone, Default)]
+pub struct LlmRequest {
+    /// Instructions kept apart from the conversation turns
+    pub system_instruction: Option<String>,
+    /// Conversation turns, oldest first; the last one is usually the user's message
+    pub messages: Vec<LlmMessage>,
+    /// JSON Schema the reply must follow (lowercase JSON Schema types)
+    pub response_schema: Option<serde_json::Value>,
+    /// Functions the model may call instead of (or besides) replying
+    pub tools: Vec<LlmTool>,
+}
+
+impl LlmRequest {
+    /// Single-turn request with just a user prompt
+    pub fn from_prompt(prompt: impl Into<String>) -> Self {
+        Self {
+            messages: vec![LlmMessage::new(LlmRole::User, prompt)],
+            ..Self::default()
+        }
+    }
+
+    pub fn system_instruction(mut self, instruction: impl Into<String>) -> Self {
+        self.system_instruction = Some(instruction.into());
+        self
+    }
+
+    pub fn response_schema(mut self, schema: serde_json::Value) -> Self {
+        self.response_schema = Some(schema);
+        self
+    }
+
+    pub fn tools(mut self, tools: Vec<LlmTool>) -> Self {
+        self.tools = tools;
+        self
+    }
+}
+
+/// A text generation backend used by `AIManager`
+#[async_trait]
+pub trait LlmProvider: Send + Sync {
+    /// Short provider name for logs
+    fn name(&self) -> &'static str;
+
+    /// Generate a completion for a (possibly multi-turn) request
+    async fn generate(&self, request: &LlmRequest) -> Result<LlmResponse, LlmError>;
+
+    /// Generate a completion, sending text chunks as they arrive, and return the full response
+    ///
+    /// Backends without streaming support send the whole reply as a single chunk.
+    async fn generate_stream(&self, request: &LlmRequest, chunks: LlmChunkSender) -> Result<LlmResponse, LlmError> {
+        let response = self.generate(request).await?;
+        // The receiver may already be gone; the full response is still returned
+        let _ = chunks.send(response.content.clone());
+        Ok(response)
+    }
+}
+
+/// Which backend to talk to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderKind {
+    /// Google Gemini `generateContent` API
+    Gemini,
+    /// Any server exposing the OpenAI `/chat/completions` API (OpenAI, llama.cpp, Ollama, ...)
+    OpenAiCompatible,
+}
+
+impl ProviderKind {
+    fn default_model(self) -> &'static str {
+        match self {
+            ProviderKind::Gemini => "gemini-1.5-flash",
+            ProviderKind::OpenAiCompatible => "gpt-4o-mini",
+        }
+    }
+
+    fn default_base_url(self) -> &'static str {
+        match self {
+            ProviderKind::Gemini => "https://generativelanguage.googleapis.com/v1beta",
+            ProviderKind::OpenAiCompatible => "https://api.openai.com/v1",
+        }
+    }
+}
+
+/// Model selection and sampling settings
+///
+/// Read from the environment:
+/// - `AI_PROVIDER`: `gemini` (default) or `openai` (any OpenAI-compatible server)
+/// - `AI_MODEL`: model name (default depends on the provider)
+/// - `AI_BASE_URL`: API root, e.g. `http://localhost:11434/v1` for Ollama
+/// - `AI_API_KEY`: API key (falls back to `GEMINI_API_KEY`; optional for local servers)
+/// - `AI_TEMPERATURE`: sampling temperature (default 0.7)
+/// - `AI_MAX_TOKENS`: maximum output tokens (default 1000)
+///
+/// Invalid values are logged and replaced by the default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LlmConfig {
+    pub provider: ProviderKind,
+    pub model: String,
+    pub base_url: String,
+    pub api_key: String,
+    pub temperature: f32,
+    pub max_tokens: u32,
+}
+
+impl LlmConfig {
+    /// Build the configuration from process environment variables
+    pub fn from_env() -> Self {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Build the configuration from an arbitrary key lookup (used by tests)
+    pub fn from_lookup<F>(lookup: F) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let get = |key: &str| lookup(key).map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+        let provider = match get("AI_PROVIDER").map(|v| v.to_ascii_lowercase()).as_deref() {
+            None | Some("gemini") => ProviderKind::Gemini,
+            Some("openai") | Some("openai_compatible") | Some("ollama") | Some("llamacpp") => {
+                ProviderKind::OpenAiCompatible
+            }
+            Some(other) => {
+                eprintln!("⚠️  Unknown AI_PROVIDER {:?}, using gemini", other);
+                ProviderKind::Gemini
+            }
+        };
+
+        let temperature = match get("AI_TEMPERATURE") {
+            Some(raw) => match raw.parse::<f32>() {
+                Ok(value) if (0.0..=2.0).contains(&value) => value,
+                _ => {
+                    eprintln!("⚠️  Invalid AI_TEMPERATURE {:?}, using 0.7", raw);
+                    0.7
+                }
+            },
+            None => 0.7,
+        };
+
+        let max_tokens = match get("AI_MAX_TOKENS") {
+            Some(raw) => match raw.parse::<u32>() {
+                Ok(value) if value > 0 => value,
+                _ => {
+                    eprintln!("⚠️  Invalid AI_MAX_TOKENS {:?}, using 1000", raw);
+                    1000
+                }
+            },
+            None => 1000,
+        };
+
+        Self {
+            provider,
+            model: get("AI_MODEL").unwrap_or_else(|| provider.default_model().to_string()),
+            base_url: get("AI_BASE_URL")
+                .unwrap_or_else(|| provider.default_base_url().to_string())
+                .trim_end_matches('/')
+                .to_string(),
+            // The legacy Gemini key must never be sent to another provider's server
+            api_key: get("AI_API_KEY")
+                .or_else(|| get("GEMINI_API_KEY").filter(|_| provider == ProviderKind::Gemini))
+                .unwrap_or_default(),
+            temperature,
+            max_tokens,
+        }
+    }
+}
+
+/// Create the provider selected by the configuration
+pub fn build_provider(config: &LlmConfig) -> Result<Box<dyn LlmProvider>, Box<dyn Error>> {
+    Ok(match config.provider {
+        ProviderKind::Gemini => Box::new(GeminiClient::new(config)?),
+        ProviderKind::OpenAiCompatible => Box::new(OpenAiCompatibleClient::new(config)),
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+
+    /// Serve one HTTP request with a canned response; the handle yields the raw request
+    pub async fn mock_http_server(status: u16, body: &str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let body = body.to_string();
+
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let content_length = text[..header_end]
+                        .lines()
+                        .find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().ok())?
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= header_end + 4 + content_length {
+                        break;
+                    }
+                }
+            }
+
+            let response = format!(
+                "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request).into_owned()
+        });
+
+        (base_url, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config(vars: &[(&str, &str)]) -> LlmConfig {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        LlmConfig::from_lookup(|key| vars.get(key).cloned())
+    }
+
+    #[test]
+    fn defaults_to_gemini() {
+        let cfg = config(&[("GEMINI_API_KEY", "key")]);
+        assert_eq!(cfg.provider, ProviderKind::Gemini);
+        assert_eq!(cfg.model, "gemini-1.5-flash");
+        assert_eq!(cfg.api_key, "key");
+        assert_eq!(cfg.temperature, 0.7);
+        assert_eq!(cfg.max_tokens, 1000);
+    }
+
+    #[test]
+    fn selects_openai_compatible_with_custom_settings() {
+        let cfg = config(&[
+            ("AI_PROVIDER", "OpenAI"),
+            ("AI_MODEL", "llama3"),
+            ("AI_BASE_URL", "http://localhost:11434/v1/"),
+            ("AI_TEMPERATURE", "0.2"),
+            ("AI_MAX_TOKENS", "512"),
+        ]);
+        assert_eq!(cfg.provider, ProviderKind::OpenAiCompatible);
+        assert_eq!(cfg.model, "llama3");
+        assert_eq!(cfg.base_url, "http://localhost:11434/v1");
+        assert_eq!(cfg.temperature, 0.2);
+        assert_eq!(cfg.max_tokens, 512);
+        assert!(cfg.api_key.is_empty());
+    }
+
+    #[test]
+    fn invalid_values_fall_back_to_defaults() {
+        let cfg = config(&[
+            ("AI_PROVIDER", "mystery"),
+            ("AI_TEMPERATURE", "hot"),
+            ("AI_MAX_TOKENS", "0"),
+        ]);
+        assert_eq!(cfg.provider, ProviderKind::Gemini);
+        assert_eq!(cfg.temperature, 0.7);
+        assert_eq!(cfg.max_tokens, 1000);
+    }
+
+    #[test]
+    fn gemini_key_is_not_used_for_other_providers() {
+        let cfg = config(&[("AI_PROVIDER", "openai"), ("GEMINI_API_KEY", "legacy")]);
+        assert_eq!(cfg.provider, ProviderKind::OpenAiCompatible);
+        assert!(cfg.api_key.is_empty());
+    }
+
+    #[test]
+    fn ai_api_key_wins_over_gemini_key() {
+        let cfg = config(&[("AI_API_KEY", "primary"), ("GEMINI_API_KEY", "legacy")]);
+        assert_eq!(cfg.api_key, "primary");
+    }
+}
@@ -1,6 +1,8 @@
 use serenity::builder::{CreateEmbed, CreateActionRow, CreateButton};
 use serenity::model::prelude::*;
 use crate::ai::emotions::EmotionType;
+use crate::ai::moderation::ModerationFlag;
+use crate::ai::provider::{LlmToolCall, TokenUsage};
 use crate::lang::{ImageManager, EmojiManager};
 use chrono::Utc;
 
@@ -73,6 +75,10 @@ pub struct AIResponseBuilder {
     thumbnail_override: Option<String>,
     footer_text: Option<String>,
     custom_color: Option<(u8, u8, u8)>,
+    tool_calls: Vec<LlmToolCall>,
+    sources: Vec<String>,
+    usage: Option<TokenUsage>,
+    moderation: Option<ModerationFlag>,
 }
 
 impl AIResponseBuilder {
@@ -84,6 +90,10 @@ impl AIResponseBuilder {
             thumbnail_override: None,
             footer_text: None,
             custom_color: None,
+            tool_calls: Vec::new(),
+            sources: Vec::new(),
+            usage: None,
+            moderation: None,
         }
     }
 
@@ -117,6 +127,29 @@ impl AIResponseBuilder {
         self
     }
 
+    pub fn tool_calls(mut self, tool_calls: Vec<LlmToolCall>) -> Self {
+        self.tool_calls = tool_calls;
+        self
+    }
+
+    /// Tokens the reply cost, as reported by the provider
+    pub fn usage(mut self, usage: Option<TokenUsage>) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    /// Why the reply was refused or redacted by moderation
+    pub fn moderation(mut self, flag: Option<ModerationFlag>) -> Self {
+        self.moderation = flag;
+        self
+    }
+
+    /// Knowledge base sources cited by the reply
+    pub fn sources(mut self, sources: Vec<String>) -> Self {
+        self.sources = sources;
+        self
+    }
+
     /// Build the Discord embed for this AI response
     pub fn build_embed(
         &self,
@@ -124,6 +157,7 @@ impl AIResponseBuilder {
         _emojis: &EmojiManager,
         author_name: &str,
         lang: &crate::lang::LanguageManager,
+        locale: &str,
     ) -> CreateEmbed {
         let color = if let Some(custom_color) = self.custom_color {
             custom_color
@@ -142,22 +176,33 @@ impl AIResponseBuilder {
         };
 
         // Build the embed title with emotion emoji using translations
-        let title = lang.format_ai_embed_title(self.emotion.emoji());
+        let title = lang.format_ai_embed_title(locale, self.emotion.emoji());
 
         // Build footer text using translations
         let footer_text = if let Some(ref custom_footer) = self.footer_text {
             custom_footer.clone()
         } else {
-            lang.format_ai_embed_footer(author_name, self.emotion.emoji())
+            lang.format_ai_embed_footer(locale, author_name, self.emotion.emoji())
         };
 
-        CreateEmbed::new()
+        let mut embed = CreateEmbed::new()
             .title(title)
             .description(&self.content)
             .color(Color::from_rgb(color.0, color.1, color.2))
             .thumbnail(thumbnail_url)
             .footer(serenity::builder::CreateEmbedFooter::new(footer_text))
-            .timestamp(Utc::now())
+            .timestamp(Utc::now());
+
+        if !self.sources.is_empty() {
+            let sources = self.sources
+                .iter()
+                .map(|source| format!("• {}", source))
+                .collect::<Vec<_>>()
+                .join("\n");
+            embed = embed.field(&lang.get(locale).ai.embeds.sources_field, sources, false);
+        }
+
+        embed
     }
 
     /// Build the action rows with buttons for this response
@@ -195,6 +240,21 @@ impl AIResponseBuilder {
         &self.content
     }
 
+    /// Get the tool calls requested by the model
+    pub fn get_tool_calls(&self) -> &[LlmToolCall] {
+        &self.tool_calls
+    }
+
+    /// Get the token usage reported for the reply
+    pub fn get_usage(&self) -> Option<TokenUsage> {
+        self.usage
+    }
+
+    /// Get the moderation flag, if the reply was refused or redacted
+    pub fn get_moderation(&self) -> Option<&ModerationFlag> {
+        self.moderation.as_ref()
+    }
+
     /// Get the emotion type
     pub fn get_emotion(&self) -> &EmotionType {
         &self.emotion
@@ -0,0 +1,139 @@
+//! Helpers for streamed model replies: server-sent event decoding and
+//! previewing the `content` field of a JSON reply that is still arriving.
+
+/// Splits a `text/event-stream` body into `data:` payloads as bytes arrive
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    buffer: Vec<u8>,
+    data: Vec<String>,
+}
+
+impl SseDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed raw bytes and return the payloads of every event completed by them
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut events = Vec::new();
+        while let Some(newline) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=newline).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                // A blank line terminates the event
+                if !self.data.is_empty() {
+                    events.push(self.data.join("\n"));
+                    self.data.clear();
+                }
+            } else if let Some(value) = line.strip_prefix("data:") {
+                self.data.push(value.strip_prefix(' ').unwrap_or(value).to_string());
+            }
+            // Comments (`:`), `event:`, `id:` and `retry:` lines carry nothing we use
+        }
+        events
+    }
+
+    /// Flush an event left unterminated when the stream closed
+    pub fn finish(&mut self) -> Option<String> {
+        if !self.buffer.is_empty() {
+            let rest = std::mem::take(&mut self.buffer);
+            let rest = String::from_utf8_lossy(&rest);
+            if let Some(value) = rest.trim_end().strip_prefix("data:") {
+                self.data.push(value.trim_start().to_string());
+            }
+        }
+        if self.data.is_empty() {
+            None
+        } else {
+            let event = self.data.join("\n");
+            self.data.clear();
+            Some(event)
+        }
+    }
+}
+
+/// Decode as much of the `"content"` string of a partial `AIJSONResponse` as has arrived
+///
+/// Returns `None` until the `content` value has started. An escape sequence cut in
+/// half by the stream is left out until the rest of it arrives.
+pub fn partial_response_content(raw: &str) -> Option<String> {
+    let key = raw.find("\"content\"")?;
+    let after_key = raw[key + "\"content\"".len()..].trim_start();
+    let value = after_key.strip_prefix(':')?.trim_start().strip_prefix('"')?;
+
+    let mut content = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => break,
+            '\\' => match chars.next() {
+                Some('n') => content.push('\n'),
+                Some('t') => content.push('\t'),
+                Some('r') => content.push('\r'),
+                Some('b') => content.push('\u{8}'),
+                Some('f') => content.push('\u{c}'),
+                Some('u') => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    if hex.len() < 4 {
+                        break;
+                    }
+                    match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                        Some(decoded) => content.push(decoded),
+                        // Surrogate pairs and bad escapes are left to the final parse
+                        None => content.push('\u{FFFD}'),
+                    }
+                }
+                Some(other) => content.push(other),
+                None => break,
+            },
+            other => content.push(other),
+        }
+    }
+    Some(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sse_events_split_across_chunks() {
+        let mut decoder = SseDecoder::new();
+        assert!(decoder.push(b"data: {\"a\":").is_empty());
+        assert_eq!(decoder.push(b"1}\r\n\r\ndata: two\n"), vec!["{\"a\":1}".to_string()]);
+        assert_eq!(decoder.push(b": keep-alive\n\n"), vec!["two".to_string()]);
+        assert_eq!(decoder.finish(), None);
+    }
+
+    #[test]
+    fn sse_unterminated_event_is_flushed() {
+        let mut decoder = SseDecoder::new();
+        assert!(decoder.push(b"data: [DONE]").is_empty());
+        assert_eq!(decoder.finish().as_deref(), Some("[DONE]"));
+    }
+
+    #[test]
+    fn partial_content_before_value_starts() {
+        assert_eq!(partial_response_content(""), None);
+        assert_eq!(partial_response_content("{\"content\""), None);
+        assert_eq!(partial_response_content("{\"content\": "), None);
+    }
+
+    #[test]
+    fn partial_content_decodes_escapes() {
+        let raw = "{\"content\": \"Hi \\\"there\\\"\\nline \\u00e9";
+        assert_eq!(partial_response_content(raw).as_deref(), Some("Hi \"there\"\nline é"));
+    }
+
+    #[test]
+    fn partial_content_stops_at_cut_escape_and_closing_quote() {
+        assert_eq!(partial_response_content("{\"content\":\"ab\\").as_deref(), Some("ab"));
+        assert_eq!(partial_response_content("{\"content\":\"ab\\u00").as_deref(), Some("ab"));
+        let complete = "{\"content\":\"done\",\"color\":\"#fff\"}";
+        assert_eq!(partial_response_content(complete).as_deref(), Some("done"));
+    }
+}
@@ -0,0 +1,355 @@
+//! Bot actions the AI assistant can request through function calling.
+//!
+//! The model only ever *asks* for an action: calls are validated into a
+//! `ToolAction`, checked against the requester, and actions with side effects
+//! wait for the requester to press a confirmation button (see `commands::ai_tools`).
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::provider::{LlmTool, LlmToolCall};
+
+/// Pending reminders a user may hold before the assistant refuses to add more
+pub const MAX_PENDING_REMINDERS: usize = 5;
+
+/// Furthest ahead the assistant may schedule a reminder (30 days)
+pub const MAX_REMINDER_MINUTES: i64 = 60 * 24 * 30;
+
+/// Longest reminder text or ticket reason accepted from the model
+const MAX_TEXT_LENGTH: usize = 500;
+
+/// Functions exposed to the model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AiTool {
+    CreateReminder,
+    OpenTicket,
+    CommissionAvailability,
+    GithubActivity,
+}
+
+impl AiTool {
+    pub const ALL: [AiTool; 4] = [
+        AiTool::CreateReminder,
+        AiTool::OpenTicket,
+        AiTool::CommissionAvailability,
+        AiTool::GithubActivity,
+    ];
+
+    /// Function name sent to the model
+    pub fn name(self) -> &'static str {
+        match self {
+            AiTool::CreateReminder => "create_reminder",
+            AiTool::OpenTicket => "open_ticket",
+            AiTool::CommissionAvailability => "show_commission_availability",
+            AiTool::GithubActivity => "show_latest_github_activity",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|tool| tool.name() == name)
+    }
+
+    /// Word stems in a message that make the tool worth offering
+    ///
+    /// Covers every language in `lang/`, since people write in their own language
+    /// whatever the server locale is.
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            AiTool::CreateReminder => &[
+                "remind", "reminder", "recuérda", "recuerda", "recordatorio", "avísame", "avisame",
+                "rappel", "lembr", "напомн", "提醒",
+            ],
+            AiTool::OpenTicket => &["ticket", "support", "soporte", "suporte", "тикет", "поддержк", "工单", "客服"],
+            AiTool::CommissionAvailability => &["commission", "comisión", "comision", "encargo", "comiss", "комисси", "委托", "约稿"],
+            AiTool::GithubActivity => &["github", "repo", "commit", "коммит", "репозитор", "仓库"],
+        }
+    }
+
+    /// Declaration (description and argument schema) sent to the model
+    pub fn declaration(self) -> LlmTool {
+        let (description, parameters) = match self {
+            AiTool::CreateReminder => (
+                "Schedule a personal reminder for the user in this channel. The user confirms it with a button before it is saved.",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "message": { "type": "string", "description": "What to remind the user about" },
+                        "minutes_from_now": { "type": "integer", "description": "Delay in minutes, at most 43200 (30 days)" },
+                    },
+                    "required": ["message", "minutes_from_now"],
+                }),
+            ),
+            AiTool::OpenTicket => (
+                "Open a private support ticket channel for the user. The user confirms it with a button before it is created.",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "reason": { "type": "string", "description": "Short summary of what the user needs help with" },
+                    },
+                }),
+            ),
+            AiTool::CommissionAvailability => (
+                "Show whether commissions are currently open, waitlist-only or closed, with the button to request one.",
+                serde_json::json!({ "type": "object", "properties": {} }),
+            ),
+            AiTool::GithubActivity => (
+                "Show the owner's most recent public GitHub activity.",
+                serde_json::json!({ "type": "object", "properties": {} }),
+            ),
+        };
+
+        LlmTool {
+            name: self.name().to_string(),
+            description: description.to_string(),
+            parameters,
+        }
+    }
+}
+
+/// Declarations of the tools a message plausibly asks for, for `LlmRequest::tools`
+///
+/// Gemini can't combine function calling with a response schema, so tools are
+/// only offered when the message mentions what they do and every other reply
+/// keeps structured output.
+pub fn declarations_for(message: &str) -> Vec<LlmTool> {
+    let message = message.to_lowercase();
+    AiTool::ALL
+        .into_iter()
+        .filter(|tool| tool.keywords().iter().any(|keyword| message.contains(keyword)))
+        .map(AiTool::declaration)
+        .collect()
+}
+
+/// A validated tool call; persisted with pending confirmations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "tool", rename_all = "snake_case")]
+pub enum ToolAction {
+    CreateReminder {
+        message: String,
+        remind_at: DateTime<Utc>,
+    },
+    OpenTicket {
+        reason: Option<String>,
+    },
+    CommissionAvailability,
+    GithubActivity,
+}
+
+/// Why a tool call from the model couldn't be turned into an action
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToolCallError {
+    UnknownTool(String),
+    InvalidArguments(String),
+}
+
+impl std::fmt::Display for ToolCallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ToolCallError::UnknownTool(name) => write!(f, "unknown tool `{}`", name),
+            ToolCallError::InvalidArguments(reason) => write!(f, "invalid arguments: {}", reason),
+        }
+    }
+}
+
+/// Why the requester may not run an action
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToolDenied {
+    /// The action needs a server (e.g. tickets are guild channels)
+    GuildOnly,
+    /// The requester already has this ticket channel open
+    TicketAlreadyOpen(String),
+    /// The requester already holds `MAX_PENDING_REMINDERS` reminders
+    TooManyReminders,
+}
+
+/// The user asking for an action, with the state the permission checks need
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolRequester {
+    pub in_guild: bool,
+    pub active_ticket: Option<String>,
+    pub pending_reminders: usize,
+}
+
+impl ToolAction {
+    /// Validate a model tool call into an action
+    pub fn from_call(call: &LlmToolCall, now: DateTime<Utc>) -> Result<Self, ToolCallError> {
+        let tool = AiTool::parse(&call.name).ok_or_else(|| ToolCallError::UnknownTool(call.name.clone()))?;
+        let text = |key: &str| {
+            call.arguments
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(|v| v.chars().take(MAX_TEXT_LENGTH).collect::<String>())
+        };
+
+        Ok(match tool {
+            AiTool::CreateReminder => {
+                let message = text("message")
+                    .ok_or_else(|| ToolCallError::InvalidArguments("missing reminder message".to_string()))?;
+                // Models sometimes send integers as floats or strings
+                let minutes = match call.arguments.get("minutes_from_now") {
+                    Some(serde_json::Value::Number(n)) => n.as_i64().or_else(|| n.as_f64().map(|f| f.round() as i64)),
+                    Some(serde_json::Value::String(s)) => s.trim().parse::<i64>().ok(),
+                    _ => None,
+                }
+                .ok_or_else(|| ToolCallError::InvalidArguments("missing reminder delay".to_string()))?;
+                if !(1..=MAX_REMINDER_MINUTES).contains(&minutes) {
+                    return Err(ToolCallError::InvalidArguments(format!(
+                        "reminder delay must be between 1 and {} minutes",
+                        MAX_REMINDER_MINUTES
+                    )));
+                }
+                ToolAction::CreateReminder {
+                    message,
+                    remind_at: now + Duration::minutes(minutes),
+                }
+            }
+            AiTool::OpenTicket => ToolAction::OpenTicket { reason: text("reason") },
+            AiTool::CommissionAvailability => ToolAction::CommissionAvailability,
+            AiTool::GithubActivity => ToolAction::GithubActivity,
+        })
+    }
+
+    pub fn tool(&self) -> AiTool {
+        match self {
+            ToolAction::CreateReminder { .. } => AiTool::CreateReminder,
+            ToolAction::OpenTicket { .. } => AiTool::OpenTicket,
+            ToolAction::CommissionAvailability => AiTool::CommissionAvailability,
+            ToolAction::GithubActivity => AiTool::GithubActivity,
+        }
+    }
+
+    /// Check whether the requester may run this action
+    ///
+    /// Run again at confirmation time since the state may have changed meanwhile.
+    pub fn check_permission(&self, requester: &ToolRequester) -> Result<(), ToolDenied> {
+        match self {
+            ToolAction::CreateReminder { .. } => {
+                if requester.pending_reminders >= MAX_PENDING_REMINDERS {
+                    return Err(ToolDenied::TooManyReminders);
+                }
+                Ok(())
+            }
+            ToolAction::OpenTicket { .. } => {
+                if !requester.in_guild {
+                    return Err(ToolDenied::GuildOnly);
+                }
+                if let Some(channel) = &requester.active_ticket {
+                    return Err(ToolDenied::TicketAlreadyOpen(channel.clone()));
+                }
+                Ok(())
+            }
+            // Read-only and public
+            ToolAction::CommissionAvailability | ToolAction::GithubActivity => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: serde_json::Value) -> LlmToolCall {
+        LlmToolCall {
+            name: name.to_string(),
+            arguments,
+        }
+    }
+
+    fn requester() -> ToolRequester {
+        ToolRequester {
+            in_guild: true,
+            active_ticket: None,
+            pending_reminders: 0,
+        }
+    }
+
+    #[test]
+    fn tool_names_round_trip() {
+        for tool in AiTool::ALL {
+            assert_eq!(AiTool::parse(tool.name()), Some(tool));
+            assert_eq!(tool.declaration().name, tool.name());
+        }
+        assert_eq!(AiTool::parse("delete_server"), None);
+    }
+
+    #[test]
+    fn tools_are_only_offered_when_the_message_calls_for_them() {
+        assert!(declarations_for("How much is a full illustration?").is_empty());
+        let names = |message: &str| declarations_for(message).into_iter().map(|tool| tool.name).collect::<Vec<_>>();
+        assert_eq!(names("Remind me to stretch in 30 minutes"), ["create_reminder"]);
+        assert_eq!(names("¿Están abiertas las comisiones?"), ["show_commission_availability"]);
+        assert_eq!(names("open a ticket about my GitHub repo"), ["open_ticket", "show_latest_github_activity"]);
+        assert_eq!(names("Rappelle-moi de m'étirer dans 30 minutes"), ["create_reminder"]);
+        assert_eq!(names("Напомни мне через час"), ["create_reminder"]);
+        assert_eq!(names("委托现在开放吗？"), ["show_commission_availability"]);
+    }
+
+    #[test]
+    fn reminder_call_is_validated() {
+        let now = Utc::now();
+        let action = ToolAction::from_call(
+            &call("create_reminder", serde_json::json!({"message": " stretch ", "minutes_from_now": "30"})),
+            now,
+        )
+        .unwrap();
+        assert_eq!(action, ToolAction::CreateReminder {
+            message: "stretch".to_string(),
+            remind_at: now + Duration::minutes(30),
+        });
+        assert_eq!(action.tool(), AiTool::CreateReminder);
+
+        for arguments in [
+            serde_json::json!({"minutes_from_now": 5}),
+            serde_json::json!({"message": "x"}),
+            serde_json::json!({"message": "x", "minutes_from_now": 0}),
+            serde_json::json!({"message": "x", "minutes_from_now": MAX_REMINDER_MINUTES + 1}),
+        ] {
+            assert!(matches!(
+                ToolAction::from_call(&call("create_reminder", arguments), now),
+                Err(ToolCallError::InvalidArguments(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn unknown_tools_are_rejected() {
+        assert_eq!(
+            ToolAction::from_call(&call("ban_user", serde_json::json!({})), Utc::now()),
+            Err(ToolCallError::UnknownTool("ban_user".to_string()))
+        );
+    }
+
+    #[test]
+    fn permission_checks() {
+        let ticket = ToolAction::OpenTicket { reason: None };
+        assert_eq!(ticket.check_permission(&requester()), Ok(()));
+        assert_eq!(
+            ticket.check_permission(&ToolRequester { in_guild: false, ..requester() }),
+            Err(ToolDenied::GuildOnly)
+        );
+        assert_eq!(
+            ticket.check_permission(&ToolRequester { active_ticket: Some("7".to_string()), ..requester() }),
+            Err(ToolDenied::TicketAlreadyOpen("7".to_string()))
+        );
+
+        let reminder = ToolAction::CreateReminder {
+            message: "x".to_string(),
+            remind_at: Utc::now(),
+        };
+        assert_eq!(
+            reminder.check_permission(&ToolRequester { pending_reminders: MAX_PENDING_REMINDERS, ..requester() }),
+            Err(ToolDenied::TooManyReminders)
+        );
+        assert_eq!(ToolAction::GithubActivity.check_permission(&ToolRequester { in_guild: false, ..requester() }), Ok(()));
+    }
+
+    #[test]
+    fn action_serialization_round_trip() {
+        let action = ToolAction::OpenTicket { reason: Some("billing".to_string()) };
+        let json = serde_json::to_string(&action).unwrap();
+        assert!(json.contains("\"tool\":\"open_ticket\""));
+        assert_eq!(serde_json::from_str::<ToolAction>(&json).unwrap(), action);
+    }
+}
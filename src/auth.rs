@@ -0,0 +1,179 @@
+//! Authorization for privileged actions.
+//!
+//! Privileged actions are named capabilities. `config::OWNER_ID` holds all of
+//! them and is the only one who can delegate them, per guild, to roles or users
+//! with /capability. `update_bot` cannot be delegated: self-update stays pinned
+//! to the owner as `docs/tasks/self-update-0714/DECISIONS.md` requires.
+
+use serenity::all::{CommandInteraction, ComponentInteraction, GuildId, Member, Permissions, RoleId, UserId};
+
+use crate::config;
+use crate::data::{CapabilityGrants, DataManager};
+
+/// A privileged action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    /// Set up the ticket panel and close anyone's tickets
+    ManageTickets,
+    /// Set up the feedback channel
+    ManageFeedback,
+    /// Bulk delete messages with /purge
+    Purge,
+    /// Change the AI channel, quotas, personas and reply settings
+    ConfigureAi,
+    /// Run /update (never delegated)
+    UpdateBot,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 5] = [
+        Capability::ManageTickets,
+        Capability::ManageFeedback,
+        Capability::Purge,
+        Capability::ConfigureAi,
+        Capability::UpdateBot,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Capability::ManageTickets => "manage_tickets",
+            Capability::ManageFeedback => "manage_feedback",
+            Capability::Purge => "purge",
+            Capability::ConfigureAi => "configure_ai",
+            Capability::UpdateBot => "update_bot",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|capability| capability.as_str() == value)
+    }
+
+    /// Whether the owner may hand it out
+    pub fn is_delegable(self) -> bool {
+        self != Capability::UpdateBot
+    }
+
+    /// Guild permission that grants it without a delegation, matching who
+    /// could use the action before capabilities existed
+    fn implied_by(self) -> Option<Permissions> {
+        match self {
+            Capability::ManageTickets | Capability::ManageFeedback => Some(Permissions::ADMINISTRATOR),
+            Capability::Purge => Some(Permissions::MANAGE_MESSAGES),
+            Capability::ConfigureAi | Capability::UpdateBot => None,
+        }
+    }
+
+    /// Members Discord shows its commands to before any Integrations override:
+    /// those with the implied permission, or nobody but admins when there is none.
+    /// Delegated holders are let in through the override.
+    pub fn default_permissions(self) -> Permissions {
+        self.implied_by().unwrap_or_else(Permissions::empty)
+    }
+}
+
+/// The user behind an interaction
+pub struct Caller<'a> {
+    pub user_id: UserId,
+    pub guild_id: Option<GuildId>,
+    /// Guild member, with the permissions Discord computed for the channel
+    pub member: Option<&'a Member>,
+}
+
+impl<'a> Caller<'a> {
+    pub fn of_command(command: &'a CommandInteraction) -> Self {
+        Self {
+            user_id: command.user.id,
+            guild_id: command.guild_id,
+            member: command.member.as_deref(),
+        }
+    }
+
+    pub fn of_component(component: &'a ComponentInteraction) -> Self {
+        Self {
+            user_id: component.user.id,
+            guild_id: component.guild_id,
+            member: component.member.as_ref(),
+        }
+    }
+
+    pub fn is_owner(&self) -> bool {
+        self.user_id.get() == config::OWNER_ID
+    }
+
+    /// Channel permissions; Discord only sends them for guild interactions
+    pub fn permissions(&self) -> Option<Permissions> {
+        self.member.and_then(|member| member.permissions)
+    }
+}
+
+/// Whether a user holds a capability, given its delegations in the guild
+pub fn holds(
+    capability: Capability,
+    user_id: UserId,
+    roles: &[RoleId],
+    permissions: Option<Permissions>,
+    grants: &CapabilityGrants,
+) -> bool {
+    if user_id.get() == config::OWNER_ID {
+        return true;
+    }
+    if !capability.is_delegable() {
+        return false;
+    }
+    let implied = capability.implied_by().zip(permissions).is_some_and(|(required, permissions)| {
+        permissions.administrator() || permissions.contains(required)
+    });
+    implied
+        || grants.users.contains(&user_id.get())
+        || roles.iter().any(|role_id| grants.roles.contains(&role_id.get()))
+}
+
+/// Whether the caller holds a capability
+pub fn allowed(data_manager: &DataManager, caller: &Caller, capability: Capability) -> bool {
+    let grants = match caller.guild_id {
+        Some(guild_id) if capability.is_delegable() && !caller.is_owner() => {
+            data_manager.get_capability_grants(&guild_id.to_string(), capability.as_str())
+        }
+        _ => CapabilityGrants::default(),
+    };
+    let roles = caller.member.map_or(&[][..], |member| member.roles.as_slice());
+    holds(capability, caller.user_id, roles, caller.permissions(), &grants)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn owner_holds_everything_and_update_is_never_delegated() {
+        let owner = UserId::new(config::OWNER_ID);
+        let staff = UserId::new(config::OWNER_ID + 1);
+        let grants = CapabilityGrants {
+            roles: [7].into_iter().collect(),
+            users: [staff.get()].into_iter().collect(),
+        };
+
+        for capability in Capability::ALL {
+            assert!(holds(capability, owner, &[], None, &CapabilityGrants::default()));
+            assert_eq!(Capability::parse(capability.as_str()), Some(capability));
+        }
+        assert!(!holds(Capability::UpdateBot, staff, &[RoleId::new(7)], Some(Permissions::ADMINISTRATOR), &grants));
+        assert!(!Capability::UpdateBot.is_delegable());
+    }
+
+    #[test]
+    fn delegations_and_implied_permissions_grant_capabilities() {
+        let user = UserId::new(config::OWNER_ID + 1);
+        let grants = CapabilityGrants {
+            roles: [7].into_iter().collect(),
+            users: Default::default(),
+        };
+
+        assert!(holds(Capability::ManageTickets, user, &[RoleId::new(7)], None, &grants));
+        assert!(!holds(Capability::ManageTickets, user, &[RoleId::new(8)], None, &grants));
+        assert!(holds(Capability::Purge, user, &[], Some(Permissions::MANAGE_MESSAGES), &CapabilityGrants::default()));
+        assert!(holds(Capability::ManageFeedback, user, &[], Some(Permissions::ADMINISTRATOR), &CapabilityGrants::default()));
+        assert!(!holds(Capability::ConfigureAi, user, &[], Some(Permissions::ADMINISTRATOR), &CapabilityGrants::default()));
+        assert!(!holds(Capability::Purge, user, &[], None, &CapabilityGrants::default()));
+    }
+}
@@ -0,0 +1,487 @@
+use serenity::all::{
+    Color, CommandDataOption, CommandDataOptionValue, CommandInteraction, Context, CreateAttachment,
+    CreateEmbed, CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage,
+    CreateMessage,
+};
+use chrono::Utc;
+use crate::ai::{AIManager, TokenUsage};
+use crate::auth::{Caller, Capability};
+use crate::data::DataManager;
+use crate::lang::LanguageManager;
+use super::registry::Access;
+use super::{respond_ephemeral, truncate_field};
+
+/// Users listed by /ai usage
+const TOP_CONSUMERS: usize = 10;
+
+/// Exchanges listed by /ai flagged
+const FLAGGED_SHOWN: usize = 10;
+
+/// Characters of a flagged message or reply quoted by /ai flagged
+const FLAGGED_EXCERPT_LENGTH: usize = 150;
+
+/// Handle the /ai command (memory is for everyone; everything else needs configure_ai)
+pub async fn handle_ai_command(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data_manager: &DataManager,
+    ai_manager: &AIManager,
+    lang: &LanguageManager,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let (subcommand, options) = match command.data.options.first() {
+        Some(CommandDataOption { name, value: CommandDataOptionValue::SubCommand(options), .. }) => {
+            (name.as_str(), options.as_slice())
+        }
+        Some(CommandDataOption { name, value: CommandDataOptionValue::SubCommandGroup(options), .. }) if name == "memory" => {
+            return handle_ai_memory(ctx, command, options, data_manager, lang, locale).await;
+        }
+        Some(CommandDataOption { name, value: CommandDataOptionValue::SubCommandGroup(options), .. }) => {
+            (name.as_str(), options.as_slice())
+        }
+        _ => return respond_ephemeral(ctx, command, &lang.get(locale).responses.missing_subcommand).await,
+    };
+
+    // Everything but /ai memory configures the AI
+    let access = Access::Capability(Capability::ConfigureAi);
+    if !access.allows(data_manager, &Caller::of_command(command)) {
+        return respond_ephemeral(ctx, command, &access.denial(lang, locale)).await;
+    }
+
+    match subcommand {
+        "usage" => handle_ai_usage(ctx, command, data_manager, ai_manager, lang, locale).await,
+        "quota" => handle_ai_quota(ctx, command, options, data_manager, lang, locale).await,
+        "persona" => handle_ai_persona(ctx, command, options, data_manager, ai_manager, lang, locale).await,
+        "flagged" => handle_ai_flagged(ctx, command, data_manager, lang, locale).await,
+        "replies" => handle_ai_replies(ctx, command, options, data_manager, lang, locale).await,
+        "dms" => handle_ai_dms(ctx, command, options, data_manager, lang, locale).await,
+        _ => respond_ephemeral(ctx, command, &lang.get(locale).responses.unknown_subcommand).await,
+    }
+}
+
+/// Handle /ai memory show|export|forget|opt-out for the calling user
+async fn handle_ai_memory(
+    ctx: &Context,
+    command: &CommandInteraction,
+    options: &[CommandDataOption],
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let memory = &lang.get(locale).ai.memory;
+    let user_id = command.user.id.to_string();
+
+    let (subcommand, options) = match options.first() {
+        Some(CommandDataOption { name, value: CommandDataOptionValue::SubCommand(options), .. }) => {
+            (name.as_str(), options.as_slice())
+        }
+        _ => return respond_ephemeral(ctx, command, &lang.get(locale).responses.missing_subcommand).await,
+    };
+
+    match subcommand {
+        "show" => {
+            let context = data_manager.get_conversation_context(&user_id);
+            let opted_out = data_manager.is_ai_memory_opted_out(&user_id);
+
+            let field_text = |text: Option<&str>| match text.filter(|t| !t.is_empty()) {
+                Some(text) => truncate_field(text),
+                None => memory.nothing_stored.clone(),
+            };
+            let messages = match context.as_ref().and_then(|c| c.messages.first().map(|m| (c.messages.len(), m.timestamp))) {
+                Some((count, since)) => lang.format_ai_memory_messages(locale, count, since.timestamp()),
+                None => memory.nothing_stored.clone(),
+            };
+
+            let embed = CreateEmbed::new()
+                .title(&memory.show_title)
+                .color(Color::from_rgb(105, 90, 205))
+                .field(&memory.summary_field, field_text(context.as_ref().map(|c| c.get_summary())), false)
+                .field(&memory.conversation_field, field_text(context.as_ref().map(|c| c.conversation_summary.as_str())), false)
+                .field(&memory.messages_field, messages, true)
+                .field(&memory.status_field, if opted_out { &memory.status_off } else { &memory.status_on }, true)
+                .footer(CreateEmbedFooter::new(&memory.footer));
+
+            let response = CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .add_embed(embed)
+                    .ephemeral(true),
+            );
+            command.create_response(&ctx.http, response).await?;
+            Ok(())
+        }
+        "export" => {
+            let export = serde_json::json!({
+                "user_id": user_id,
+                "exported_at": Utc::now(),
+                "memory_opted_out": data_manager.is_ai_memory_opted_out(&user_id),
+                "conversation": data_manager.get_conversation_context(&user_id),
+                "usage": data_manager.get_ai_usage().users.get(&user_id),
+            });
+            let file = CreateAttachment::bytes(serde_json::to_vec_pretty(&export)?, "ai-memory.json");
+            let dm = command.user
+                .direct_message(&ctx.http, CreateMessage::new().content(&memory.export_dm).add_file(file))
+                .await;
+
+            match dm {
+                Ok(_) => respond_ephemeral(ctx, command, &memory.export_sent).await,
+                Err(e) => {
+                    eprintln!("Error sending AI memory export to {}: {}", command.user.name, e);
+                    respond_ephemeral(ctx, command, &memory.export_dm_failed).await
+                }
+            }
+        }
+        "forget" => {
+            data_manager.clear_conversation(&user_id).map_err(|e| e.to_string())?;
+            println!("🗑️ Cleared AI memory for user {}", command.user.name);
+            respond_ephemeral(ctx, command, &memory.forget_done).await
+        }
+        "opt-out" => {
+            let enabled = options.iter().find(|o| o.name == "enabled").and_then(|o| match &o.value {
+                CommandDataOptionValue::Boolean(value) => Some(*value),
+                _ => None,
+            });
+            let Some(enabled) = enabled else {
+                return respond_ephemeral(ctx, command, &lang.format_missing_option(locale, "enabled")).await;
+            };
+
+            data_manager.set_ai_memory_opt_out(&user_id, enabled).map_err(|e| e.to_string())?;
+            if enabled {
+                // Opting out also forgets what was stored so far
+                data_manager.clear_conversation(&user_id).map_err(|e| e.to_string())?;
+                respond_ephemeral(ctx, command, &memory.opt_out_on).await
+            } else {
+                respond_ephemeral(ctx, command, &memory.opt_out_off).await
+            }
+        }
+        _ => respond_ephemeral(ctx, command, &lang.get(locale).responses.unknown_subcommand).await,
+    }
+}
+
+/// Show the top AI consumers with token counts and estimated cost
+async fn handle_ai_usage(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data_manager: &DataManager,
+    ai_manager: &AIManager,
+    lang: &LanguageManager,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let messages = &lang.get(locale).ai.usage;
+    let usage = data_manager.get_ai_usage();
+    let today = Utc::now().date_naive();
+
+    let total = usage.users.values().fold(TokenUsage::default(), |total, user| TokenUsage {
+        prompt_tokens: total.prompt_tokens + user.prompt_tokens,
+        response_tokens: total.response_tokens + user.response_tokens,
+    });
+    let total_messages: u64 = usage.users.values().map(|user| user.total_messages).sum();
+    let messages_today: u32 = usage.users.values().map(|user| user.messages_on(today)).sum();
+
+    let top = usage.top_consumers(TOP_CONSUMERS);
+    let ranking = if top.is_empty() {
+        messages.nothing_recorded.clone()
+    } else {
+        top.iter()
+            .enumerate()
+            .map(|(index, (user_id, user))| {
+                let cost = ai_manager.estimate_cost(TokenUsage {
+                    prompt_tokens: user.prompt_tokens,
+                    response_tokens: user.response_tokens,
+                });
+                lang.format_ai_usage_ranking_line(locale, index + 1, &format!("<@{}>", user_id), user, today, cost)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let mut quotas = vec![lang.format_ai_quota_default(locale, usage.quotas.default_daily)];
+    let mut roles: Vec<_> = usage.quotas.roles.iter().collect();
+    roles.sort();
+    quotas.extend(roles.into_iter().map(|(role_id, daily)| format!("<@&{}>: {}", role_id, lang.format_ai_quota(locale, *daily))));
+
+    let embed = CreateEmbed::new()
+        .title(&messages.title)
+        .description(ranking)
+        .color(Color::from_rgb(105, 90, 205))
+        .field(&messages.messages_field, lang.format_ai_usage_messages(locale, total_messages, messages_today), true)
+        .field(&messages.tokens_field, lang.format_ai_usage_tokens(locale, &total), true)
+        .field(&messages.cost_field, lang.format_ai_usage_cost(locale, ai_manager.estimate_cost(total)), true)
+        .field(&messages.quotas_field, quotas.join("\n"), false)
+        .footer(CreateEmbedFooter::new(&messages.footer))
+        .timestamp(Utc::now());
+
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .add_embed(embed)
+            .ephemeral(true),
+    );
+    command.create_response(&ctx.http, response).await?;
+    Ok(())
+}
+
+/// Set the daily message quota for a role, or the default quota without a role
+async fn handle_ai_quota(
+    ctx: &Context,
+    command: &CommandInteraction,
+    options: &[CommandDataOption],
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let messages = &lang.get(locale).ai.usage;
+    let daily = options.iter().find(|o| o.name == "daily").and_then(|o| match &o.value {
+        CommandDataOptionValue::Integer(value) => u32::try_from(*value).ok(),
+        _ => None,
+    });
+    let Some(daily) = daily else {
+        return respond_ephemeral(ctx, command, &messages.quota_invalid).await;
+    };
+    let role_id = options.iter().find(|o| o.name == "role").and_then(|o| match &o.value {
+        CommandDataOptionValue::Role(id) => Some(id.to_string()),
+        _ => None,
+    });
+
+    data_manager
+        .set_ai_quota(role_id.clone(), daily)
+        .map_err(|e| e.to_string())?;
+
+    let description = match role_id {
+        Some(role_id) => lang.format_ai_quota_role_set(locale, &format!("<@&{}>", role_id), daily),
+        None => lang.format_ai_quota_default_set(locale, daily),
+    };
+    let embed = CreateEmbed::new()
+        .title(&messages.quota_title)
+        .description(description)
+        .color(Color::from_rgb(0, 255, 127));
+
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .add_embed(embed)
+            .ephemeral(true),
+    );
+    command.create_response(&ctx.http, response).await?;
+    Ok(())
+}
+
+/// Switch a channel's persona, or list personas and channel assignments without a name
+async fn handle_ai_persona(
+    ctx: &Context,
+    command: &CommandInteraction,
+    options: &[CommandDataOption],
+    data_manager: &DataManager,
+    ai_manager: &AIManager,
+    lang: &LanguageManager,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let messages = &lang.get(locale).ai.personas;
+    let personas = ai_manager.personas();
+    let name = options.iter().find(|o| o.name == "name").and_then(|o| match &o.value {
+        CommandDataOptionValue::String(value) => Some(value.trim().to_lowercase()),
+        _ => None,
+    });
+    let channel_id = options.iter().find(|o| o.name == "channel").and_then(|o| match &o.value {
+        CommandDataOptionValue::Channel(id) => Some(*id),
+        _ => None,
+    }).unwrap_or(command.channel_id);
+
+    let Some(name) = name else {
+        let list = personas
+            .profiles()
+            .iter()
+            .map(|profile| {
+                let default = if profile.name == personas.default_name() { messages.default_marker.as_str() } else { "" };
+                format!("**{}**{} — {}", profile.name, default, profile.description)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut assignments: Vec<_> = data_manager.get_ai_personas().into_iter().collect();
+        assignments.sort();
+        let assignments = if assignments.is_empty() {
+            messages.no_assignments.clone()
+        } else {
+            assignments
+                .iter()
+                .map(|(channel_id, persona)| format!("<#{}> → **{}**", channel_id, persona))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let embed = CreateEmbed::new()
+            .title(&messages.list_title)
+            .description(list)
+            .color(Color::from_rgb(105, 90, 205))
+            .field(&messages.channels_field, assignments, false)
+            .footer(CreateEmbedFooter::new(&messages.list_footer));
+
+        let response = CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .add_embed(embed)
+                .ephemeral(true),
+        );
+        command.create_response(&ctx.http, response).await?;
+        return Ok(());
+    };
+
+    if !personas.contains(&name) {
+        return respond_ephemeral(ctx, command, &lang.format_ai_persona_unknown(locale, &name)).await;
+    }
+
+    // Channels on the default persona aren't stored, so they follow AI_DEFAULT_PERSONA
+    let stored = (name != personas.default_name()).then(|| name.clone());
+    data_manager
+        .set_ai_persona(&channel_id.to_string(), stored)
+        .map_err(|e| e.to_string())?;
+
+    let embed = CreateEmbed::new()
+        .title(&messages.updated_title)
+        .description(lang.format_ai_persona_updated(locale, &format!("<#{}>", channel_id), &name))
+        .color(Color::from_rgb(0, 255, 127));
+
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .add_embed(embed)
+            .ephemeral(true),
+    );
+    command.create_response(&ctx.http, response).await?;
+    Ok(())
+}
+
+/// Show the most recent AI exchanges flagged by moderation
+async fn handle_ai_flagged(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let moderation = &lang.get(locale).ai.moderation;
+    let entries = data_manager.get_ai_moderation_log(FLAGGED_SHOWN);
+    let excerpt = |text: &str| {
+        let line = text.replace('\n', " ");
+        if line.chars().count() > FLAGGED_EXCERPT_LENGTH {
+            format!("{}…", line.chars().take(FLAGGED_EXCERPT_LENGTH).collect::<String>())
+        } else {
+            line
+        }
+    };
+
+    let description = if entries.is_empty() {
+        moderation.flagged_empty.clone()
+    } else {
+        entries
+            .iter()
+            .map(|entry| {
+                let mut text = format!(
+                    "<t:{}:R> <@{}> in <#{}> — **{} {}**: {}\n> {}",
+                    entry.created_at.timestamp(),
+                    entry.user_id,
+                    entry.channel_id,
+                    entry.stage,
+                    entry.action,
+                    entry.reason,
+                    excerpt(&entry.message)
+                );
+                if let Some(response) = entry.response.as_deref().filter(|r| !r.trim().is_empty()) {
+                    text.push_str(&format!("\n> ↳ {}", excerpt(response)));
+                }
+                text
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    };
+
+    let embed = CreateEmbed::new()
+        .title(&moderation.flagged_title)
+        .description(description.chars().take(4096).collect::<String>())
+        .color(Color::ORANGE)
+        .footer(CreateEmbedFooter::new(lang.format_ai_flagged_footer(locale, crate::config::AI_MODERATION_LOG_LIMIT)));
+
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .add_embed(embed)
+            .ephemeral(true),
+    );
+    command.create_response(&ctx.http, response).await?;
+    Ok(())
+}
+
+/// Handle /ai replies allow|disallow|list: channels of this server where mentions are answered
+async fn handle_ai_replies(
+    ctx: &Context,
+    command: &CommandInteraction,
+    options: &[CommandDataOption],
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let Some(guild_id) = command.guild_id else {
+        return respond_ephemeral(ctx, command, &lang.get(locale).ai.replies.guild_only).await;
+    };
+    let guild_key = guild_id.to_string();
+
+    let (subcommand, options) = match options.first() {
+        Some(CommandDataOption { name, value: CommandDataOptionValue::SubCommand(options), .. }) => {
+            (name.as_str(), options.as_slice())
+        }
+        _ => return respond_ephemeral(ctx, command, &lang.get(locale).responses.missing_subcommand).await,
+    };
+    let channel_id = options.iter().find(|o| o.name == "channel").and_then(|o| match &o.value {
+        CommandDataOptionValue::Channel(id) => Some(*id),
+        _ => None,
+    });
+
+    let description = match (subcommand, channel_id) {
+        ("allow", Some(channel_id)) => {
+            data_manager.set_ai_reply_channel(&guild_key, channel_id.get(), true).map_err(|e| e.to_string())?;
+            lang.format_ai_replies_allowed(locale, &format!("<#{}>", channel_id))
+        }
+        ("disallow", Some(channel_id)) => {
+            data_manager.set_ai_reply_channel(&guild_key, channel_id.get(), false).map_err(|e| e.to_string())?;
+            lang.format_ai_replies_disallowed(locale, &format!("<#{}>", channel_id))
+        }
+        ("list", _) => {
+            let mut channels: Vec<u64> = data_manager.get_ai_reply_channels(&guild_key).into_iter().collect();
+            channels.sort_unstable();
+            let list = channels.iter().map(|id| format!("• <#{}>", id)).collect::<Vec<_>>().join("\n");
+            lang.format_ai_replies_list(locale, &list, data_manager.is_ai_dm_enabled())
+        }
+        ("allow" | "disallow", None) => return respond_ephemeral(ctx, command, &lang.format_missing_option(locale, "channel")).await,
+        _ => return respond_ephemeral(ctx, command, &lang.get(locale).responses.unknown_subcommand).await,
+    };
+
+    let embed = CreateEmbed::new()
+        .title(&lang.get(locale).ai.replies.title)
+        .description(description)
+        .color(Color::from_rgb(105, 90, 205));
+
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .add_embed(embed)
+            .ephemeral(true),
+    );
+    command.create_response(&ctx.http, response).await?;
+    Ok(())
+}
+
+/// Turn AI replies to direct messages on or off
+async fn handle_ai_dms(
+    ctx: &Context,
+    command: &CommandInteraction,
+    options: &[CommandDataOption],
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let enabled = options.iter().find(|o| o.name == "enabled").and_then(|o| match &o.value {
+        CommandDataOptionValue::Boolean(value) => Some(*value),
+        _ => None,
+    });
+    let Some(enabled) = enabled else {
+        return respond_ephemeral(ctx, command, &lang.format_missing_option(locale, "enabled")).await;
+    };
+
+    data_manager.set_ai_dm_enabled(enabled).map_err(|e| e.to_string())?;
+    let replies = &lang.get(locale).ai.replies;
+    let message = if enabled { &replies.dms_on } else { &replies.dms_off };
+    respond_ephemeral(ctx, command, message).await
+}
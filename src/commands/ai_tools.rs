@@ -0,0 +1,296 @@
+//! Runs the bot actions the AI assistant asks for through function calling.
+//!
+//! Read-only tools answer right away. Tools with side effects post a
+//! confirmation embed; the pending action is persisted as button data and only
+//! runs when the requester presses Confirm.
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use serenity::all::{
+    ButtonStyle, ChannelId, Color, ComponentInteraction, Context, CreateActionRow, CreateButton,
+    CreateEmbed, CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage,
+    CreateMessage, Message,
+};
+use uuid::Uuid;
+
+use crate::ai::tools::{ToolAction, ToolRequester};
+use crate::ai::LlmToolCall;
+use crate::data::message_data::{ButtonAction, MessageType};
+use crate::data::{ButtonMessageData, DataManager, Reminder};
+use crate::lang::{EmojiManager, ImageManager, LanguageManager};
+use super::commission::create_commission_panel;
+use super::github::fetch_latest_event_embed;
+use super::ticket::create_ticket_channel;
+use super::respond_component_ephemeral;
+
+pub const AI_TOOL_CONFIRM_ID: &str = "ai_tool_confirm";
+pub const AI_TOOL_CANCEL_ID: &str = "ai_tool_cancel";
+
+/// `ButtonAction::Custom` name of a pending tool confirmation
+const PENDING_ACTION_NAME: &str = "ai_tool";
+
+/// Tool calls acted on per reply; extra calls are ignored
+const MAX_TOOL_CALLS_PER_REPLY: usize = 3;
+
+/// Handle the tool calls of an AI reply to `msg`, posting in `channel_id` (where the reply went)
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_ai_tool_calls(
+    ctx: &Context,
+    msg: &Message,
+    channel_id: ChannelId,
+    tool_calls: &[LlmToolCall],
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+    images: &ImageManager,
+    emojis: &EmojiManager,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let tools = &lang.get(locale).ai.tools;
+
+    for call in tool_calls.iter().take(MAX_TOOL_CALLS_PER_REPLY) {
+        let action = match ToolAction::from_call(call, Utc::now()) {
+            Ok(action) => action,
+            Err(e) => {
+                eprintln!("⚠️  Rejected AI tool call {}: {}", call.name, e);
+                send_notice(ctx, channel_id, None, lang.format_ai_tool_invalid_call(locale, &e.to_string()), Color::ORANGE).await?;
+                continue;
+            }
+        };
+
+        let requester = tool_requester(data_manager, &msg.author.id.to_string(), msg.guild_id.is_some());
+        if let Err(denied) = action.check_permission(&requester) {
+            send_notice(ctx, channel_id, Some(&tools.denied_title), lang.format_ai_tool_denied(locale, &denied), Color::ORANGE).await?;
+            continue;
+        }
+
+        match &action {
+            ToolAction::CreateReminder { message, remind_at } => {
+                let prompt = lang.format_ai_tool_reminder_confirm(locale, message, remind_at.timestamp());
+                request_confirmation(ctx, msg, channel_id, &action, prompt, data_manager, lang, locale).await?;
+            }
+            ToolAction::OpenTicket { reason } => {
+                let prompt = lang.format_ai_tool_ticket_confirm(locale, reason.as_deref());
+                request_confirmation(ctx, msg, channel_id, &action, prompt, data_manager, lang, locale).await?;
+            }
+            ToolAction::CommissionAvailability => {
+                let availability = data_manager.get_commission_board().availability;
+                let (embed, action_row) = create_commission_panel(lang, locale, images, emojis, availability);
+                channel_id
+                    .send_message(&ctx.http, CreateMessage::new().embed(embed).components(vec![action_row]))
+                    .await?;
+            }
+            ToolAction::GithubActivity => match fetch_latest_event_embed(&reqwest::Client::new()).await {
+                Ok(Some(embed)) => {
+                    channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await?;
+                }
+                Ok(None) => {
+                    send_notice(ctx, channel_id, None, tools.github_empty.clone(), Color::from_rgb(105, 90, 205)).await?;
+                }
+                Err(e) => {
+                    eprintln!("Error fetching GitHub activity for AI tool: {}", e);
+                    send_notice(ctx, channel_id, None, tools.github_failed.clone(), Color::ORANGE).await?;
+                }
+            },
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the Confirm / Cancel buttons of a pending tool action
+pub async fn handle_ai_tool_confirmation(
+    ctx: &Context,
+    component: &ComponentInteraction,
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+    images: &ImageManager,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let tools = &lang.get(locale).ai.tools;
+    let message_id = component.message.id.to_string();
+
+    let pending = data_manager
+        .get_button_message(&message_id)
+        .and_then(|data| match data.get_button_action(&component.data.custom_id) {
+            Some(ButtonAction::Custom { action_name, parameters }) if action_name == PENDING_ACTION_NAME => {
+                Some(parameters.clone())
+            }
+            _ => None,
+        });
+    let requester_id = pending.as_ref().and_then(|p| p.get("requester_id").cloned());
+    let action = pending
+        .as_ref()
+        .and_then(|p| p.get("action"))
+        .and_then(|json| serde_json::from_str::<ToolAction>(json).ok());
+    let (Some(requester_id), Some(action)) = (requester_id, action) else {
+        return respond_component_ephemeral(ctx, component, &tools.expired).await;
+    };
+
+    if component.user.id.to_string() != requester_id {
+        return respond_component_ephemeral(ctx, component, &tools.not_requester).await;
+    }
+
+    // Answered either way: the buttons must not run the action twice
+    if let Err(e) = data_manager.remove_button_message(&message_id) {
+        eprintln!("Error removing AI tool button data: {}", e);
+    }
+
+    if component.data.custom_id == AI_TOOL_CANCEL_ID {
+        return update_result(ctx, component, &tools.confirm_title, tools.cancelled.clone(), Color::DARK_GREY).await;
+    }
+
+    // Check again: a ticket may have been opened or reminders added meanwhile
+    let requester = tool_requester(data_manager, &requester_id, component.guild_id.is_some());
+    if let Err(denied) = action.check_permission(&requester) {
+        return update_result(ctx, component, &tools.denied_title, lang.format_ai_tool_denied(locale, &denied), Color::ORANGE).await;
+    }
+
+    let (description, color) = match &action {
+        ToolAction::CreateReminder { message, remind_at } => {
+            let reminder = Reminder {
+                id: Uuid::new_v4().to_string(),
+                user_id: requester_id.clone(),
+                user_name: component.user.name.clone(),
+                message: message.clone(),
+                channel_id: component.channel_id.to_string(),
+                reminder_time: *remind_at,
+                created_at: Utc::now(),
+                is_sent: false,
+                is_private: false,
+                mention_type: "creator".to_string(),
+                has_status: false,
+            };
+            data_manager.add_reminder(reminder).map_err(|e| e.to_string())?;
+            (lang.format_ai_tool_reminder_created(locale, remind_at.timestamp()), Color::from_rgb(0, 255, 127))
+        }
+        ToolAction::OpenTicket { reason } => {
+            // The permission check guarantees a guild
+            let guild_id = component.guild_id.ok_or("No guild found")?;
+            match create_ticket_channel(ctx, guild_id, &component.user, data_manager, images).await {
+                Ok(ticket_channel) => {
+                    if let Some(reason) = reason {
+                        let embed = CreateEmbed::new()
+                            .title(&tools.ticket_reason_title)
+                            .description(reason)
+                            .color(Color::from_rgb(138, 43, 226));
+                        ticket_channel.send_message(&ctx.http, CreateMessage::new().embed(embed)).await?;
+                    }
+                    println!("✅ Created ticket channel {} for user {} via AI", ticket_channel.name, component.user.name);
+                    (lang.format_ai_tool_ticket_created(locale, &ticket_channel.id.to_string()), Color::from_rgb(0, 255, 127))
+                }
+                Err(e) => {
+                    eprintln!("❌ Failed to create ticket channel for user {}: {}", component.user.name, e);
+                    (lang.format_ai_tool_ticket_failed(locale, &e.to_string()), Color::RED)
+                }
+            }
+        }
+        // Read-only actions run immediately and never wait for confirmation
+        ToolAction::CommissionAvailability | ToolAction::GithubActivity => (tools.expired.clone(), Color::DARK_GREY),
+    };
+
+    update_result(ctx, component, &tools.confirm_title, description, color).await
+}
+
+/// Gather what the permission checks need to know about a user
+fn tool_requester(data_manager: &DataManager, user_id: &str, in_guild: bool) -> ToolRequester {
+    ToolRequester {
+        in_guild,
+        active_ticket: data_manager.get_user_active_tickets(user_id).into_iter().next(),
+        pending_reminders: data_manager.count_user_pending_reminders(user_id),
+    }
+}
+
+/// Post a confirmation embed and remember the action until it is answered
+#[allow(clippy::too_many_arguments)]
+async fn request_confirmation(
+    ctx: &Context,
+    msg: &Message,
+    channel_id: ChannelId,
+    action: &ToolAction,
+    prompt: String,
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let tools = &lang.get(locale).ai.tools;
+
+    let embed = CreateEmbed::new()
+        .title(&tools.confirm_title)
+        .description(prompt)
+        .color(Color::from_rgb(105, 90, 205))
+        .footer(CreateEmbedFooter::new(lang.format_ai_tool_confirm_footer(locale, &msg.author.name)));
+    let buttons = CreateActionRow::Buttons(vec![
+        CreateButton::new(AI_TOOL_CONFIRM_ID)
+            .label(&tools.confirm_button)
+            .style(ButtonStyle::Success),
+        CreateButton::new(AI_TOOL_CANCEL_ID)
+            .label(&tools.cancel_button)
+            .style(ButtonStyle::Secondary),
+    ]);
+
+    let sent_message = channel_id
+        .send_message(&ctx.http, CreateMessage::new().embed(embed).components(vec![buttons]))
+        .await?;
+
+    let mut parameters = HashMap::new();
+    parameters.insert("requester_id".to_string(), msg.author.id.to_string());
+    parameters.insert("action".to_string(), serde_json::to_string(action)?);
+
+    let mut button_data = ButtonMessageData::new(
+        sent_message.id.to_string(),
+        channel_id.to_string(),
+        MessageType::General,
+    );
+    for custom_id in [AI_TOOL_CONFIRM_ID, AI_TOOL_CANCEL_ID] {
+        button_data.add_button_action(
+            custom_id.to_string(),
+            ButtonAction::Custom {
+                action_name: PENDING_ACTION_NAME.to_string(),
+                parameters: parameters.clone(),
+            },
+        );
+    }
+    button_data.add_metadata("ai_tool".to_string(), action.tool().name().to_string());
+
+    if let Err(e) = data_manager.add_button_message(sent_message.id.to_string(), button_data) {
+        eprintln!("Error storing AI tool button data: {}", e);
+    }
+
+    Ok(())
+}
+
+/// Send a short embed to the channel
+async fn send_notice(
+    ctx: &Context,
+    channel_id: ChannelId,
+    title: Option<&str>,
+    description: String,
+    color: Color,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let mut embed = CreateEmbed::new().description(description).color(color);
+    if let Some(title) = title {
+        embed = embed.title(title);
+    }
+    channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await?;
+    Ok(())
+}
+
+/// Replace the confirmation embed with the outcome and remove its buttons
+async fn update_result(
+    ctx: &Context,
+    component: &ComponentInteraction,
+    title: &str,
+    description: String,
+    color: Color,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let embed = CreateEmbed::new().title(title).description(description).color(color);
+    component.create_response(&ctx.http,
+        CreateInteractionResponse::UpdateMessage(
+            CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .components(vec![])
+        )
+    ).await?;
+    Ok(())
+}
@@ -0,0 +1,102 @@
+//! /capability command handler
+
+use serenity::all::{
+    Color, CommandDataOption, CommandDataOptionValue, CommandInteraction, Context, CreateEmbed,
+    CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+use crate::auth::Capability;
+use crate::config;
+use crate::data::DataManager;
+use crate::lang::LanguageManager;
+use super::respond_ephemeral;
+
+/// Handle /capability grant|revoke|list (owner only)
+pub async fn handle_capability_command(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let messages = &lang.get(locale).embeds.capability;
+    let Some(guild_id) = command.guild_id else {
+        return respond_ephemeral(ctx, command, &messages.guild_only).await;
+    };
+    let (subcommand, options) = match command.data.options.first() {
+        Some(CommandDataOption { name, value: CommandDataOptionValue::SubCommand(options), .. }) => {
+            (name.as_str(), options.as_slice())
+        }
+        _ => return respond_ephemeral(ctx, command, &lang.get(locale).responses.missing_subcommand).await,
+    };
+    let guild_id = guild_id.to_string();
+
+    if subcommand == "list" {
+        let grants = data_manager.get_guild_capability_grants(&guild_id);
+        let embed = Capability::ALL
+            .iter()
+            .filter(|capability| capability.is_delegable())
+            .fold(
+                CreateEmbed::new()
+                    .title(&messages.list_title)
+                    .description(lang.format_capability_list_description(locale, &format!("<@{}>", config::OWNER_ID)))
+                    .color(Color::from_rgb(88, 166, 255)),
+                |embed, capability| {
+                    let holders = grants
+                        .get(capability.as_str())
+                        .map(|grants| {
+                            let roles = grants.roles.iter().map(|id| format!("<@&{}>", id));
+                            let users = grants.users.iter().map(|id| format!("<@{}>", id));
+                            roles.chain(users).collect::<Vec<_>>().join(", ")
+                        })
+                        .filter(|holders| !holders.is_empty())
+                        .unwrap_or_else(|| messages.nobody.clone());
+                    embed.field(format!("`{}`", capability.as_str()), holders, false)
+                },
+            );
+        let response = CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new().add_embed(embed).ephemeral(true),
+        );
+        command.create_response(&ctx.http, response).await?;
+        return Ok(());
+    }
+
+    let granted = match subcommand {
+        "grant" => true,
+        "revoke" => false,
+        _ => return respond_ephemeral(ctx, command, &lang.get(locale).responses.unknown_subcommand).await,
+    };
+    let mut capability = None;
+    let mut role_id = None;
+    let mut user_id = None;
+    for option in options {
+        match (option.name.as_str(), &option.value) {
+            ("capability", CommandDataOptionValue::String(value)) => capability = Capability::parse(value),
+            ("role", CommandDataOptionValue::Role(id)) => role_id = Some(id.get()),
+            ("user", CommandDataOptionValue::User(id)) => user_id = Some(id.get()),
+            _ => {}
+        }
+    }
+    let Some(capability) = capability.filter(|capability| capability.is_delegable()) else {
+        return respond_ephemeral(ctx, command, &messages.unknown_capability).await;
+    };
+    if role_id.is_none() && user_id.is_none() {
+        return respond_ephemeral(ctx, command, &messages.missing_target).await;
+    }
+
+    data_manager
+        .set_capability_grant(&guild_id, capability.as_str(), role_id, user_id, granted)
+        .map_err(|e| e.to_string())?;
+
+    let holders = role_id
+        .map(|id| format!("<@&{}>", id))
+        .into_iter()
+        .chain(user_id.map(|id| format!("<@{}>", id)))
+        .collect::<Vec<_>>()
+        .join(&messages.holders_separator);
+    let content = if granted {
+        lang.format_capability_granted(locale, &holders, capability.as_str())
+    } else {
+        lang.format_capability_revoked(locale, &holders, capability.as_str())
+    };
+    respond_ephemeral(ctx, command, &content).await
+}
@@ -1,13 +1,23 @@
 use serenity::all::{
     CreateEmbed, CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage, 
     CommandInteraction, Context, Permissions, Color, CreateButton, CreateActionRow, ButtonStyle,
-    ComponentInteraction, ChannelType, CreateChannel, PermissionOverwrite, PermissionOverwriteType
+    ComponentInteraction, ChannelType, CreateChannel, PermissionOverwrite, PermissionOverwriteType,
+    ActionRowComponent, CreateInputText, CreateModal, InputTextStyle, ModalInteraction, HttpError
 };
 use serenity::model::prelude::*;
-use crate::data::{DataManager, ButtonMessageData};
+use crate::auth::Caller;
+use crate::data::{DataManager, ButtonMessageData, Commission, CommissionAvailability, CommissionBrief};
 use crate::data::message_data::{MessageType, ButtonAction};
 use crate::lang::{LanguageManager, ImageManager, EmojiManager};
 use std::env;
+use uuid::Uuid;
+use super::registry::Access;
+
+/// Custom ID of the intake modal shown when the commission button is clicked
+pub const COMMISSION_INTAKE_MODAL_ID: &str = "commission_intake";
+
+/// Who may close any commission besides its client
+const CLOSE_ACCESS: Access = Access::Permission(Permissions::ADMINISTRATOR);
 
 /// Handle the /commission_setup command
 pub async fn handle_commission_setup_command(
@@ -15,38 +25,12 @@ pub async fn handle_commission_setup_command(
     command: &CommandInteraction,
     data_manager: &DataManager,
     lang: &LanguageManager,
+    locale: &str,
     images: &ImageManager,
     emojis: &EmojiManager,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let lang_msgs = lang.get();
+    let lang_msgs = lang.get(locale);
     
-    // Check if user has administrator permission
-    if let Some(guild_id) = command.guild_id {
-        if let Ok(member) = guild_id.member(&ctx.http, command.user.id).await {
-            let has_permission = {
-                if let Some(guild) = ctx.cache.guild(guild_id) {
-                    let permissions = guild.member_permissions(&member);
-                    permissions.contains(Permissions::ADMINISTRATOR)
-                } else {
-                    false
-                }
-            };
-            
-            if !has_permission {
-                let embed = CreateEmbed::new()
-                    .title(&lang_msgs.embeds.commission.title)
-                    .description(&lang_msgs.commission.messages.setup_error_permission)
-                    .color(Color::RED);
-                
-                let response = CreateInteractionResponse::Message(
-                    CreateInteractionResponseMessage::new().embed(embed)
-                );
-                command.create_response(&ctx.http, response).await?;
-                return Ok(());
-            }
-        }
-    }
-
     // Get the commission channel ID from environment or use the one from new.md
     let commission_channel_id = env::var("COMMISSION_CHANNEL_ID")
         .unwrap_or_else(|_| "1400493436993278043".to_string());
@@ -68,15 +52,9 @@ pub async fn handle_commission_setup_command(
         }
     };
 
-    // Create the commission embed
-    let commission_embed = create_commission_embed(&lang_msgs, images, emojis);
-    
-    // Create the commission button
-    let commission_button = CreateButton::new("commission_create")
-        .style(ButtonStyle::Primary)
-        .label(&lang_msgs.embeds.commission.button_text);
-    
-    let action_row = CreateActionRow::Buttons(vec![commission_button]);
+    // Create the commission embed and button for the current availability
+    let availability = data_manager.get_commission_board().availability;
+    let (commission_embed, action_row) = create_commission_panel(lang, locale, images, emojis, availability);
     
     // Send the message to the commission channel
     let message_builder = serenity::builder::CreateMessage::new()
@@ -104,10 +82,15 @@ pub async fn handle_commission_setup_command(
                 eprintln!("Error storing button message data: {}", e);
             }
             
+            // Remember the panel so /commission availability can edit it in place
+            if let Err(e) = data_manager.set_commission_panel(channel_id.to_string(), sent_message.id.to_string()) {
+                eprintln!("Error storing commission panel location: {}", e);
+            }
+            
             // Send success response to the user
             let success_embed = CreateEmbed::new()
                 .title("✅ Commission Setup Complete")
-                .description(&lang.format_commission_setup_success(&format!("<#{}>", channel_id)))
+                .description(&lang.format_commission_setup_success(locale, &format!("<#{}>", channel_id)))
                 .color(Color::from_rgb(0, 255, 127))
                 .footer(CreateEmbedFooter::new("Commission System"));
             
@@ -140,15 +123,131 @@ pub async fn handle_commission_create(
     component: &ComponentInteraction,
     data_manager: &DataManager,
     lang: &LanguageManager,
+    locale: &str,
+    _images: &ImageManager,
+    _emojis: &EmojiManager,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let lang_msgs = lang.get(locale);
+    let user = &component.user;
+    if component.guild_id.is_none() {
+        component.create_response(&ctx.http, 
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content("This command can only be used in a server.")
+                    .ephemeral(true)
+            )
+        ).await?;
+        return Ok(());
+    }
+
+    // Check if user already has an active commission channel
+    if let Some(existing) = data_manager.get_user_commission(&user.id.to_string()) {
+        let existing_channel = existing.channel_id;
+        let embed = CreateEmbed::new()
+            .title(&lang_msgs.embeds.commission.title)
+            .description(&lang.format_commission_already_exists(locale, &format!("<#{}>", existing_channel)))
+            .color(Color::ORANGE)
+            .footer(CreateEmbedFooter::new("Commission System"));
+        
+        let response = CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .ephemeral(true)
+        );
+        component.create_response(&ctx.http, response).await?;
+        return Ok(());
+    }
+
+    // While commissions aren't open, clicks go onto the waitlist instead
+    if data_manager.get_commission_board().availability != CommissionAvailability::Open {
+        let response = join_waitlist(data_manager, lang, locale, user)?;
+        component.create_response(&ctx.http, response).await?;
+        return Ok(());
+    }
+
+    // Collect the structured brief before creating the channel
+    let intake = &lang_msgs.commission.intake;
+    let modal = CreateModal::new(COMMISSION_INTAKE_MODAL_ID, &intake.modal_title)
+        .components(vec![
+            CreateActionRow::InputText(
+                CreateInputText::new(InputTextStyle::Short, &intake.project_type_label, "project_type")
+                    .placeholder(&intake.project_type_placeholder)
+                    .max_length(100)
+                    .required(true)
+            ),
+            CreateActionRow::InputText(
+                CreateInputText::new(InputTextStyle::Short, &intake.budget_label, "budget_range")
+                    .placeholder(&intake.budget_placeholder)
+                    .max_length(100)
+                    .required(true)
+            ),
+            CreateActionRow::InputText(
+                CreateInputText::new(InputTextStyle::Short, &intake.deadline_label, "deadline")
+                    .placeholder(&intake.deadline_placeholder)
+                    .max_length(100)
+                    .required(true)
+            ),
+            CreateActionRow::InputText(
+                CreateInputText::new(InputTextStyle::Paragraph, &intake.description_label, "description")
+                    .placeholder(&intake.description_placeholder)
+                    .max_length(1000)
+                    .required(true)
+            ),
+        ]);
+
+    component.create_response(&ctx.http, CreateInteractionResponse::Modal(modal)).await?;
+    Ok(())
+}
+
+/// Put a user on the commission waitlist; the reply tells them their position
+fn join_waitlist(
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+    user: &User,
+) -> Result<CreateInteractionResponse, Box<dyn std::error::Error + Send + Sync>> {
+    let (position, newly_added) = match data_manager.join_commission_waitlist(user.id.to_string(), user.name.clone()) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("Error adding user to commission waitlist: {}", e);
+            return Err(e.to_string().into());
+        }
+    };
+    let description = if newly_added {
+        lang.format_commission_waitlist_joined(locale, position)
+    } else {
+        lang.format_commission_waitlist_already(locale, position)
+    };
+
+    let embed = CreateEmbed::new()
+        .title(&lang.get(locale).embeds.commission.title)
+        .description(description)
+        .color(Color::from_rgb(105, 90, 205))
+        .footer(CreateEmbedFooter::new("Commission System"));
+
+    Ok(CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .ephemeral(true)
+    ))
+}
+
+/// Handle submission of the commission intake modal
+pub async fn handle_commission_intake_submit(
+    ctx: &Context,
+    modal: &ModalInteraction,
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
     images: &ImageManager,
     emojis: &EmojiManager,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let lang_msgs = lang.get();
-    let user = &component.user;
-    let guild_id = match component.guild_id {
+    let lang_msgs = lang.get(locale);
+    let user = &modal.user;
+    let guild_id = match modal.guild_id {
         Some(id) => id,
         None => {
-            component.create_response(&ctx.http, 
+            modal.create_response(&ctx.http, 
                 CreateInteractionResponse::Message(
                     CreateInteractionResponseMessage::new()
                         .content("This command can only be used in a server.")
@@ -159,11 +258,12 @@ pub async fn handle_commission_create(
         }
     };
 
-    // Check if user already has an active commission channel
-    if let Some(existing_channel) = get_user_commission_channel(ctx, guild_id, user.id).await? {
+    // The user may have opened two modals; re-check before creating a channel
+    if let Some(existing) = data_manager.get_user_commission(&user.id.to_string()) {
+        let existing_channel = existing.channel_id;
         let embed = CreateEmbed::new()
             .title(&lang_msgs.embeds.commission.title)
-            .description(&lang.format_commission_already_exists(&format!("<#{}>", existing_channel)))
+            .description(lang.format_commission_already_exists(locale, &format!("<#{}>", existing_channel)))
             .color(Color::ORANGE)
             .footer(CreateEmbedFooter::new("Commission System"));
         
@@ -172,10 +272,25 @@ pub async fn handle_commission_create(
                 .embed(embed)
                 .ephemeral(true)
         );
-        component.create_response(&ctx.http, response).await?;
+        modal.create_response(&ctx.http, response).await?;
+        return Ok(());
+    }
+
+    // Commissions may have closed while the form was open; join the waitlist then
+    if data_manager.get_commission_board().availability != CommissionAvailability::Open {
+        let response = join_waitlist(data_manager, lang, locale, user)?;
+        modal.create_response(&ctx.http, response).await?;
         return Ok(());
     }
 
+    let brief = CommissionBrief {
+        project_type: modal_input_value(modal, "project_type"),
+        budget_range: modal_input_value(modal, "budget_range"),
+        deadline: modal_input_value(modal, "deadline"),
+        description: modal_input_value(modal, "description"),
+    };
+    let commission_id = Uuid::new_v4().to_string()[..8].to_lowercase();
+
     // Get owner ID for permissions
     let owner_id = crate::config::OWNER_ID;
 
@@ -215,6 +330,9 @@ pub async fn handle_commission_create(
 
     match guild_id.create_channel(&ctx.http, channel_builder).await {
         Ok(created_channel) => {
+            // The brief goes first so the owner sees the full request at a glance
+            let brief_embed = create_commission_brief_embed(lang, locale, &commission_id, &user.name, &brief);
+
             // Create welcome message in the new channel
             let welcome_embed = create_commission_welcome_embed(&lang_msgs, &user.name, images, emojis);
             
@@ -226,7 +344,7 @@ pub async fn handle_commission_create(
             let action_row = CreateActionRow::Buttons(vec![close_button]);
             
             let welcome_message = serenity::builder::CreateMessage::new()
-                .embed(welcome_embed)
+                .embeds(vec![brief_embed, welcome_embed])
                 .components(vec![action_row])
                 .content(&format!("<@{}>", user.id)); // Mention the user
             
@@ -255,6 +373,20 @@ pub async fn handle_commission_create(
                 }
             }
 
+            let commission = Commission::new(
+                commission_id.clone(),
+                created_channel.id.to_string(),
+                user.id.to_string(),
+                user.name.clone(),
+                brief,
+            );
+            if let Err(e) = data_manager.add_commission(commission) {
+                eprintln!("Error storing commission record: {}", e);
+            }
+            if let Err(e) = data_manager.leave_commission_waitlist(&user.id.to_string()) {
+                eprintln!("Error updating commission waitlist: {}", e);
+            }
+
             // Send confirmation response
             let confirmation_embed = CreateEmbed::new()
                 .title("✅ Commission Channel Created!")
@@ -267,16 +399,16 @@ pub async fn handle_commission_create(
                     .embed(confirmation_embed)
                     .ephemeral(true)
             );
-            component.create_response(&ctx.http, response).await?;
+            modal.create_response(&ctx.http, response).await?;
             
-            println!("{}", lang.format_commission_channel_created(&user.name));
+            println!("{}", lang.format_commission_channel_created(locale, &user.name));
         }
         Err(e) => {
             eprintln!("Failed to create commission channel: {}", e);
             
             let error_embed = CreateEmbed::new()
                 .title(&lang_msgs.embeds.commission.title)
-                .description(&lang.format_commission_channel_creation_failed(&e.to_string()))
+                .description(&lang.format_commission_channel_creation_failed(locale, &e.to_string()))
                 .color(Color::RED);
             
             let response = CreateInteractionResponse::Message(
@@ -284,7 +416,7 @@ pub async fn handle_commission_create(
                     .embed(error_embed)
                     .ephemeral(true)
             );
-            component.create_response(&ctx.http, response).await?;
+            modal.create_response(&ctx.http, response).await?;
         }
     }
     
@@ -297,10 +429,11 @@ pub async fn handle_commission_close(
     component: &ComponentInteraction,
     data_manager: &DataManager,
     lang: &LanguageManager,
+    locale: &str,
     _images: &ImageManager,
     _emojis: &EmojiManager,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let lang_msgs = lang.get();
+    let lang_msgs = lang.get(locale);
     let user = &component.user;
     
     // Extract creator ID from custom_id
@@ -311,22 +444,8 @@ pub async fn handle_commission_close(
     };
     
     // Check if user has permission to close (creator or admin)
-    let can_close = user.id.get() == creator_id || {
-        if let Some(guild_id) = component.guild_id {
-            if let Ok(member) = guild_id.member(&ctx.http, user.id).await {
-                if let Some(guild) = ctx.cache.guild(guild_id) {
-                    let permissions = guild.member_permissions(&member);
-                    permissions.contains(Permissions::ADMINISTRATOR)
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
-        } else {
-            false
-        }
-    };
+    let can_close = user.id.get() == creator_id
+        || CLOSE_ACCESS.allows(data_manager, &Caller::of_component(component));
     
     if !can_close {
         let embed = CreateEmbed::new()
@@ -368,6 +487,10 @@ pub async fn handle_commission_close(
     } else {
         println!("✅ Cleaned up button data for closed commission message: {}", message_id);
     }
+    // Also drops button data for any other messages in the channel
+    if let Err(e) = data_manager.remove_commission(&component.channel_id.to_string()) {
+        eprintln!("Warning: Could not remove commission record for channel {}: {}", component.channel_id, e);
+    }
     
     // Delete the channel after a delay
     let channel_id = component.channel_id;
@@ -381,7 +504,7 @@ pub async fn handle_commission_close(
         }
     });
     
-    println!("{}", lang.format_commission_close_success());
+    println!("{}", lang.format_commission_close_success(locale));
     Ok(())
 }
 
@@ -428,6 +551,168 @@ fn create_commission_embed(
         .timestamp(chrono::Utc::now())
 }
 
+/// Create the setup panel embed and button for the given availability
+pub fn create_commission_panel(
+    lang: &LanguageManager,
+    locale: &str,
+    images: &ImageManager,
+    emojis: &EmojiManager,
+    availability: CommissionAvailability,
+) -> (CreateEmbed, CreateActionRow) {
+    let lang_msgs = lang.get(locale);
+    let messages = &lang_msgs.commission.availability;
+
+    let note = match availability {
+        CommissionAvailability::Open => &messages.panel_open,
+        CommissionAvailability::Waitlist => &messages.panel_waitlist,
+        CommissionAvailability::Closed => &messages.panel_closed,
+    };
+    let embed = create_commission_embed(&lang_msgs, images, emojis).field(
+        &messages.panel_field,
+        format!("**{}**\n{}", lang.commission_availability_label(locale, availability), note),
+        false,
+    );
+
+    // Same custom_id in every state; handle_commission_create checks availability
+    let button = match availability {
+        CommissionAvailability::Open => CreateButton::new("commission_create")
+            .style(ButtonStyle::Primary)
+            .label(&lang_msgs.embeds.commission.button_text),
+        CommissionAvailability::Waitlist | CommissionAvailability::Closed => CreateButton::new("commission_create")
+            .style(ButtonStyle::Secondary)
+            .label(&messages.waitlist_button),
+    };
+
+    (embed, CreateActionRow::Buttons(vec![button]))
+}
+
+/// Handle /commission availability: store the new state and edit the setup panel in place
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_commission_availability(
+    ctx: &Context,
+    command: &CommandInteraction,
+    availability: CommissionAvailability,
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+    images: &ImageManager,
+    emojis: &EmojiManager,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if let Err(e) = data_manager.set_commission_availability(availability) {
+        return Err(e.to_string().into());
+    }
+
+    let board = data_manager.get_commission_board();
+    let panel = board.panel_channel_id.as_deref().and_then(|id| id.parse::<u64>().ok())
+        .zip(board.panel_message_id.as_deref().and_then(|id| id.parse::<u64>().ok()));
+
+    let mut panel_updated = false;
+    if let Some((channel_id, message_id)) = panel {
+        let (embed, action_row) = create_commission_panel(lang, locale, images, emojis, availability);
+        let edit = serenity::builder::EditMessage::new()
+            .embed(embed)
+            .components(vec![action_row]);
+        match ChannelId::new(channel_id).edit_message(&ctx.http, MessageId::new(message_id), edit).await {
+            Ok(_) => panel_updated = true,
+            Err(e) => eprintln!("Warning: Could not update commission panel: {}", e),
+        }
+    }
+
+    let mut description = lang.format_commission_availability_updated(locale, availability);
+    if !panel_updated {
+        description.push_str("\n\n");
+        description.push_str(&lang.get(locale).commission.availability.panel_missing);
+    }
+
+    let embed = CreateEmbed::new()
+        .description(description)
+        .color(if panel_updated { Color::from_rgb(0, 255, 127) } else { Color::ORANGE })
+        .footer(CreateEmbedFooter::new("Commission System"));
+    
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .ephemeral(true)
+    );
+    command.create_response(&ctx.http, response).await?;
+    Ok(())
+}
+
+/// Handle /commission queue: active commissions oldest first with ETAs, then the waitlist
+pub async fn handle_commission_queue(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let messages = &lang.get(locale).commission.availability;
+    let board = data_manager.get_commission_board();
+
+    let active = data_manager.get_active_commissions();
+    let mut description = String::new();
+    for (index, commission) in active.iter().enumerate() {
+        let eta = match commission.eta().and_then(|date| date.and_hms_opt(0, 0, 0)) {
+            Some(date) => format!("<t:{}:D>", date.and_utc().timestamp()),
+            None => lang.format_commission_queue_eta_requested(locale, &commission.brief.deadline),
+        };
+        let line = lang.format_commission_queue_entry(
+            locale,
+            index + 1,
+            &commission.channel_id,
+            &format!("<@{}>", commission.client_id),
+            commission.status,
+            &eta,
+        );
+        // Embed descriptions are capped at 4096 characters
+        if description.len() + line.len() + 1 > 4000 {
+            description.push('…');
+            break;
+        }
+        description.push_str(&line);
+        description.push('\n');
+    }
+    if description.is_empty() {
+        description = messages.queue_empty.clone();
+    }
+
+    let mut waitlist = String::new();
+    for (index, entry) in board.waitlist.iter().enumerate() {
+        let line = lang.format_commission_queue_waitlist_entry(
+            locale,
+            index + 1,
+            &entry.user_id,
+            entry.joined_at.timestamp(),
+        );
+        // Field values are capped at 1024 characters
+        if waitlist.len() + line.len() + 1 > 1000 {
+            waitlist.push('…');
+            break;
+        }
+        waitlist.push_str(&line);
+        waitlist.push('\n');
+    }
+    if waitlist.is_empty() {
+        waitlist = messages.queue_waitlist_empty.clone();
+    }
+
+    let embed = CreateEmbed::new()
+        .title(&messages.queue_title)
+        .description(description)
+        .color(Color::from_rgb(105, 90, 205)) // Purple theme
+        .field(lang.format_commission_queue_waitlist_field(locale, board.waitlist.len()), waitlist, false)
+        .footer(CreateEmbedFooter::new(lang.format_commission_queue_footer(locale, board.availability)))
+        .timestamp(chrono::Utc::now());
+    
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .ephemeral(true)
+    );
+    command.create_response(&ctx.http, response).await?;
+    Ok(())
+}
+
 /// Create welcome embed for new commission channel
 fn create_commission_welcome_embed(
     lang_msgs: &crate::lang::Messages,
@@ -474,22 +759,87 @@ fn create_commission_welcome_embed(
         .timestamp(chrono::Utc::now())
 }
 
-/// Check if user already has an active commission channel
-async fn get_user_commission_channel(
-    ctx: &Context,
-    guild_id: GuildId,
-    user_id: UserId,
-) -> Result<Option<ChannelId>, Box<dyn std::error::Error + Send + Sync>> {
-    let channels = guild_id.channels(&ctx.http).await?;
-    
-    for (channel_id, channel) in channels {
-        if channel.name.starts_with("commission-") && channel.name.contains(&user_id.to_string()) {
-            // Simple check: if the channel name contains the user ID, they probably have access
-            return Ok(Some(channel_id));
+/// Create the brief embed rendered at the top of a new commission channel
+fn create_commission_brief_embed(
+    lang: &LanguageManager,
+    locale: &str,
+    commission_id: &str,
+    username: &str,
+    brief: &CommissionBrief,
+) -> CreateEmbed {
+    let intake = &lang.get(locale).commission.intake;
+
+    CreateEmbed::new()
+        .title(&intake.brief_title)
+        .description(lang.format_commission_brief_description(locale, username))
+        .color(Color::from_rgb(105, 90, 205)) // Purple theme
+        .field(&intake.project_type_field, &brief.project_type, true)
+        .field(&intake.budget_field, &brief.budget_range, true)
+        .field(&intake.deadline_field, &brief.deadline, true)
+        .field(&intake.description_field, &brief.description, false)
+        .footer(CreateEmbedFooter::new(lang.format_commission_brief_footer(locale, commission_id)))
+        .timestamp(chrono::Utc::now())
+}
+
+/// Read the submitted value of a modal text input by custom_id
+fn modal_input_value(modal: &ModalInteraction, custom_id: &str) -> String {
+    modal.data.components
+        .iter()
+        .flat_map(|row| row.components.iter())
+        .find_map(|component| match component {
+            ActionRowComponent::InputText(input) if input.custom_id == custom_id => {
+                input.value.clone()
+            }
+            _ => None,
+        })
+        .map(|value| value.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Reconcile commission records with Discord on startup: adopt channels that only
+/// exist as button data and drop records whose channel was deleted by hand
+pub async fn reconcile_commissions(ctx: &Context, data_manager: &DataManager) {
+    match data_manager.adopt_untracked_commissions() {
+        Ok(0) => {}
+        Ok(adopted) => println!("📋 Registered {} untracked commission channel(s)", adopted),
+        Err(e) => eprintln!("Warning: Could not register untracked commission channels: {}", e),
+    }
+
+    let mut removed = 0;
+    for channel_id in data_manager.get_commission_channel_ids() {
+        let Ok(id) = channel_id.parse::<u64>() else {
+            continue;
+        };
+
+        // Only a 404 means the channel is gone; other errors may be transient
+        if let Err(serenity::Error::Http(HttpError::UnsuccessfulRequest(response))) =
+            ChannelId::new(id).to_channel(&ctx.http).await
+        {
+            if response.status_code.as_u16() == 404 {
+                match data_manager.remove_commission(&channel_id) {
+                    Ok(_) => removed += 1,
+                    Err(e) => eprintln!("Warning: Could not remove commission record for channel {}: {}", channel_id, e),
+                }
+            }
         }
     }
-    
-    Ok(None)
+
+    if removed > 0 {
+        println!("🧹 Removed {} commission record(s) for deleted channels", removed);
+    }
+}
+
+/// Drop the commission record when its channel is deleted outside the bot
+pub fn handle_commission_channel_deleted(channel_id: ChannelId, data_manager: &DataManager) {
+    let channel_id = channel_id.to_string();
+    if data_manager.get_commission(&channel_id).is_none() {
+        return;
+    }
+
+    match data_manager.remove_commission(&channel_id) {
+        Ok(_) => println!("🧹 Removed commission record for deleted channel {}", channel_id),
+        Err(e) => eprintln!("Warning: Could not remove commission record for channel {}: {}", channel_id, e),
+    }
 }
 
 /// Handle the /commission_close command
@@ -498,49 +848,30 @@ pub async fn handle_commission_close_command(
     command: &CommandInteraction,
     data_manager: &DataManager,
     lang: &LanguageManager,
+    locale: &str,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let lang_msgs = lang.get();
+    let lang_msgs = lang.get(locale);
     
     // Check if this is a commission channel
-    let channel_name = if let Ok(channel) = command.channel_id.to_channel(&ctx.http).await {
-        if let Some(guild_channel) = channel.guild() {
-            guild_channel.name.clone()
-        } else {
-            String::new()
+    let commission = match data_manager.get_commission(&command.channel_id.to_string()) {
+        Some(commission) => commission,
+        None => {
+            let embed = CreateEmbed::new()
+                .title(&lang_msgs.embeds.commission_closed.title)
+                .description(&lang_msgs.commission.messages.close_error_not_commission)
+                .color(Color::RED);
+            
+            let response = CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new().embed(embed)
+            );
+            command.create_response(&ctx.http, response).await?;
+            return Ok(());
         }
-    } else {
-        String::new()
     };
 
-    if !channel_name.starts_with("commission-") {
-        let embed = CreateEmbed::new()
-            .title(&lang_msgs.embeds.commission_closed.title)
-            .description(&lang_msgs.commission.messages.close_error_not_commission)
-            .color(Color::RED);
-        
-        let response = CreateInteractionResponse::Message(
-            CreateInteractionResponseMessage::new().embed(embed)
-        );
-        command.create_response(&ctx.http, response).await?;
-        return Ok(());
-    }
-
     // Check permissions (creator or admin)
-    let can_close = if let Some(guild_id) = command.guild_id {
-        if let Ok(member) = guild_id.member(&ctx.http, command.user.id).await {
-            if let Some(guild) = ctx.cache.guild(guild_id) {
-                let permissions = guild.member_permissions(&member);
-                permissions.contains(Permissions::ADMINISTRATOR) ||
-                channel_name.contains(&command.user.id.to_string())
-            } else {
-                false
-            }
-        } else {
-            false
-        }
-    } else {
-        false
-    };
+    let can_close = commission.client_id == command.user.id.to_string()
+        || CLOSE_ACCESS.allows(data_manager, &Caller::of_command(command));
 
     if !can_close {
         let embed = CreateEmbed::new()
@@ -570,21 +901,9 @@ pub async fn handle_commission_close_command(
     );
     command.create_response(&ctx.http, response).await?;
     
-    // Clean up any button data related to this commission channel
-    // We need to find and remove button message data for this channel
-    if let Ok(message_history) = command.channel_id.messages(&ctx.http, serenity::builder::GetMessages::new().limit(50)).await {
-        for message in message_history {
-            if message.author.bot {
-                // Check if this message has commission button data
-                if data_manager.get_button_message(&message.id.to_string()).is_some() {
-                    if let Err(e) = data_manager.remove_button_message(&message.id.to_string()) {
-                        eprintln!("Warning: Could not remove button data for message {}: {}", message.id, e);
-                    } else {
-                        println!("✅ Cleaned up button data for commission message: {}", message.id);
-                    }
-                }
-            }
-        }
+    // Clean up the commission record and its button data
+    if let Err(e) = data_manager.remove_commission(&command.channel_id.to_string()) {
+        eprintln!("Warning: Could not remove commission record for channel {}: {}", command.channel_id, e);
     }
     
     // Delete the channel after a delay
@@ -0,0 +1,388 @@
+use serenity::all::{
+    ButtonStyle, ChannelId, Color, CommandDataOption, CommandDataOptionValue, CommandInteraction,
+    ComponentInteraction, Context, CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter,
+    CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage, EditMessage, MessageId,
+};
+use chrono::NaiveDate;
+use crate::data::{Commission, CommissionAvailability, CommissionStatus, CommissionTransitionError, DataManager};
+use crate::lang::{EmojiManager, ImageManager, LanguageManager};
+use super::commission::{handle_commission_availability, handle_commission_queue};
+use super::{respond_component_ephemeral, respond_ephemeral, truncate_field};
+
+/// Custom ID prefix of the button the client uses to accept a quote (followed by the quote number)
+pub const COMMISSION_QUOTE_ACCEPT_ID: &str = "commission_quote_accept";
+/// Custom ID prefix of the button the client uses to decline a quote (followed by the quote number)
+pub const COMMISSION_QUOTE_DECLINE_ID: &str = "commission_quote_decline";
+
+/// Handle the owner-only /commission command (quote, milestone, complete, status, availability, queue)
+pub async fn handle_commission_command(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+    images: &ImageManager,
+    emojis: &EmojiManager,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let lifecycle = &lang.get(locale).commission.lifecycle;
+
+    let (subcommand, options) = match command.data.options.first() {
+        Some(CommandDataOption { name, value: CommandDataOptionValue::SubCommand(options), .. }) => {
+            (name.as_str(), options.as_slice())
+        }
+        _ => return respond_ephemeral(ctx, command, "❌ Missing subcommand.").await,
+    };
+
+    // Board-wide subcommands don't need a commission channel
+    match subcommand {
+        "availability" => {
+            return match string_option(options, "state").as_deref().and_then(CommissionAvailability::parse) {
+                Some(availability) => {
+                    handle_commission_availability(ctx, command, availability, data_manager, lang, locale, images, emojis).await
+                }
+                None => respond_ephemeral(ctx, command, "❌ Unknown availability.").await,
+            };
+        }
+        "queue" => return handle_commission_queue(ctx, command, data_manager, lang, locale).await,
+        _ => {}
+    }
+
+    let channel = command.channel_id.to_string();
+    if data_manager.get_commission(&channel).is_none() {
+        return respond_ephemeral(ctx, command, &lifecycle.not_commission_channel).await;
+    }
+
+    // Changes are applied under the data lock; the Discord calls come after
+    let commission = match subcommand {
+        "quote" => {
+            let amount = string_option(options, "amount").unwrap_or_default();
+            let details = string_option(options, "details").unwrap_or_default();
+
+            let change = data_manager
+                .update_commission(&channel, |commission| {
+                    let replaced = commission.quote.as_ref().and_then(|quote| quote.message_id.clone());
+                    commission.send_quote(amount, details).map(|()| replaced)
+                })
+                .map_err(|e| e.to_string())?;
+            let (replaced, commission) = match change {
+                Some(Ok(changed)) => changed,
+                Some(Err(e)) => return respond_ephemeral(ctx, command, &transition_error_message(lang, locale, e, 0)).await,
+                None => return respond_ephemeral(ctx, command, &lifecycle.not_commission_channel).await,
+            };
+
+            // The replaced quote's buttons can no longer answer anything
+            if let Some(message_id) = replaced.and_then(|id| id.parse::<u64>().ok()) {
+                let edit = EditMessage::new().components(vec![]);
+                if let Err(e) = command.channel_id.edit_message(&ctx.http, MessageId::new(message_id), edit).await {
+                    eprintln!("Warning: Could not remove the buttons of the replaced quote: {}", e);
+                }
+            }
+
+            let number = commission.quote.as_ref().map_or(0, |quote| quote.number);
+            let quote_message = CreateMessage::new()
+                .content(format!("<@{}>", commission.client_id))
+                .embed(create_quote_embed(lang, locale, &commission))
+                .components(vec![CreateActionRow::Buttons(vec![
+                    CreateButton::new(quote_button_id(COMMISSION_QUOTE_ACCEPT_ID, number))
+                        .style(ButtonStyle::Success)
+                        .label(&lifecycle.quote_accept_button),
+                    CreateButton::new(quote_button_id(COMMISSION_QUOTE_DECLINE_ID, number))
+                        .style(ButtonStyle::Danger)
+                        .label(&lifecycle.quote_decline_button),
+                ])]);
+            let message = command.channel_id.send_message(&ctx.http, quote_message).await?;
+            if let Err(e) = data_manager.set_commission_quote_message(&channel, number, message.id.to_string()) {
+                eprintln!("Error storing commission quote message: {}", e);
+            }
+
+            respond_ephemeral(ctx, command, &lifecycle.quote_sent).await?;
+            commission
+        }
+        "milestone" => {
+            let title = string_option(options, "title").unwrap_or_default();
+            let due_date = match string_option(options, "due") {
+                Some(due) => match NaiveDate::parse_from_str(due.trim(), "%Y-%m-%d") {
+                    Ok(date) => Some(date),
+                    Err(_) => return respond_ephemeral(ctx, command, &lifecycle.invalid_date).await,
+                },
+                None => None,
+            };
+
+            let change = data_manager
+                .update_commission(&channel, |commission| {
+                    commission.add_milestone(title.clone(), due_date).map(|()| commission.milestones.len())
+                })
+                .map_err(|e| e.to_string())?;
+            let (number, commission) = match change {
+                Some(Ok(changed)) => changed,
+                Some(Err(e)) => return respond_ephemeral(ctx, command, &transition_error_message(lang, locale, e, 0)).await,
+                None => return respond_ephemeral(ctx, command, &lifecycle.not_commission_channel).await,
+            };
+
+            respond_public(ctx, command, &lang.format_commission_milestone_added(locale, number, &title)).await?;
+            commission
+        }
+        "complete" => {
+            let number = match integer_option(options, "number") {
+                Some(number) if number > 0 => number as usize,
+                _ => 0,
+            };
+
+            let change = data_manager
+                .update_commission(&channel, |commission| {
+                    commission.complete_milestone(number).map(|()| commission.milestones[number - 1].title.clone())
+                })
+                .map_err(|e| e.to_string())?;
+            let (title, commission) = match change {
+                Some(Ok(changed)) => changed,
+                Some(Err(e)) => return respond_ephemeral(ctx, command, &transition_error_message(lang, locale, e, number)).await,
+                None => return respond_ephemeral(ctx, command, &lifecycle.not_commission_channel).await,
+            };
+
+            respond_public(ctx, command, &lang.format_commission_milestone_completed(locale, number, &title)).await?;
+            commission
+        }
+        "status" => {
+            let status = match string_option(options, "status").as_deref().and_then(CommissionStatus::parse) {
+                Some(status) => status,
+                None => return respond_ephemeral(ctx, command, "❌ Unknown status.").await,
+            };
+
+            let change = data_manager
+                .update_commission(&channel, |commission| {
+                    commission.status = status;
+                    Ok::<(), CommissionTransitionError>(())
+                })
+                .map_err(|e| e.to_string())?;
+            let Some(Ok(((), commission))) = change else {
+                return respond_ephemeral(ctx, command, &lifecycle.not_commission_channel).await;
+            };
+
+            respond_public(ctx, command, &lang.format_commission_status_updated(locale, status)).await?;
+            commission
+        }
+        _ => return respond_ephemeral(ctx, command, "❌ Unknown subcommand.").await,
+    };
+
+    refresh_progress_embed(ctx, command.channel_id, &commission, data_manager, lang, locale).await;
+
+    Ok(())
+}
+
+/// Custom ID of a quote button: the button kind and the quote's number
+fn quote_button_id(kind: &str, number: u32) -> String {
+    format!("{}_{}", kind, number)
+}
+
+/// Whether a quote button accepts, and the number of the quote it belongs to
+fn parse_quote_button(custom_id: &str) -> Option<(bool, u32)> {
+    let (accepted, rest) = match custom_id.strip_prefix(COMMISSION_QUOTE_ACCEPT_ID) {
+        Some(rest) => (true, rest),
+        None => (false, custom_id.strip_prefix(COMMISSION_QUOTE_DECLINE_ID)?),
+    };
+    let number = rest.strip_prefix('_')?.parse().ok()?;
+    Some((accepted, number))
+}
+
+/// Handle the Accept / Decline buttons on a quote
+pub async fn handle_commission_quote_response(
+    ctx: &Context,
+    component: &ComponentInteraction,
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let lifecycle = &lang.get(locale).commission.lifecycle;
+    let Some((accepted, number)) = parse_quote_button(&component.data.custom_id) else {
+        return Ok(());
+    };
+    let channel = component.channel_id.to_string();
+
+    let client_id = match data_manager.get_commission(&channel) {
+        Some(commission) => commission.client_id,
+        None => return respond_component_ephemeral(ctx, component, &lifecycle.not_commission_channel).await,
+    };
+
+    if component.user.id.to_string() != client_id {
+        return respond_component_ephemeral(ctx, component, &lifecycle.not_client).await;
+    }
+
+    let change = data_manager
+        .update_commission(&channel, |commission| {
+            if accepted {
+                commission.accept_quote(number)
+            } else {
+                commission.decline_quote(number)
+            }
+        })
+        .map_err(|e| e.to_string())?;
+    let commission = match change {
+        Some(Ok(((), commission))) => commission,
+        Some(Err(e)) => return respond_component_ephemeral(ctx, component, &transition_error_message(lang, locale, e, 0)).await,
+        None => return respond_component_ephemeral(ctx, component, &lifecycle.not_commission_channel).await,
+    };
+
+    // Remove the buttons so the quote can't be answered twice
+    component.create_response(&ctx.http,
+        CreateInteractionResponse::UpdateMessage(
+            CreateInteractionResponseMessage::new().components(vec![])
+        )
+    ).await?;
+
+    let client = format!("<@{}>", commission.client_id);
+    let announcement = if accepted {
+        lang.format_commission_quote_accepted(locale, &client)
+    } else {
+        lang.format_commission_quote_declined(locale, &client)
+    };
+    let embed = CreateEmbed::new()
+        .description(announcement)
+        .color(if accepted { Color::from_rgb(0, 255, 127) } else { Color::ORANGE });
+    component.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await?;
+
+    refresh_progress_embed(ctx, component.channel_id, &commission, data_manager, lang, locale).await;
+
+    Ok(())
+}
+
+/// Edit the pinned progress embed, or post and pin a new one if it's missing
+async fn refresh_progress_embed(
+    ctx: &Context,
+    channel_id: ChannelId,
+    commission: &Commission,
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+) {
+    let embed = create_progress_embed(lang, locale, commission);
+
+    if let Some(message_id) = commission.progress_message_id.as_deref().and_then(|id| id.parse::<u64>().ok()) {
+        let edit = EditMessage::new().embed(embed.clone());
+        if channel_id.edit_message(&ctx.http, MessageId::new(message_id), edit).await.is_ok() {
+            return;
+        }
+    }
+
+    match channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await {
+        Ok(message) => {
+            if let Err(e) = message.pin(&ctx.http).await {
+                eprintln!("Warning: Could not pin commission progress message: {}", e);
+            }
+            if let Err(e) = data_manager.set_commission_progress_message(&commission.channel_id, message.id.to_string()) {
+                eprintln!("Error storing commission progress message: {}", e);
+            }
+        }
+        Err(e) => eprintln!("Error sending commission progress message: {}", e),
+    }
+}
+
+/// Create the quote embed sent to the client
+fn create_quote_embed(lang: &LanguageManager, locale: &str, commission: &Commission) -> CreateEmbed {
+    let lifecycle = &lang.get(locale).commission.lifecycle;
+    let mut embed = CreateEmbed::new()
+        .title(&lifecycle.quote_title)
+        .description(lang.format_commission_quote_description(locale, &format!("<@{}>", commission.client_id)))
+        .color(Color::from_rgb(105, 90, 205)) // Purple theme
+        .footer(CreateEmbedFooter::new(lang.format_commission_brief_footer(locale, &commission.id)))
+        .timestamp(chrono::Utc::now());
+
+    if let Some(quote) = &commission.quote {
+        embed = embed.field(&lifecycle.quote_amount_field, truncate_field(&quote.amount), true);
+        if !quote.details.is_empty() {
+            embed = embed.field(&lifecycle.quote_details_field, truncate_field(&quote.details), false);
+        }
+    }
+
+    embed
+}
+
+/// Create the progress embed pinned in the commission channel
+fn create_progress_embed(lang: &LanguageManager, locale: &str, commission: &Commission) -> CreateEmbed {
+    let lifecycle = &lang.get(locale).commission.lifecycle;
+    let (completed, total) = commission.progress();
+
+    let milestones = if commission.milestones.is_empty() {
+        lifecycle.progress_no_milestones.clone()
+    } else {
+        truncate_field(&commission.milestones
+            .iter()
+            .enumerate()
+            .map(|(index, milestone)| {
+                let check = if milestone.is_completed() { "✅" } else { "⬜" };
+                let due = milestone.due_date
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+                    .map(|date| format!(" — {}", lang.format_commission_progress_due(locale, &format!("<t:{}:D>", date.and_utc().timestamp()))))
+                    .unwrap_or_default();
+                format!("{} **{}.** {}{}", check, index + 1, milestone.title, due)
+            })
+            .collect::<Vec<_>>()
+            .join("\n"))
+    };
+
+    let mut embed = CreateEmbed::new()
+        .title(&lifecycle.progress_title)
+        .color(Color::from_rgb(105, 90, 205)) // Purple theme
+        .field(&lifecycle.progress_status_field, lang.commission_status_label(locale, commission.status), true);
+
+    if let Some(quote) = &commission.quote {
+        embed = embed.field(&lifecycle.progress_quote_field, truncate_field(&quote.amount), true);
+    }
+
+    embed
+        .field(format!("{} ({}/{})", lifecycle.progress_milestones_field, completed, total), milestones, false)
+        .footer(CreateEmbedFooter::new(lang.format_commission_progress_footer(locale, &commission.id)))
+        .timestamp(chrono::Utc::now())
+}
+
+/// Map a rejected lifecycle transition to a user-facing message
+fn transition_error_message(lang: &LanguageManager, locale: &str, error: CommissionTransitionError, number: usize) -> String {
+    match error {
+        CommissionTransitionError::InvalidStatus(status) => lang.format_commission_invalid_status(locale, status),
+        CommissionTransitionError::UnknownMilestone => lang.format_commission_unknown_milestone(locale, number),
+        CommissionTransitionError::MilestoneAlreadyCompleted => lang.format_commission_milestone_already_completed(locale, number),
+        CommissionTransitionError::OutdatedQuote => lang.get(locale).commission.lifecycle.quote_outdated.clone(),
+    }
+}
+
+fn string_option(options: &[CommandDataOption], name: &str) -> Option<String> {
+    options.iter().find(|o| o.name == name).and_then(|o| match &o.value {
+        CommandDataOptionValue::String(value) => Some(value.clone()),
+        _ => None,
+    })
+}
+
+fn integer_option(options: &[CommandDataOption], name: &str) -> Option<i64> {
+    options.iter().find(|o| o.name == name).and_then(|o| match &o.value {
+        CommandDataOptionValue::Integer(value) => Some(*value),
+        _ => None,
+    })
+}
+
+async fn respond_public(
+    ctx: &Context,
+    command: &CommandInteraction,
+    content: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let embed = CreateEmbed::new()
+        .description(content)
+        .color(Color::from_rgb(105, 90, 205));
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new().embed(embed)
+    );
+    command.create_response(&ctx.http, response).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quote_buttons_carry_their_quote_number() {
+        let accept = quote_button_id(COMMISSION_QUOTE_ACCEPT_ID, 3);
+        assert_eq!(parse_quote_button(&accept), Some((true, 3)));
+        assert_eq!(parse_quote_button(&quote_button_id(COMMISSION_QUOTE_DECLINE_ID, 12)), Some((false, 12)));
+        assert_eq!(parse_quote_button("commission_quote_accepted"), None);
+        assert_eq!(parse_quote_button("commission_create"), None);
+    }
+}
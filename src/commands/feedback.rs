@@ -14,31 +14,13 @@ const FEEDBACK_CHANNEL_ID: &str = "1400466972293992498";
 pub async fn handle_feedback_setup_command(
     ctx: &Context,
     command: &CommandInteraction,
-    data_manager: &DataManager,
     lang: &LanguageManager,
+    locale: &str,
     images: &ImageManager,
     emojis: &EmojiManager,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let lang_msgs = lang.get();
+    let lang_msgs = lang.get(locale);
     
-    // Check if user has administrator permission
-    if let Some(guild_id) = command.guild_id {
-        if let Ok(member) = guild_id.member(&ctx.http, command.user.id).await {
-            if !member.permissions(&ctx.cache)?.administrator() {
-                let embed = CreateEmbed::new()
-                    .title(&lang_msgs.feedback.messages.setup_error_permission_title)
-                    .description(&lang_msgs.feedback.messages.setup_error_permission)
-                    .color(Color::RED);
-                
-                let response = CreateInteractionResponse::Message(
-                    CreateInteractionResponseMessage::new().embed(embed)
-                );
-                command.create_response(&ctx.http, response).await?;
-                return Ok(());
-            }
-        }
-    }
-
     // Get the feedback channel
     let channel_id = ChannelId::new(FEEDBACK_CHANNEL_ID.parse::<u64>()?);
     
@@ -119,6 +101,7 @@ pub async fn handle_feedback_message(
     msg: &Message,
     data_manager: &DataManager,
     lang: &LanguageManager,
+    locale: &str,
     _images: &ImageManager,
     emojis: &EmojiManager,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -135,7 +118,7 @@ pub async fn handle_feedback_message(
         }
         
         // Send a warning to the user (optional)
-        let lang_msgs = lang.get();
+        let lang_msgs = lang.get(locale);
         let warning = format!("<@{}>, {}", msg.author.id, &lang_msgs.feedback.messages.content_filtered);
         let warning_msg = msg.channel_id.say(&ctx.http, warning).await?;
         
@@ -155,7 +138,7 @@ pub async fn handle_feedback_message(
     }
 
     // Create the feedback embed
-    let lang_msgs = lang.get();
+    let lang_msgs = lang.get(locale);
     let embed = CreateEmbed::new()
         .title(&format!("{} {}", 
             emojis.get_emoji("interface", "star").unwrap_or(&"⭐".to_string()),
@@ -177,52 +160,11 @@ pub async fn handle_feedback_message(
     let message = CreateMessage::new().embed(embed);
     let sent_message = msg.channel_id.send_message(&ctx.http, message).await?;
 
-    // Add reactions for voting using custom bot emojis
-    let upvote_emoji = if let Some(up_emoji_str) = emojis.get_emoji("status", "up") {
-        // Parse custom emoji: <:up:1400579660408029235>
-        if let Some(emoji_id_str) = up_emoji_str.split(':').nth(2) {
-            if let Some(emoji_id_str) = emoji_id_str.strip_suffix('>') {
-                if let Ok(emoji_id) = emoji_id_str.parse::<u64>() {
-                    ReactionType::Custom {
-                        animated: false,
-                        id: serenity::model::id::EmojiId::new(emoji_id),
-                        name: Some("up".to_string()),
-                    }
-                } else {
-                    ReactionType::Unicode("⬆️".to_string())
-                }
-            } else {
-                ReactionType::Unicode("⬆️".to_string())
-            }
-        } else {
-            ReactionType::Unicode("⬆️".to_string())
-        }
-    } else {
-        ReactionType::Unicode("⬆️".to_string())
-    };
-
-    let downvote_emoji = if let Some(down_emoji_str) = emojis.get_emoji("status", "down") {
-        // Parse custom emoji: <:down:1400579662161383594>
-        if let Some(emoji_id_str) = down_emoji_str.split(':').nth(2) {
-            if let Some(emoji_id_str) = emoji_id_str.strip_suffix('>') {
-                if let Ok(emoji_id) = emoji_id_str.parse::<u64>() {
-                    ReactionType::Custom {
-                        animated: false,
-                        id: serenity::model::id::EmojiId::new(emoji_id),
-                        name: Some("down".to_string()),
-                    }
-                } else {
-                    ReactionType::Unicode("⬇️".to_string())
-                }
-            } else {
-                ReactionType::Unicode("⬇️".to_string())
-            }
-        } else {
-            ReactionType::Unicode("⬇️".to_string())
-        }
-    } else {
-        ReactionType::Unicode("⬇️".to_string())
-    };
+    // Add reactions for voting using custom bot emojis (Unicode when unavailable)
+    let upvote_emoji = emojis.get_reaction("status", "up")
+        .unwrap_or_else(|| ReactionType::Unicode("⬆️".to_string()));
+    let downvote_emoji = emojis.get_reaction("status", "down")
+        .unwrap_or_else(|| ReactionType::Unicode("⬇️".to_string()));
     
     sent_message.react(&ctx.http, upvote_emoji).await?;
     sent_message.react(&ctx.http, downvote_emoji).await?;
@@ -302,6 +244,7 @@ pub async fn handle_feedback_reaction_add(
     reaction: &serenity::model::channel::Reaction,
     data_manager: &DataManager,
     lang: &LanguageManager,
+    locale: &str,
     emojis: &EmojiManager,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Skip if reaction is from a bot
@@ -356,7 +299,7 @@ pub async fn handle_feedback_reaction_add(
 
         if updated {
             // Update the message embed with new rating
-            let lang_msgs = lang.get();
+            let lang_msgs = lang.get(locale);
             let new_star_display = generate_star_display(feedback_msg.upvotes, feedback_msg.downvotes, emojis);
             
             let embed = CreateEmbed::new()
@@ -402,6 +345,7 @@ pub async fn handle_feedback_reaction_remove(
     reaction: &serenity::model::channel::Reaction,
     data_manager: &DataManager,
     lang: &LanguageManager,
+    locale: &str,
     emojis: &EmojiManager,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Skip if reaction is from a bot
@@ -456,7 +400,7 @@ pub async fn handle_feedback_reaction_remove(
 
         if updated {
             // Update the message embed with new rating
-            let lang_msgs = lang.get();
+            let lang_msgs = lang.get(locale);
             let new_star_display = generate_star_display(feedback_msg.upvotes, feedback_msg.downvotes, emojis);
             
             let embed = CreateEmbed::new()
@@ -503,6 +447,7 @@ async fn update_feedback_reactions(
     message_id: serenity::model::id::MessageId,
     data_manager: &DataManager,
     lang: &LanguageManager,
+    locale: &str,
     emojis: &EmojiManager,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Get the feedback message data
@@ -538,7 +483,7 @@ async fn update_feedback_reactions(
                 feedback_msg.downvotes = downvotes;
 
                 // Update the message embed with new rating
-                let lang_msgs = lang.get();
+                let lang_msgs = lang.get(locale);
                 let new_star_display = generate_star_display(feedback_msg.upvotes, feedback_msg.downvotes, emojis);
                 
                 let embed = CreateEmbed::new()
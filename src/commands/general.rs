@@ -0,0 +1,275 @@
+//! /ping, /info, /hello, /images and the User Info context menu
+
+use std::time::Instant;
+use chrono::Utc;
+use serenity::all::{
+    Color, CommandInteraction, Context, CreateEmbed, CreateEmbedFooter, CreateInteractionResponse,
+    CreateInteractionResponseMessage,
+};
+use crate::data::DataManager;
+use crate::lang::{EmojiManager, ImageManager, LanguageManager};
+
+fn format_uptime(started: Instant) -> String {
+    let uptime = started.elapsed();
+    let days = uptime.as_secs() / 86400;
+    let hours = (uptime.as_secs() % 86400) / 3600;
+    let minutes = (uptime.as_secs() % 3600) / 60;
+    let seconds = uptime.as_secs() % 60;
+
+    if days > 0 {
+        format!("{}d {}h {}m {}s", days, hours, minutes, seconds)
+    } else if hours > 0 {
+        format!("{}h {}m {}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+fn memory_usage() -> f64 {
+    // Get basic memory usage (this is a simplified version)
+    // In a real scenario, you might want to use a more sophisticated method
+    let _pid = std::process::id();
+    // For now, return a placeholder. You could integrate with system monitoring libraries
+    42.5 // MB placeholder
+}
+
+/// Handle the /ping command
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_ping_command(
+    ctx: &Context,
+    command: &CommandInteraction,
+    lang: &LanguageManager,
+    locale: &str,
+    images: &ImageManager,
+    emojis: &EmojiManager,
+    received: Instant,
+    started: Instant,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let lang_msgs = lang.get(locale);
+
+    // Calculate latency
+    let latency = received.elapsed().as_millis() as u64;
+    let uptime = format_uptime(started);
+    let memory = memory_usage();
+
+    let embed = CreateEmbed::new()
+        .title(&format!("{} {}", emojis.success(), lang_msgs.embeds.ping.title))
+        .description(&lang_msgs.embeds.ping.description)
+        .color(Color::from_rgb(0, 255, 127)) // Spring green
+        .field(
+            format!("{} {}", emojis.get_emoji("status", "up").unwrap_or(&"📶".to_string()), lang_msgs.embeds.ping.latency_field),
+            lang.format_ping_embed_latency(locale, latency),
+            true,
+        )
+        .field(
+            format!("{} {}", emojis.get_emoji("status", "on").unwrap_or(&"🟢".to_string()), lang_msgs.embeds.ping.uptime_field),
+            lang.format_ping_embed_uptime(locale, &uptime),
+            true,
+        )
+        .field(
+            format!("{} {}", emojis.get_emoji("technology", "console").unwrap_or(&"💻".to_string()), lang_msgs.embeds.ping.memory_field),
+            lang.format_ping_embed_memory(locale, memory),
+            true,
+        )
+        .thumbnail(
+            images.get_default_image("ping_embed")
+                .unwrap_or(&lang_msgs.embeds.ping.thumbnail)
+        )
+        .footer(CreateEmbedFooter::new(&lang_msgs.embeds.ping.footer))
+        .timestamp(Utc::now());
+
+    let data = CreateInteractionResponseMessage::new().embed(embed);
+    command.create_response(&ctx.http, CreateInteractionResponse::Message(data)).await?;
+    Ok(())
+}
+
+/// Handle the /info command
+pub async fn handle_info_command(
+    ctx: &Context,
+    command: &CommandInteraction,
+    lang: &LanguageManager,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let data = CreateInteractionResponseMessage::new().content(&lang.get(locale).responses.info);
+    command.create_response(&ctx.http, CreateInteractionResponse::Message(data)).await?;
+    Ok(())
+}
+
+/// Handle the /hello command
+pub async fn handle_hello_command(
+    ctx: &Context,
+    command: &CommandInteraction,
+    lang: &LanguageManager,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let data = CreateInteractionResponseMessage::new().content(lang.format_hello(locale, &command.user.name));
+    command.create_response(&ctx.http, CreateInteractionResponse::Message(data)).await?;
+    Ok(())
+}
+
+/// Handle the /images command
+pub async fn handle_images_command(
+    ctx: &Context,
+    command: &CommandInteraction,
+    lang: &LanguageManager,
+    locale: &str,
+    images: &ImageManager,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let lang_msgs = lang.get(locale);
+
+    // Create images gallery embed
+    let categories = images.list_categories();
+    let mut total_images = 0;
+    let mut description = lang_msgs.embeds.images.description.clone();
+    description.push_str("\n\n");
+
+    for category in &categories {
+        let images_in_category = images.list_images_in_category(category);
+        total_images += images_in_category.len();
+        description.push_str(&format!("**{}**: {} images\n",
+            category.to_uppercase(), images_in_category.len()));
+    }
+
+    // Use a random avatar image for thumbnail
+    let thumbnail_url = images.get_random_avatar()
+        .or_else(|| images.get_image("avatar", "pointing"))
+        .unwrap_or(&lang_msgs.embeds.ping.thumbnail);
+
+    let embed = CreateEmbed::new()
+        .title(&lang_msgs.embeds.images.title)
+        .description(description)
+        .color(Color::from_rgb(138, 43, 226)) // Blue violet
+        .thumbnail(thumbnail_url)
+        .footer(CreateEmbedFooter::new(
+            lang.format_images_embed_footer(locale, total_images)
+        ))
+        .timestamp(Utc::now());
+
+    let data = CreateInteractionResponseMessage::new().embed(embed);
+    command.create_response(&ctx.http, CreateInteractionResponse::Message(data)).await?;
+    Ok(())
+}
+
+/// Handle the User Info context menu command
+pub async fn handle_userinfo_command(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+    images: &ImageManager,
+    emojis: &EmojiManager,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let lang_msgs = lang.get(locale);
+
+    // Get target user from context menu
+    let target_user = if let Some(target_id) = command.data.target_id {
+        // For user context menu commands, convert target_id to UserId first
+        let user_id = target_id.to_user_id();
+        user_id.to_user(&ctx.http).await.unwrap_or_else(|_| command.user.clone())
+    } else {
+        command.user.clone()
+    };
+
+    // Get member info if in a guild
+    let member_info = if let Some(guild_id) = command.guild_id {
+        guild_id.member(&ctx.http, target_user.id).await.ok()
+    } else {
+        None
+    };
+
+    // Format roles
+    let roles_text = if let Some(ref member) = member_info {
+        if member.roles.is_empty() {
+            lang_msgs.embeds.userinfo.no_roles.clone()
+        } else {
+            member.roles.iter()
+                .take(10) // Limit roles to prevent embed overflow
+                .map(|role_id| format!("<@&{}>", role_id))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+    } else {
+        lang_msgs.embeds.userinfo.no_roles.clone()
+    };
+
+    // Format account creation date
+    let account_created = format!("<t:{}:F>", target_user.created_at().timestamp());
+
+    // Format server join date
+    let server_joined = if let Some(ref member) = member_info {
+        if let Some(joined_at) = member.joined_at {
+            format!("<t:{}:F>", joined_at.timestamp())
+        } else {
+            "Unknown".to_string()
+        }
+    } else {
+        "Not in server".to_string()
+    };
+
+    // Get user avatar or default
+    let user_avatar = target_user.avatar_url().unwrap_or_else(|| target_user.default_avatar_url());
+
+    // Get thumbnail from bot images
+    let thumbnail_url = images.get_default_image("userinfo_embed")
+        .or_else(|| {
+            // Try to resolve the thumbnail reference from lang file
+            images.get_image("talking", &lang_msgs.embeds.userinfo.thumbnail)
+        })
+        .unwrap_or(&lang_msgs.embeds.userinfo.thumbnail);
+
+    let embed = CreateEmbed::new()
+        .title(format!("{} {}", emojis.get_emoji("interface", "stats").unwrap_or(&"📊".to_string()), lang_msgs.embeds.userinfo.title))
+        .description(lang.format_userinfo_embed_description(locale, &target_user.name))
+        .color(Color::from_rgb(105, 90, 205)) // Our purple theme
+        .field(
+            format!("{} {}", emojis.get_emoji("interface", "list").unwrap_or(&"🆔".to_string()), lang_msgs.embeds.userinfo.user_id_field),
+            target_user.id.to_string(),
+            true,
+        )
+        .field(
+            format!("{} {}", emojis.get_emoji("interface", "star").unwrap_or(&"⭐".to_string()), lang_msgs.embeds.userinfo.account_created_field),
+            account_created,
+            true,
+        )
+        .field(
+            format!("{} {}", emojis.get_emoji("emotions", "heart").unwrap_or(&"💖".to_string()), lang_msgs.embeds.userinfo.server_joined_field),
+            server_joined,
+            true,
+        )
+        .field(
+            format!("{} {}", emojis.get_emoji("interface", "staff").unwrap_or(&"👥".to_string()), lang_msgs.embeds.userinfo.roles_field),
+            roles_text,
+            false,
+        );
+
+    // Add AI summary if available
+    let ai_summary_text = if let Some(context) = data_manager.get_conversation_context(&target_user.id.to_string()) {
+        if context.has_summary() {
+            context.get_summary().to_string()
+        } else {
+            lang_msgs.embeds.userinfo.no_ai_summary.clone()
+        }
+    } else {
+        lang_msgs.embeds.userinfo.no_ai_summary.clone()
+    };
+
+    let embed = embed
+        .field(
+            format!("{} {}", emojis.get_emoji("emotions", "happy").unwrap_or(&"🤖".to_string()), lang_msgs.embeds.userinfo.ai_summary_field),
+            ai_summary_text,
+            false,
+        )
+        .thumbnail(thumbnail_url)
+        .image(user_avatar)
+        .footer(CreateEmbedFooter::new(
+            lang.format_userinfo_embed_footer(locale, &command.user.name)
+        ))
+        .timestamp(Utc::now());
+
+    let data = CreateInteractionResponseMessage::new().embed(embed);
+    command.create_response(&ctx.http, CreateInteractionResponse::Message(data)).await?;
+    Ok(())
+}
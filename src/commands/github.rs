@@ -20,16 +20,6 @@ pub async fn handle_github_channel_command(
     command: &CommandInteraction,
     data_manager: &DataManager,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    if command.user.id.get() != config::OWNER_ID {
-        let response = CreateInteractionResponse::Message(
-            CreateInteractionResponseMessage::new()
-                .content("❌ Unauthorized. This command is owner-only.")
-                .ephemeral(true),
-        );
-        command.create_response(&ctx.http, response).await?;
-        return Ok(());
-    }
-
     let channel_id = match command.data.options.first().map(|o| &o.value) {
         Some(CommandDataOptionValue::Channel(id)) => id.get(),
         _ => {
@@ -80,14 +70,7 @@ pub async fn poll_github_events(
         return Ok(());
     };
 
-    let mut request = client
-        .get(format!(
-            "https://api.github.com/users/{}/events/public?per_page=30",
-            config::GITHUB_USER
-        ))
-        .header("User-Agent", "lorian-discord-bot")
-        .header("Accept", "application/vnd.github+json")
-        .header("X-GitHub-Api-Version", "2022-11-28");
+    let mut request = events_request(client);
     if let Some(tag) = etag.as_deref() {
         request = request.header("If-None-Match", tag);
     }
@@ -147,6 +130,33 @@ pub async fn poll_github_events(
     Ok(())
 }
 
+/// Fetch the newest public event worth announcing, as an embed (used by the AI assistant)
+pub async fn fetch_latest_event_embed(
+    client: &reqwest::Client,
+) -> Result<Option<CreateEmbed>, Box<dyn std::error::Error + Send + Sync>> {
+    let response = events_request(client).send().await?;
+    if !response.status().is_success() {
+        return Err(format!("GitHub API status {}", response.status()).into());
+    }
+
+    let events: serde_json::Value = response.json().await?;
+    Ok(events
+        .as_array()
+        .and_then(|events| events.iter().find_map(build_event_embed)))
+}
+
+/// GET request for the configured account's public events (newest first)
+fn events_request(client: &reqwest::Client) -> reqwest::RequestBuilder {
+    client
+        .get(format!(
+            "https://api.github.com/users/{}/events/public?per_page=30",
+            config::GITHUB_USER
+        ))
+        .header("User-Agent", "lorian-discord-bot")
+        .header("Accept", "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+}
+
 fn event_id(event: &serde_json::Value) -> Option<u64> {
     event["id"].as_str().and_then(|s| s.parse().ok())
 }
@@ -2,20 +2,24 @@
 use serenity::all::{
     CreateEmbed, CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage, 
     CommandInteraction, Context, CreateSelectMenu, CreateSelectMenuOption, CreateActionRow, 
-    Color, CreateButton, ButtonStyle, ComponentInteraction, CreateSelectMenuKind
+    Color, CreateButton, ButtonStyle, ComponentInteraction, CreateSelectMenuKind,
+    CommandDataOptionValue
 };
+use crate::auth::Caller;
+use crate::data::DataManager;
 use crate::lang::LanguageManager;
+use super::registry;
 use chrono::Utc;
 
-/// Handle the /help command with dropdown menu
-pub async fn handle_help_command(
-    ctx: &Context,
-    command: &CommandInteraction,
+/// Main help embed with a dropdown of the commands this user can use
+fn help_menu(
+    data_manager: &DataManager,
+    caller: &Caller,
     lang: &LanguageManager,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let lang_msgs = lang.get();
-    
-    // Create the main help embed
+    locale: &str,
+) -> (CreateEmbed, CreateActionRow) {
+    let lang_msgs = lang.get(locale);
+
     let embed = CreateEmbed::new()
         .title(&lang_msgs.embeds.help.title)
         .description(&lang_msgs.embeds.help.description)
@@ -23,33 +27,59 @@ pub async fn handle_help_command(
         .footer(CreateEmbedFooter::new(&lang_msgs.embeds.help.footer))
         .timestamp(Utc::now());
 
-    // Create dropdown menu with all commands
-    let select_menu = CreateSelectMenu::new(
-        "help_select", 
-        CreateSelectMenuKind::String {
-            options: vec![
-                CreateSelectMenuOption::new("🏓 Ping Command", "ping")
-                    .description("Check bot latency and status"),
-                CreateSelectMenuOption::new("ℹ️ Info Command", "info")
-                    .description("Get basic bot information"),
-                CreateSelectMenuOption::new("👋 Hello Command", "hello")
-                    .description("Get a personalized greeting"),
-                CreateSelectMenuOption::new("📊 Stats Command", "stats")
-                    .description("View bot usage statistics"),
-                CreateSelectMenuOption::new("🖼️ Images Command", "images")
-                    .description("Browse bot image gallery"),
-                CreateSelectMenuOption::new("👤 User Info Command", "userinfo")
-                    .description("Get detailed user information"),
-                CreateSelectMenuOption::new("🧹 Purge Command", "purge")
-                    .description("Delete multiple messages"),
-                CreateSelectMenuOption::new("⏰ Reminder Command", "reminder")
-                    .description("Set reminders for the future"),
-            ]
-        }
-    )
-    .placeholder(&lang_msgs.embeds.help.select_placeholder);
-
-    let action_row = CreateActionRow::SelectMenu(select_menu);
+    let options = registry::help_entries(data_manager, caller)
+        .filter_map(|spec| {
+            let page = lang_msgs.embeds.help.commands.get(spec.key)?;
+            Some(CreateSelectMenuOption::new(&page.title, spec.key).description(&page.description))
+        })
+        .collect();
+
+    let select_menu = CreateSelectMenu::new("help_select", CreateSelectMenuKind::String { options })
+        .placeholder(&lang_msgs.embeds.help.select_placeholder);
+
+    (embed, CreateActionRow::SelectMenu(select_menu))
+}
+
+/// Help page for one command, with a button back to the menu
+fn help_page(lang: &LanguageManager, locale: &str, key: &str) -> Option<(CreateEmbed, CreateActionRow)> {
+    let page = lang.get(locale).embeds.help.commands.get(key)?;
+
+    let embed = CreateEmbed::new()
+        .title(&page.title)
+        .description(&page.description)
+        .color(Color::from_rgb(105, 90, 205))
+        .field("📝 Usage", &page.usage, false)
+        .field("📖 Details", &page.details, false)
+        .footer(CreateEmbedFooter::new("TheLorian's Bot Help System"))
+        .timestamp(Utc::now());
+
+    // Create back button
+    let back_button = CreateButton::new("help_back")
+        .label("← Back to Help Menu")
+        .style(ButtonStyle::Secondary);
+
+    Some((embed, CreateActionRow::Buttons(vec![back_button])))
+}
+
+/// Handle the /help command: the dropdown menu, or a command's page straight away
+pub async fn handle_help_command(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let requested = command.data.options.iter().find_map(|option| match &option.value {
+        CommandDataOptionValue::String(key) if option.name == "command" => Some(key.as_str()),
+        _ => None,
+    });
+    let caller = Caller::of_command(command);
+    let visible = |key: &str| registry::help_entries(data_manager, &caller).any(|spec| spec.key == key);
+
+    let (embed, action_row) = match requested.filter(|key| visible(key)).and_then(|key| help_page(lang, locale, key)) {
+        Some(page) => page,
+        None => help_menu(data_manager, &caller, lang, locale),
+    };
 
     let response = CreateInteractionResponse::Message(
         CreateInteractionResponseMessage::new()
@@ -61,98 +91,55 @@ pub async fn handle_help_command(
     Ok(())
 }
 
+/// Autocomplete for /help's command option: help pages whose title matches what was typed
+pub fn command_choices(
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+    command: &CommandInteraction,
+) -> Vec<(String, String)> {
+    let typed = command
+        .data
+        .autocomplete()
+        .map(|option| option.value.to_lowercase())
+        .unwrap_or_default();
+    let pages = &lang.get(locale).embeds.help.commands;
+
+    registry::help_entries(data_manager, &Caller::of_command(command))
+        .filter_map(|spec| {
+            let page = pages.get(spec.key)?;
+            let matches = page.title.to_lowercase().contains(&typed) || spec.key.contains(&typed);
+            matches.then(|| (page.title.clone(), spec.key.to_string()))
+        })
+        .collect()
+}
+
 /// Handle help dropdown menu selection
 pub async fn handle_help_selection(
     ctx: &Context,
     interaction: &ComponentInteraction,
     lang: &LanguageManager,
+    locale: &str,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let lang_msgs = lang.get();
     let command_name = match &interaction.data.kind {
         serenity::model::application::ComponentInteractionDataKind::StringSelect { values } => &values[0],
         _ => return Ok(()),
     };
 
-    let (embed_title, embed_description, usage, details) = match command_name.as_str() {
-        "ping" => (
-            &lang_msgs.embeds.help.commands.ping.title,
-            &lang_msgs.embeds.help.commands.ping.description,
-            &lang_msgs.embeds.help.commands.ping.usage,
-            &lang_msgs.embeds.help.commands.ping.details,
-        ),
-        "info" => (
-            &lang_msgs.embeds.help.commands.info.title,
-            &lang_msgs.embeds.help.commands.info.description,
-            &lang_msgs.embeds.help.commands.info.usage,
-            &lang_msgs.embeds.help.commands.info.details,
-        ),
-        "hello" => (
-            &lang_msgs.embeds.help.commands.hello.title,
-            &lang_msgs.embeds.help.commands.hello.description,
-            &lang_msgs.embeds.help.commands.hello.usage,
-            &lang_msgs.embeds.help.commands.hello.details,
-        ),
-        "stats" => (
-            &lang_msgs.embeds.help.commands.stats.title,
-            &lang_msgs.embeds.help.commands.stats.description,
-            &lang_msgs.embeds.help.commands.stats.usage,
-            &lang_msgs.embeds.help.commands.stats.details,
-        ),
-        "images" => (
-            &lang_msgs.embeds.help.commands.images.title,
-            &lang_msgs.embeds.help.commands.images.description,
-            &lang_msgs.embeds.help.commands.images.usage,
-            &lang_msgs.embeds.help.commands.images.details,
-        ),
-        "userinfo" => (
-            &lang_msgs.embeds.help.commands.userinfo.title,
-            &lang_msgs.embeds.help.commands.userinfo.description,
-            &lang_msgs.embeds.help.commands.userinfo.usage,
-            &lang_msgs.embeds.help.commands.userinfo.details,
-        ),
-        "purge" => (
-            &lang_msgs.embeds.help.commands.purge.title,
-            &lang_msgs.embeds.help.commands.purge.description,
-            &lang_msgs.embeds.help.commands.purge.usage,
-            &lang_msgs.embeds.help.commands.purge.details,
-        ),
-        "reminder" => (
-            &lang_msgs.embeds.help.commands.reminder.title,
-            &lang_msgs.embeds.help.commands.reminder.description,
-            &lang_msgs.embeds.help.commands.reminder.usage,
-            &lang_msgs.embeds.help.commands.reminder.details,
-        ),
-        _ => {
-            // Default case - shouldn't happen but good to have
-            let embed = CreateEmbed::new()
-                .title("❌ Unknown Command")
-                .description("The selected command was not found.")
-                .color(Color::RED);
-            
-            let response = CreateInteractionResponse::UpdateMessage(
-                CreateInteractionResponseMessage::new().embed(embed)
-            );
-            interaction.create_response(&ctx.http, response).await?;
-            return Ok(());
-        }
+    let Some((embed, action_row)) = help_page(lang, locale, command_name) else {
+        // Default case - shouldn't happen but good to have
+        let embed = CreateEmbed::new()
+            .title("❌ Unknown Command")
+            .description("The selected command was not found.")
+            .color(Color::RED);
+        
+        let response = CreateInteractionResponse::UpdateMessage(
+            CreateInteractionResponseMessage::new().embed(embed)
+        );
+        interaction.create_response(&ctx.http, response).await?;
+        return Ok(());
     };
 
-    let embed = CreateEmbed::new()
-        .title(embed_title)
-        .description(embed_description)
-        .color(Color::from_rgb(105, 90, 205))
-        .field("📝 Usage", usage, false)
-        .field("📖 Details", details, false)
-        .footer(CreateEmbedFooter::new("TheLorian's Bot Help System"))
-        .timestamp(Utc::now());
-
-    // Create back button
-    let back_button = CreateButton::new("help_back")
-        .label("← Back to Help Menu")
-        .style(ButtonStyle::Secondary);
-
-    let action_row = CreateActionRow::Buttons(vec![back_button]);
-
     let response = CreateInteractionResponse::UpdateMessage(
         CreateInteractionResponseMessage::new()
             .embed(embed)
@@ -167,44 +154,12 @@ pub async fn handle_help_selection(
 pub async fn handle_help_back(
     ctx: &Context,
     interaction: &ComponentInteraction,
+    data_manager: &DataManager,
     lang: &LanguageManager,
+    locale: &str,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let lang_msgs = lang.get();
-    
     // Recreate the main help embed and dropdown
-    let embed = CreateEmbed::new()
-        .title(&lang_msgs.embeds.help.title)
-        .description(&lang_msgs.embeds.help.description)
-        .color(Color::from_rgb(105, 90, 205))
-        .footer(CreateEmbedFooter::new(&lang_msgs.embeds.help.footer))
-        .timestamp(Utc::now());
-
-    let select_menu = CreateSelectMenu::new(
-        "help_select", 
-        CreateSelectMenuKind::String {
-            options: vec![
-                CreateSelectMenuOption::new("🏓 Ping Command", "ping")
-                    .description("Check bot latency and status"),
-                CreateSelectMenuOption::new("ℹ️ Info Command", "info")
-                    .description("Get basic bot information"),
-                CreateSelectMenuOption::new("👋 Hello Command", "hello")
-                    .description("Get a personalized greeting"),
-                CreateSelectMenuOption::new("📊 Stats Command", "stats")
-                    .description("View bot usage statistics"),
-                CreateSelectMenuOption::new("🖼️ Images Command", "images")
-                    .description("Browse bot image gallery"),
-                CreateSelectMenuOption::new("👤 User Info Command", "userinfo")
-                    .description("Get detailed user information"),
-                CreateSelectMenuOption::new("🧹 Purge Command", "purge")
-                    .description("Delete multiple messages"),
-                CreateSelectMenuOption::new("⏰ Reminder Command", "reminder")
-                    .description("Set reminders for the future"),
-            ]
-        }
-    )
-    .placeholder(&lang_msgs.embeds.help.select_placeholder);
-
-    let action_row = CreateActionRow::SelectMenu(select_menu);
+    let (embed, action_row) = help_menu(data_manager, &Caller::of_component(interaction), lang, locale);
 
     let response = CreateInteractionResponse::UpdateMessage(
         CreateInteractionResponseMessage::new()
@@ -214,4 +169,4 @@ pub async fn handle_help_back(
 
     interaction.create_response(&ctx.http, response).await?;
     Ok(())
-}
\ No newline at end of file
+}
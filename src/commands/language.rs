@@ -0,0 +1,50 @@
+use serenity::all::{CommandDataOption, CommandDataOptionValue, CommandInteraction, Context};
+use crate::auth::Caller;
+use crate::data::DataManager;
+use crate::lang::LanguageManager;
+use super::registry::Access;
+use super::respond_ephemeral;
+
+/// Choice value meaning "no explicit language"
+pub const LANGUAGE_AUTO: &str = "auto";
+
+/// Handle /language me|server (me is for everyone; server is owner-only)
+pub async fn handle_language_command(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let Some(CommandDataOption { name, value: CommandDataOptionValue::SubCommand(options), .. }) = command.data.options.first() else {
+        return respond_ephemeral(ctx, command, "❌ Missing subcommand.").await;
+    };
+    let chosen = options.iter().find(|o| o.name == "language").and_then(|o| o.value.as_str());
+    // Anything that isn't a loaded locale (including "auto") clears the choice
+    let chosen = chosen.and_then(|tag| lang.find(tag));
+
+    match name.as_str() {
+        "me" => {
+            data_manager
+                .set_user_locale(&command.user.id.to_string(), chosen.map(str::to_string))
+                .map_err(|e| e.to_string())?;
+            // Confirm in the language that applies from now on
+            let locale = lang.resolve(chosen, Some(&command.locale), None);
+            respond_ephemeral(ctx, command, &lang.format_language_user_set(locale, chosen)).await
+        }
+        "server" => {
+            // Only this subcommand is restricted; the registry lets everyone use /language
+            if !Access::Owner.allows(data_manager, &Caller::of_command(command)) {
+                return respond_ephemeral(ctx, command, &Access::Owner.denial(lang, locale)).await;
+            }
+            let Some(guild_id) = command.guild_id else {
+                return respond_ephemeral(ctx, command, &lang.get(locale).language.server_only).await;
+            };
+            data_manager
+                .set_guild_locale(&guild_id.to_string(), chosen.map(str::to_string))
+                .map_err(|e| e.to_string())?;
+            respond_ephemeral(ctx, command, &lang.format_language_server_set(locale, chosen)).await
+        }
+        _ => respond_ephemeral(ctx, command, "❌ Unknown subcommand.").await,
+    }
+}
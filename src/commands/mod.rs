@@ -1,17 +1,26 @@
 // Módulo de comandos
 pub mod help;
 pub mod commission;
+pub mod commission_lifecycle;
 pub mod ticket;
 pub mod feedback;
 pub mod update;
 pub mod github;
+pub mod ai_tools;
+pub mod ai;
+pub mod language;
+pub mod registration;
+pub mod registry;
+pub mod reload;
+pub mod general;
+pub mod capability;
+pub mod sync;
+pub mod purge;
 
 use serenity::all::{
     CreateEmbed, CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage, 
-    CommandInteraction, Context, CommandDataOptionValue, Permissions, Color
+    CommandInteraction, ComponentInteraction, Context, CommandDataOptionValue, Color
 };
-use serenity::model::prelude::*;
-use serenity::builder::GetMessages;
 use crate::data::{DataManager, Reminder};
 use crate::lang::LanguageManager;
 use chrono::{Utc, Duration};
@@ -23,7 +32,12 @@ pub use help::{handle_help_command, handle_help_selection, handle_help_back};
 // Re-export commission functions
 pub use commission::{
     handle_commission_setup_command, handle_commission_create, handle_commission_close,
-    handle_commission_close_command
+    handle_commission_close_command, handle_commission_intake_submit, COMMISSION_INTAKE_MODAL_ID,
+    reconcile_commissions, handle_commission_channel_deleted
+};
+pub use commission_lifecycle::{
+    handle_commission_command, handle_commission_quote_response,
+    COMMISSION_QUOTE_ACCEPT_ID, COMMISSION_QUOTE_DECLINE_ID
 };
 
 // Re-export ticket functions
@@ -40,26 +54,28 @@ pub use feedback::{
 
 // Re-export update function
 pub use update::handle_update_command;
+pub use reload::handle_reload_command;
+pub use capability::handle_capability_command;
+pub use purge::handle_purge_command;
 
 // Re-export GitHub feed functions
 pub use github::{handle_github_channel_command, poll_github_events};
 
-/// Handle the /ai_channel command (owner only): persist the AI channel override
+// Re-export AI tool functions
+pub use ai_tools::{handle_ai_tool_calls, handle_ai_tool_confirmation, AI_TOOL_CONFIRM_ID, AI_TOOL_CANCEL_ID};
+
+// Re-export AI usage functions
+pub use ai::handle_ai_command;
+
+// Re-export language selection functions
+pub use language::{handle_language_command, LANGUAGE_AUTO};
+
+/// Handle the /ai_channel command (configure_ai): persist the AI channel override
 pub async fn handle_ai_channel_command(
     ctx: &Context,
     command: &CommandInteraction,
     data_manager: &DataManager,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    if command.user.id.get() != crate::config::OWNER_ID {
-        let response = CreateInteractionResponse::Message(
-            CreateInteractionResponseMessage::new()
-                .content("❌ Unauthorized. This command is owner-only.")
-                .ephemeral(true),
-        );
-        command.create_response(&ctx.http, response).await?;
-        return Ok(());
-    }
-
     let channel_id = match command.data.options.first().map(|o| &o.value) {
         Some(CommandDataOptionValue::Channel(id)) => id.get(),
         _ => {
@@ -121,152 +137,6 @@ pub async fn handle_stats_command(
     Ok(())
 }
 
-/// Handle the /purge command
-pub async fn handle_purge_command(
-    ctx: &Context,
-    command: &CommandInteraction,
-    lang: &LanguageManager,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let lang_msgs = lang.get();
-    
-    // Get the amount parameter
-    let amount = if let Some(option) = command.data.options.get(0) {
-        if let CommandDataOptionValue::Integer(amount) = &option.value {
-            *amount as u64
-        } else {
-            // Invalid parameter type
-            let embed = CreateEmbed::new()
-                .title(&lang_msgs.embeds.purge.title)
-                .description(&lang_msgs.embeds.purge.error_invalid_amount)
-                .color(Color::RED);
-            
-            let response = CreateInteractionResponse::Message(
-                CreateInteractionResponseMessage::new().embed(embed)
-            );
-            command.create_response(&ctx.http, response).await?;
-            return Ok(());
-        }
-    } else {
-        // No parameter provided
-        let embed = CreateEmbed::new()
-            .title(&lang_msgs.embeds.purge.title)
-            .description(&lang_msgs.embeds.purge.error_invalid_amount)
-            .color(Color::RED);
-        
-        let response = CreateInteractionResponse::Message(
-            CreateInteractionResponseMessage::new().embed(embed)
-        );
-        command.create_response(&ctx.http, response).await?;
-        return Ok(());
-    };
-
-    // Validate amount (1-100)
-    if amount < 1 || amount > 100 {
-        let embed = CreateEmbed::new()
-            .title(&lang_msgs.embeds.purge.title)
-            .description(&lang_msgs.embeds.purge.error_invalid_amount)
-            .color(Color::RED);
-        
-        let response = CreateInteractionResponse::Message(
-            CreateInteractionResponseMessage::new().embed(embed)
-        );
-        command.create_response(&ctx.http, response).await?;
-        return Ok(());
-    }
-
-    // Check if user has permission to manage messages
-    if let Some(guild_id) = command.guild_id {
-        if let Ok(member) = guild_id.member(&ctx.http, command.user.id).await {
-            let has_permission = {
-                if let Some(guild) = ctx.cache.guild(guild_id) {
-                    let permissions = guild.member_permissions(&member);
-                    permissions.contains(Permissions::MANAGE_MESSAGES)
-                } else {
-                    false
-                }
-            };
-            
-            if !has_permission {
-                let embed = CreateEmbed::new()
-                    .title(&lang_msgs.embeds.purge.title)
-                    .description(&lang_msgs.embeds.purge.error_permission)
-                    .color(Color::RED);
-                
-                let response = CreateInteractionResponse::Message(
-                    CreateInteractionResponseMessage::new().embed(embed)
-                );
-                command.create_response(&ctx.http, response).await?;
-                return Ok(());
-            }
-        }
-    }
-
-    // Try to delete messages
-    match command.channel_id.messages(&ctx.http, GetMessages::new().limit(amount as u8)).await {
-        Ok(messages) => {
-            let message_ids: Vec<MessageId> = messages.iter().map(|m| m.id).collect();
-            
-            if message_ids.is_empty() {
-                let embed = CreateEmbed::new()
-                    .title(&lang_msgs.embeds.purge.title)
-                    .description("No messages found to delete")
-                    .color(Color::ORANGE);
-                
-                let response = CreateInteractionResponse::Message(
-                    CreateInteractionResponseMessage::new().embed(embed)
-                );
-                command.create_response(&ctx.http, response).await?;
-                return Ok(());
-            }
-
-            // Delete messages
-            match command.channel_id.delete_messages(&ctx.http, &message_ids).await {
-                Ok(_) => {
-                    let deleted_count = message_ids.len() as u64;
-                    let embed = CreateEmbed::new()
-                        .title(&lang_msgs.embeds.purge.title)
-                        .description(&lang.format_purge_success(deleted_count))
-                        .color(Color::from_rgb(0, 255, 127))
-                        .footer(CreateEmbedFooter::new(&lang.format_purge_footer(deleted_count)));
-                    
-                    let response = CreateInteractionResponse::Message(
-                        CreateInteractionResponseMessage::new()
-                            .embed(embed)
-                            .ephemeral(true) // Make it ephemeral so only the user can see it
-                    );
-                    command.create_response(&ctx.http, response).await?;
-                }
-                Err(e) => {
-                    let error_msg = format!("Failed to delete messages: {}", e);
-                    let embed = CreateEmbed::new()
-                        .title(&lang_msgs.embeds.purge.title)
-                        .description(&error_msg)
-                        .color(Color::RED);
-                    
-                    let response = CreateInteractionResponse::Message(
-                        CreateInteractionResponseMessage::new().embed(embed)
-                    );
-                    command.create_response(&ctx.http, response).await?;
-                }
-            }
-        }
-        Err(e) => {
-            let error_msg = format!("Failed to fetch messages: {}", e);
-            let embed = CreateEmbed::new()
-                .title(&lang_msgs.embeds.purge.title)
-                .description(&error_msg)
-                .color(Color::RED);
-            
-            let response = CreateInteractionResponse::Message(
-                CreateInteractionResponseMessage::new().embed(embed)
-            );
-            command.create_response(&ctx.http, response).await?;
-        }
-    }
-
-    Ok(())
-}
-
 /// Parse time string like "5m", "2h", "1d" into Duration
 fn parse_time_string(time_str: &str) -> Option<Duration> {
     if time_str.is_empty() {
@@ -295,37 +165,11 @@ pub async fn handle_reminder_command(
     command: &CommandInteraction,
     data_manager: &DataManager,
     lang: &LanguageManager,
+    locale: &str,
     images: &crate::lang::ImageManager,
     emojis: &crate::lang::EmojiManager,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let lang_msgs = lang.get();
-    
-    // Check if user has administrator permission
-    if let Some(guild_id) = command.guild_id {
-        if let Ok(member) = guild_id.member(&ctx.http, command.user.id).await {
-            let has_permission = {
-                if let Some(guild) = ctx.cache.guild(guild_id) {
-                    let permissions = guild.member_permissions(&member);
-                    permissions.contains(Permissions::ADMINISTRATOR)
-                } else {
-                    false
-                }
-            };
-            
-            if !has_permission {
-                let embed = CreateEmbed::new()
-                    .title(&lang_msgs.embeds.reminder.title)
-                    .description(&lang_msgs.embeds.reminder.error_permission)
-                    .color(Color::RED);
-                
-                let response = CreateInteractionResponse::Message(
-                    CreateInteractionResponseMessage::new().embed(embed)
-                );
-                command.create_response(&ctx.http, response).await?;
-                return Ok(());
-            }
-        }
-    }
+    let lang_msgs = lang.get(locale);
     
     // Get the time, message, visibility, mention_type and has_status parameters
     let mut time_str = String::new();
@@ -464,7 +308,7 @@ pub async fn handle_reminder_command(
         
         let embed = CreateEmbed::new()
             .title(title_with_emoji)
-            .description(&lang.format_reminder_success(&formatted_time))
+            .description(&lang.format_reminder_success(locale, &formatted_time))
             .color(Color::from_rgb(138, 43, 226))
             .thumbnail(thumbnail_url)
             .field(&lang_msgs.embeds.reminder.time_field, &formatted_time, true)
@@ -473,7 +317,7 @@ pub async fn handle_reminder_command(
             .field(&lang_msgs.embeds.reminder.visibility_field, visibility_text, true)
             .field(&lang_msgs.embeds.reminder.mention_field, mention_text, true)
             .field(&lang_msgs.embeds.reminder.status_field, status_text, true)
-            .footer(CreateEmbedFooter::new(&lang.format_reminder_footer(&reminder_id[..8])))
+            .footer(CreateEmbedFooter::new(&lang.format_reminder_footer(locale, &reminder_id[..8])))
             .timestamp(Utc::now());
         
         let response = CreateInteractionResponse::Message(
@@ -493,4 +337,60 @@ pub async fn handle_reminder_command(
     }
 
     Ok(())
-} 
\ No newline at end of file
+} 
+
+/// Embed field values are capped at 1024 characters
+const MAX_FIELD_LENGTH: usize = 1024;
+
+/// Cut text to fit an embed field value, marking the cut with an ellipsis
+fn truncate_field(text: &str) -> String {
+    if text.chars().count() <= MAX_FIELD_LENGTH {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(MAX_FIELD_LENGTH - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Reply to a slash command with a message only the caller sees
+async fn respond_ephemeral(
+    ctx: &Context,
+    command: &CommandInteraction,
+    content: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true)
+    );
+    command.create_response(&ctx.http, response).await?;
+    Ok(())
+}
+
+/// Reply to a button or select menu with a message only the clicker sees
+async fn respond_component_ephemeral(
+    ctx: &Context,
+    component: &ComponentInteraction,
+    content: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true)
+    );
+    component.create_response(&ctx.http, response).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_fields_are_truncated() {
+        assert_eq!(truncate_field("150 USD"), "150 USD");
+        let long = truncate_field(&"milestone ".repeat(200));
+        assert_eq!(long.chars().count(), MAX_FIELD_LENGTH);
+        assert!(long.ends_with('…'));
+    }
+}
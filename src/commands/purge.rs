@@ -0,0 +1,445 @@
+//! /purge command handler
+//!
+//! Pages back through the channel from the newest message (or `before`) down
+//! to `after`, collecting up to `amount` messages that pass the filters.
+//! Discord only bulk deletes messages younger than 14 days; older ones stop
+//! the purge unless `old` asks for them to be deleted one by one, up to
+//! `MAX_OLD_DELETIONS` so the purge finishes within the interaction token's
+//! 15 minutes.
+
+use chrono::Utc;
+use regex::{Regex, RegexBuilder};
+use serenity::all::{
+    ChannelId, Color, CommandDataOptionValue, CommandInteraction, Context, CreateEmbed, CreateEmbedFooter,
+    CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage, EditInteractionResponse,
+    GetMessages, Message, MessageId, UserId,
+};
+use serenity::utils::MessageBuilder;
+use crate::data::DataManager;
+use crate::lang::LanguageManager;
+use super::truncate_field;
+
+/// Most messages one purge deletes
+pub const MAX_AMOUNT: i64 = 1000;
+/// Most messages one purge looks at, matching or not
+const MAX_SCANNED: usize = 5000;
+/// Most messages older than 14 days one purge deletes, one request each
+const MAX_OLD_DELETIONS: usize = 100;
+/// Longest text for the `contains` filter
+pub const MAX_CONTAINS_LENGTH: u16 = 200;
+/// Messages per history page and per bulk delete, Discord's limit for both
+const PAGE_SIZE: u8 = 100;
+/// Discord rejects bulk deletes of messages older than this
+const BULK_DELETE_MAX_AGE_SECS: i64 = 14 * 24 * 3600;
+/// Leeway so a message doesn't age out between collecting and deleting it
+const BULK_DELETE_MARGIN_SECS: i64 = 60;
+/// Largest compiled size accepted for the `regex` filter
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// Which messages a purge removes, besides the `before`/`after` range
+#[derive(Debug, Default)]
+struct PurgeFilter {
+    user: Option<UserId>,
+    bots: bool,
+    /// Lowercased text the message must contain
+    contains: Option<String>,
+    pattern: Option<Regex>,
+    attachments: bool,
+}
+
+impl PurgeFilter {
+    fn matches(&self, author: UserId, bot: bool, content: &str, has_attachments: bool) -> bool {
+        self.user.is_none_or(|user| user == author)
+            && (!self.bots || bot)
+            && (!self.attachments || has_attachments)
+            && self.contains.as_ref().is_none_or(|text| content.to_lowercase().contains(text))
+            && self.pattern.as_ref().is_none_or(|pattern| pattern.is_match(content))
+    }
+
+    fn matches_message(&self, message: &Message) -> bool {
+        self.matches(message.author.id, message.author.bot, &message.content, !message.attachments.is_empty())
+    }
+}
+
+/// Message ID from a raw ID or a message link
+fn parse_message_id(value: &str) -> Option<MessageId> {
+    value
+        .trim()
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .and_then(|id| id.parse::<u64>().ok())
+        .filter(|id| *id != 0)
+        .map(MessageId::new)
+}
+
+/// Whether a message sent at `sent` (unix seconds) can still be bulk deleted at `now`
+fn bulk_deletable(sent: i64, now: i64) -> bool {
+    now - sent < BULK_DELETE_MAX_AGE_SECS - BULK_DELETE_MARGIN_SECS
+}
+
+/// Channel that purge summaries are posted to (`MOD_LOG_CHANNEL_ID`)
+fn mod_log_channel() -> Option<ChannelId> {
+    std::env::var("MOD_LOG_CHANNEL_ID")
+        .ok()
+        .and_then(|id| id.trim().parse::<u64>().ok())
+        .filter(|id| *id != 0)
+        .map(ChannelId::new)
+}
+
+/// What a purge found and removed
+#[derive(Debug, Default)]
+struct PurgeOutcome {
+    scanned: usize,
+    deleted: usize,
+    /// Deleted one by one because they were too old to bulk delete
+    deleted_old: usize,
+    /// Stopped at messages too old to bulk delete
+    reached_old: bool,
+    /// Stopped after deleting `MAX_OLD_DELETIONS` old messages
+    old_limit_reached: bool,
+    error: Option<String>,
+}
+
+/// Handle the /purge command
+pub async fn handle_purge_command(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let lang_msgs = lang.get(locale);
+
+    let mut amount = None;
+    let mut filter = PurgeFilter::default();
+    let mut before = None;
+    let mut after = None;
+    let mut delete_old = false;
+    // Filters as shown in the summary: option name and the value given, if any
+    let mut filters: Vec<(&str, Option<String>)> = Vec::new();
+    for option in &command.data.options {
+        match (option.name.as_str(), &option.value) {
+            ("amount", CommandDataOptionValue::Integer(value)) => amount = Some(*value),
+            ("user", CommandDataOptionValue::User(user_id)) => {
+                filter.user = Some(*user_id);
+                filters.push(("user", Some(format!("<@{}>", user_id))));
+            }
+            ("bots", CommandDataOptionValue::Boolean(bots)) => {
+                filter.bots = *bots;
+                if *bots {
+                    filters.push(("bots", None));
+                }
+            }
+            ("contains", CommandDataOptionValue::String(text)) => {
+                filter.contains = Some(text.to_lowercase());
+                filters.push(("contains", Some(format!("\"{}\"", MessageBuilder::new().push_safe(text.as_str()).build()))));
+            }
+            ("regex", CommandDataOptionValue::String(pattern)) => {
+                let compiled = RegexBuilder::new(pattern)
+                    .case_insensitive(true)
+                    .size_limit(REGEX_SIZE_LIMIT)
+                    .build();
+                match compiled {
+                    Ok(compiled) => filter.pattern = Some(compiled),
+                    Err(e) => {
+                        let error = lang.format_purge_error_invalid_regex(locale, &e.to_string());
+                        return respond_error(ctx, command, lang, locale, &error).await;
+                    }
+                }
+                filters.push(("regex", Some(MessageBuilder::new().push_mono_safe(pattern.as_str()).build())));
+            }
+            ("attachments", CommandDataOptionValue::Boolean(attachments)) => {
+                filter.attachments = *attachments;
+                if *attachments {
+                    filters.push(("attachments", None));
+                }
+            }
+            ("before" | "after", CommandDataOptionValue::String(value)) => {
+                let Some(message_id) = parse_message_id(value) else {
+                    return respond_error(ctx, command, lang, locale, &lang_msgs.embeds.purge.error_invalid_message_id).await;
+                };
+                if option.name == "before" {
+                    before = Some(message_id);
+                } else {
+                    after = Some(message_id);
+                }
+                filters.push((option.name.as_str(), Some(message_id.to_string())));
+            }
+            ("old", CommandDataOptionValue::Boolean(old)) => delete_old = *old,
+            _ => {}
+        }
+    }
+
+    // Validate amount (1-MAX_AMOUNT)
+    let Some(amount) = amount.filter(|amount| (1..=MAX_AMOUNT).contains(amount)) else {
+        return respond_error(ctx, command, lang, locale, &lang_msgs.embeds.purge.error_invalid_amount).await;
+    };
+    let amount = amount as usize;
+
+    // Paging through history can outlast the 3 second reply window
+    let defer_response =
+        CreateInteractionResponse::Defer(CreateInteractionResponseMessage::new().ephemeral(true));
+    command.create_response(&ctx.http, defer_response).await?;
+
+    let outcome = purge(ctx, command.channel_id, &filter, before, after, amount, delete_old).await;
+
+    if outcome.deleted == 0 && outcome.error.is_none() && !outcome.reached_old {
+        let embed = CreateEmbed::new()
+            .title(&lang_msgs.embeds.purge.title)
+            .description(&lang_msgs.embeds.purge.nothing_found)
+            .color(Color::ORANGE);
+        command.edit_response(&ctx.http, EditInteractionResponse::new().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let mut description = lang.format_purge_success(locale, outcome.deleted as u64);
+    if let Some(error) = &outcome.error {
+        description.push_str("\n\n");
+        description.push_str(&lang.format_purge_error_failed(locale, error));
+    }
+    let reply = summary_embed(lang, locale, &filters, &outcome).description(description);
+    // The messages are gone whether or not the reply goes through; log them regardless
+    let replied = command.edit_response(&ctx.http, EditInteractionResponse::new().embed(reply)).await;
+
+    if outcome.deleted > 0 {
+        // The mod log is read by the whole staff, so it uses the server's language
+        let guild_locale = lang.resolve(
+            None,
+            None,
+            command.guild_id.and_then(|guild_id| data_manager.get_guild_locale(&guild_id.to_string())).as_deref(),
+        );
+        let log = summary_embed(lang, guild_locale, &filters, &outcome)
+            .title(&lang.get(guild_locale).embeds.purge.log_title)
+            .description(lang.format_purge_log_description(
+                guild_locale,
+                &format!("<@{}>", command.user.id),
+                outcome.deleted as u64,
+                &format!("<#{}>", command.channel_id),
+            ));
+        post_mod_log(ctx, command, log).await;
+    }
+
+    replied?;
+    Ok(())
+}
+
+/// Collect matching messages page by page, then delete them
+async fn purge(
+    ctx: &Context,
+    channel_id: ChannelId,
+    filter: &PurgeFilter,
+    before: Option<MessageId>,
+    after: Option<MessageId>,
+    amount: usize,
+    delete_old: bool,
+) -> PurgeOutcome {
+    let mut outcome = PurgeOutcome::default();
+    let now = Utc::now().timestamp();
+    let mut recent = Vec::new();
+    let mut old = Vec::new();
+    let mut cursor = before;
+
+    'paging: while outcome.scanned < MAX_SCANNED {
+        let request = cursor.map_or_else(
+            || GetMessages::new().limit(PAGE_SIZE),
+            |cursor| GetMessages::new().before(cursor).limit(PAGE_SIZE),
+        );
+        let page = match channel_id.messages(&ctx.http, request).await {
+            Ok(page) => page,
+            Err(e) => {
+                outcome.error = Some(e.to_string());
+                break;
+            }
+        };
+        let Some(last) = page.last() else { break };
+        cursor = Some(last.id);
+
+        for message in &page {
+            if after.is_some_and(|after| message.id <= after) {
+                break 'paging;
+            }
+            // Newest first: once one is too old, so is the rest of the channel
+            let deletable = bulk_deletable(message.timestamp.unix_timestamp(), now);
+            if !deletable && !delete_old {
+                outcome.reached_old = true;
+                break 'paging;
+            }
+            if !deletable && old.len() >= MAX_OLD_DELETIONS {
+                outcome.old_limit_reached = true;
+                break 'paging;
+            }
+            outcome.scanned += 1;
+            if !filter.matches_message(message) {
+                continue;
+            }
+            if deletable {
+                recent.push(message.id);
+            } else {
+                old.push(message.id);
+            }
+            if recent.len() + old.len() >= amount {
+                break 'paging;
+            }
+        }
+        if page.len() < PAGE_SIZE as usize {
+            break;
+        }
+    }
+
+    for chunk in recent.chunks(PAGE_SIZE as usize) {
+        // Bulk deletes need at least two messages
+        let deleted = match chunk {
+            [message_id] => channel_id.delete_message(&ctx.http, *message_id).await,
+            _ => channel_id.delete_messages(&ctx.http, chunk).await,
+        };
+        match deleted {
+            Ok(()) => outcome.deleted += chunk.len(),
+            Err(e) => {
+                outcome.error = Some(e.to_string());
+                return outcome;
+            }
+        }
+    }
+    for message_id in old {
+        match channel_id.delete_message(&ctx.http, message_id).await {
+            Ok(()) => {
+                outcome.deleted += 1;
+                outcome.deleted_old += 1;
+            }
+            Err(e) => {
+                outcome.error = Some(e.to_string());
+                return outcome;
+            }
+        }
+    }
+
+    outcome
+}
+
+/// Post a purge summary to the mod-log channel, when it belongs to the same server
+/// Summary of a finished purge, shared by the reply and the mod-log post
+fn summary_embed(lang: &LanguageManager, locale: &str, filters: &[(&str, Option<String>)], outcome: &PurgeOutcome) -> CreateEmbed {
+    let messages = &lang.get(locale).embeds.purge;
+    let filters = if filters.is_empty() {
+        messages.no_filters.clone()
+    } else {
+        let lines = filters
+            .iter()
+            .map(|(key, value)| {
+                let label = lang.command_info(locale, &["purge", key]).map_or(*key, |info| info.name.as_str());
+                match value {
+                    Some(value) => format!("`{}` {}", label, value),
+                    None => format!("`{}`", label),
+                }
+            })
+            .collect::<Vec<_>>();
+        truncate_field(&lines.join("\n"))
+    };
+    let mut summary = CreateEmbed::new()
+        .title(&messages.title)
+        .color(if outcome.error.is_some() { Color::ORANGE } else { Color::from_rgb(0, 255, 127) })
+        .field(&messages.filters_field, filters, false)
+        .field(&messages.scanned_field, outcome.scanned.to_string(), true)
+        .footer(CreateEmbedFooter::new(lang.format_purge_footer(locale, outcome.deleted as u64)))
+        .timestamp(Utc::now());
+    if outcome.deleted_old > 0 || outcome.reached_old {
+        let old = if outcome.reached_old {
+            messages.old_skipped.clone()
+        } else if outcome.old_limit_reached {
+            lang.format_purge_old_limit(locale, outcome.deleted_old as u64)
+        } else {
+            lang.format_purge_old_deleted(locale, outcome.deleted_old as u64)
+        };
+        summary = summary.field(&messages.old_field, old, true);
+    }
+    summary
+}
+
+async fn post_mod_log(ctx: &Context, command: &CommandInteraction, embed: CreateEmbed) {
+    let Some(channel_id) = mod_log_channel() else { return };
+    let same_guild = match channel_id.to_channel(&ctx.http).await {
+        Ok(channel) => channel.guild().is_some_and(|channel| Some(channel.guild_id) == command.guild_id),
+        Err(e) => {
+            eprintln!("❌ Failed to fetch mod-log channel {}: {}", channel_id, e);
+            return;
+        }
+    };
+    if !same_guild {
+        return;
+    }
+    if let Err(e) = channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await {
+        eprintln!("❌ Failed to post purge summary to mod-log channel {}: {}", channel_id, e);
+    }
+}
+
+async fn respond_error(
+    ctx: &Context,
+    command: &CommandInteraction,
+    lang: &LanguageManager,
+    locale: &str,
+    description: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let embed = CreateEmbed::new()
+        .title(&lang.get(locale).embeds.purge.title)
+        .description(description)
+        .color(Color::RED);
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .ephemeral(true)
+    );
+    command.create_response(&ctx.http, response).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_combine() {
+        let author = UserId::new(1);
+        let other = UserId::new(2);
+        assert!(PurgeFilter::default().matches(author, false, "", false));
+
+        let filter = PurgeFilter {
+            user: Some(author),
+            contains: Some("spam".to_string()),
+            ..Default::default()
+        };
+        assert!(filter.matches(author, false, "Buy SPAM now", false));
+        assert!(!filter.matches(other, false, "Buy SPAM now", false));
+        assert!(!filter.matches(author, false, "hello", false));
+
+        let filter = PurgeFilter {
+            bots: true,
+            attachments: true,
+            pattern: Some(RegexBuilder::new(r"^free \w+$").case_insensitive(true).build().unwrap()),
+            ..Default::default()
+        };
+        assert!(filter.matches(other, true, "FREE nitro", true));
+        assert!(!filter.matches(other, false, "FREE nitro", true));
+        assert!(!filter.matches(other, true, "FREE nitro", false));
+        assert!(!filter.matches(other, true, "get free nitro", true));
+    }
+
+    #[test]
+    fn message_ids_parse_from_ids_and_links() {
+        assert_eq!(parse_message_id(" 123 "), Some(MessageId::new(123)));
+        assert_eq!(
+            parse_message_id("https://discord.com/channels/1/2/345/"),
+            Some(MessageId::new(345)),
+        );
+        assert_eq!(parse_message_id("0"), None);
+        assert_eq!(parse_message_id("latest"), None);
+    }
+
+    #[test]
+    fn bulk_delete_window_is_fourteen_days() {
+        let now = 2_000_000_000;
+        assert!(bulk_deletable(now - 13 * 24 * 3600, now));
+        assert!(!bulk_deletable(now - 14 * 24 * 3600, now));
+        assert!(!bulk_deletable(now - BULK_DELETE_MAX_AGE_SECS + 10, now));
+    }
+}
@@ -0,0 +1,79 @@
+use serenity::builder::{CreateCommand, CreateCommandOption};
+use serenity::model::application::{CommandOptionType, CommandType};
+use crate::lang::{LanguageManager, DEFAULT_LOCALE};
+
+// Slash command builders whose names, descriptions and choice labels come from
+// `[commands.*]` in the lang files. Discord keeps sending the English names
+// in interactions, so handlers match on those whatever the user sees.
+
+fn name(lang: &LanguageManager, locale: &str, path: &[&str]) -> Option<String> {
+    lang.command_info(locale, path).map(|info| info.name.clone())
+}
+
+fn description(lang: &LanguageManager, locale: &str, path: &[&str]) -> Option<String> {
+    lang.command_info(locale, path).map(|info| info.description.clone())
+}
+
+/// Chat input command from `[commands.<key>]`
+pub fn slash_command(lang: &LanguageManager, key: &str) -> CreateCommand {
+    let path = [key];
+    let command = CreateCommand::new(name(lang, DEFAULT_LOCALE, &path).unwrap_or_else(|| key.to_string()))
+        .description(description(lang, DEFAULT_LOCALE, &path).unwrap_or_default());
+    let command = lang
+        .localizations(|locale| name(lang, locale, &path))
+        .into_iter()
+        .fold(command, |command, (tag, name)| command.name_localized(tag, name));
+    lang.localizations(|locale| description(lang, locale, &path))
+        .into_iter()
+        .fold(command, |command, (tag, description)| command.description_localized(tag, description))
+}
+
+/// User context menu command from `[commands.<key>]` (these have no description)
+pub fn user_command(lang: &LanguageManager, key: &str) -> CreateCommand {
+    let path = [key];
+    let command = CreateCommand::new(name(lang, DEFAULT_LOCALE, &path).unwrap_or_else(|| key.to_string()))
+        .kind(CommandType::User);
+    lang.localizations(|locale| name(lang, locale, &path))
+        .into_iter()
+        .fold(command, |command, (tag, name)| command.name_localized(tag, name))
+}
+
+/// Option, subcommand or group from `[commands.<path[0]>.options.<path[1]>...]`
+pub fn option(lang: &LanguageManager, kind: CommandOptionType, path: &[&str]) -> CreateCommandOption {
+    let key = path.last().copied().unwrap_or_default();
+    let option = CreateCommandOption::new(
+        kind,
+        name(lang, DEFAULT_LOCALE, path).unwrap_or_else(|| key.to_string()),
+        description(lang, DEFAULT_LOCALE, path).unwrap_or_default(),
+    );
+    let option = lang
+        .localizations(|locale| name(lang, locale, path))
+        .into_iter()
+        .fold(option, |option, (tag, name)| option.name_localized(tag, name));
+    lang.localizations(|locale| description(lang, locale, path))
+        .into_iter()
+        .fold(option, |option, (tag, description)| option.description_localized(tag, description))
+}
+
+/// String choice labelled from the option's `choices` table
+pub fn choice(lang: &LanguageManager, option: CreateCommandOption, path: &[&str], value: &str) -> CreateCommandOption {
+    labelled_choice(lang, option, value, |locale| {
+        lang.command_info(locale, path)
+            .and_then(|info| info.choices.get(value).cloned())
+            .unwrap_or_else(|| value.to_string())
+    })
+}
+
+/// String choice whose label comes from elsewhere in the lang files
+pub fn labelled_choice(
+    lang: &LanguageManager,
+    option: CreateCommandOption,
+    value: &str,
+    label: impl Fn(&str) -> String,
+) -> CreateCommandOption {
+    option.add_string_choice_localized(
+        label(DEFAULT_LOCALE),
+        value,
+        lang.localizations(|locale| Some(label(locale))),
+    )
+}
@@ -0,0 +1,594 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Instant;
+use serenity::all::{
+    AutocompleteChoice, CommandInteraction, Context, CreateAutocompleteResponse, CreateCommand,
+    CreateInteractionResponse, CreateInteractionResponseMessage, Permissions,
+};
+use serenity::model::application::CommandOptionType;
+use crate::ai::AIManager;
+use crate::auth::{self, Caller, Capability};
+use crate::data::{CommissionAvailability, CommissionStatus, DataManager};
+use crate::lang::assets::{AssetLibrary, Assets};
+use crate::lang::{LanguageManager, Messages, DEFAULT_LOCALE};
+use super::registration::{choice, labelled_choice, option, slash_command, user_command};
+
+// Every command the bot offers, declared once. Registration, dispatch,
+// autocomplete and the /help menu are all derived from `COMMANDS`; names,
+// descriptions and help pages come from the lang files under each key.
+
+pub type CommandResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
+type HandlerFuture<'a> = Pin<Box<dyn Future<Output = CommandResult> + Send + 'a>>;
+/// `(label, value)` suggestions for the focused option of an autocomplete request
+type Suggestions = fn(&DataManager, &LanguageManager, &str, &CommandInteraction) -> Vec<(String, String)>;
+
+/// Most choices Discord accepts in an autocomplete response
+const MAX_CHOICES: usize = 25;
+
+/// Who may use a command
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Access {
+    Everyone,
+    /// Guild members holding these permissions
+    Permission(Permissions),
+    /// Holders of a capability, see `auth`
+    Capability(Capability),
+    /// Only the bot owner
+    Owner,
+}
+
+impl Access {
+    /// Whether the caller may use the command
+    pub fn allows(self, data_manager: &DataManager, caller: &Caller) -> bool {
+        match self {
+            Access::Everyone => true,
+            Access::Permission(required) => caller
+                .permissions()
+                .is_some_and(|permissions| permissions.administrator() || permissions.contains(required)),
+            Access::Capability(capability) => auth::allowed(data_manager, caller, capability),
+            Access::Owner => caller.is_owner(),
+        }
+    }
+
+    /// Reply for a caller the command isn't available to
+    pub fn denial(self, lang: &LanguageManager, locale: &str) -> String {
+        match self {
+            Access::Everyone | Access::Permission(_) => lang.get(locale).responses.denied_permission.clone(),
+            Access::Capability(capability) => lang.format_denied_capability(locale, capability.as_str()),
+            Access::Owner => lang.get(locale).responses.denied_owner.clone(),
+        }
+    }
+
+    /// Who Discord shows the command to before any Integrations override
+    pub fn default_permissions(self) -> Option<Permissions> {
+        match self {
+            Access::Everyone => None,
+            Access::Permission(required) => Some(required),
+            Access::Capability(capability) => Some(capability.default_permissions()),
+            Access::Owner => Some(Permissions::empty()),
+        }
+    }
+}
+
+/// Everything a command handler may need
+pub struct Invocation<'a> {
+    pub ctx: &'a Context,
+    pub command: &'a CommandInteraction,
+    pub library: &'a AssetLibrary,
+    /// Assets as loaded when the interaction arrived
+    pub assets: &'a Assets,
+    pub data_manager: &'a DataManager,
+    pub ai_manager: &'a AIManager,
+    pub locale: &'a str,
+    /// When the interaction arrived
+    pub received: Instant,
+    /// When the bot started
+    pub started: Instant,
+}
+
+pub struct CommandSpec {
+    /// `[commands.<key>]` in the lang files
+    pub key: &'static str,
+    pub access: Access,
+    /// Listed in the /help menu, with its page from `[embeds.help.commands.<key>]`
+    pub help: bool,
+    pub build: fn(&LanguageManager) -> CreateCommand,
+    pub handler: for<'a> fn(&'a Invocation<'a>) -> HandlerFuture<'a>,
+    pub autocomplete: Option<Suggestions>,
+    /// Reply sent when the handler fails without answering the user itself
+    pub fallback: Option<fn(&Messages) -> String>,
+}
+
+pub static COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        key: "ping",
+        access: Access::Everyone,
+        help: true,
+        build: |lang| slash_command(lang, "ping"),
+        handler: |inv| Box::pin(super::general::handle_ping_command(
+            inv.ctx, inv.command, &inv.assets.lang, inv.locale, &inv.assets.images, &inv.assets.emojis,
+            inv.received, inv.started,
+        )),
+        autocomplete: None,
+        fallback: None,
+    },
+    CommandSpec {
+        key: "info",
+        access: Access::Everyone,
+        help: true,
+        build: |lang| slash_command(lang, "info"),
+        handler: |inv| Box::pin(super::general::handle_info_command(inv.ctx, inv.command, &inv.assets.lang, inv.locale)),
+        autocomplete: None,
+        fallback: None,
+    },
+    CommandSpec {
+        key: "hello",
+        access: Access::Everyone,
+        help: true,
+        build: |lang| slash_command(lang, "hello"),
+        handler: |inv| Box::pin(super::general::handle_hello_command(inv.ctx, inv.command, &inv.assets.lang, inv.locale)),
+        autocomplete: None,
+        fallback: None,
+    },
+    CommandSpec {
+        key: "help",
+        access: Access::Everyone,
+        help: false,
+        build: |lang| slash_command(lang, "help")
+            .add_option(option(lang, CommandOptionType::String, &["help", "command"])
+                .required(false)
+                .set_autocomplete(true)),
+        handler: |inv| Box::pin(super::handle_help_command(inv.ctx, inv.command, inv.data_manager, &inv.assets.lang, inv.locale)),
+        autocomplete: Some(super::help::command_choices),
+        fallback: Some(|messages| messages.responses.help.clone()),
+    },
+    CommandSpec {
+        key: "images",
+        access: Access::Everyone,
+        help: true,
+        build: |lang| slash_command(lang, "images"),
+        handler: |inv| Box::pin(super::general::handle_images_command(
+            inv.ctx, inv.command, &inv.assets.lang, inv.locale, &inv.assets.images,
+        )),
+        autocomplete: None,
+        fallback: None,
+    },
+    CommandSpec {
+        key: "stats",
+        access: Access::Everyone,
+        help: true,
+        build: |lang| slash_command(lang, "stats"),
+        handler: |inv| Box::pin(super::handle_stats_command(inv.ctx, inv.command, inv.data_manager, &inv.assets.lang)),
+        autocomplete: None,
+        fallback: Some(|_| "Error retrieving bot statistics.".to_string()),
+    },
+    CommandSpec {
+        key: "userinfo",
+        access: Access::Everyone,
+        help: true,
+        build: |lang| user_command(lang, "userinfo"),
+        handler: |inv| Box::pin(super::general::handle_userinfo_command(
+            inv.ctx, inv.command, inv.data_manager, &inv.assets.lang, inv.locale, &inv.assets.images, &inv.assets.emojis,
+        )),
+        autocomplete: None,
+        fallback: None,
+    },
+    CommandSpec {
+        key: "purge",
+        access: Access::Capability(Capability::Purge),
+        help: true,
+        build: build_purge,
+        handler: |inv| Box::pin(super::handle_purge_command(inv.ctx, inv.command, inv.data_manager, &inv.assets.lang, inv.locale)),
+        autocomplete: None,
+        fallback: Some(|_| "Error executing purge command.".to_string()),
+    },
+    CommandSpec {
+        key: "reminder",
+        access: Access::Permission(Permissions::ADMINISTRATOR),
+        help: true,
+        build: build_reminder,
+        handler: |inv| Box::pin(super::handle_reminder_command(
+            inv.ctx, inv.command, inv.data_manager, &inv.assets.lang, inv.locale, &inv.assets.images, &inv.assets.emojis,
+        )),
+        autocomplete: None,
+        fallback: Some(|_| "Error creating reminder.".to_string()),
+    },
+    CommandSpec {
+        key: "commission_setup",
+        access: Access::Permission(Permissions::ADMINISTRATOR),
+        help: true,
+        build: |lang| slash_command(lang, "commission_setup"),
+        handler: |inv| Box::pin(super::handle_commission_setup_command(
+            inv.ctx, inv.command, inv.data_manager, &inv.assets.lang, inv.locale, &inv.assets.images, &inv.assets.emojis,
+        )),
+        autocomplete: None,
+        fallback: Some(|_| "Error setting up commission system.".to_string()),
+    },
+    CommandSpec {
+        key: "commission_close",
+        access: Access::Everyone,
+        help: true,
+        build: |lang| slash_command(lang, "commission_close"),
+        handler: |inv| Box::pin(super::handle_commission_close_command(
+            inv.ctx, inv.command, inv.data_manager, &inv.assets.lang, inv.locale,
+        )),
+        autocomplete: None,
+        fallback: Some(|_| "Error closing commission.".to_string()),
+    },
+    CommandSpec {
+        key: "commission",
+        access: Access::Owner,
+        help: false,
+        build: build_commission,
+        handler: |inv| Box::pin(super::handle_commission_command(
+            inv.ctx, inv.command, inv.data_manager, &inv.assets.lang, inv.locale, &inv.assets.images, &inv.assets.emojis,
+        )),
+        autocomplete: None,
+        fallback: Some(|_| "Error updating commission.".to_string()),
+    },
+    CommandSpec {
+        key: "ticket_setup",
+        access: Access::Capability(Capability::ManageTickets),
+        help: false,
+        build: |lang| slash_command(lang, "ticket_setup"),
+        handler: |inv| Box::pin(super::handle_ticket_setup_command(
+            inv.ctx, inv.command, inv.data_manager, &inv.assets.lang, &inv.assets.images, &inv.assets.emojis,
+        )),
+        autocomplete: None,
+        fallback: Some(|_| "Error setting up ticket system.".to_string()),
+    },
+    CommandSpec {
+        key: "ticket_close",
+        access: Access::Everyone,
+        help: false,
+        build: |lang| slash_command(lang, "ticket_close"),
+        handler: |inv| Box::pin(super::handle_ticket_close_command(inv.ctx, inv.command, inv.data_manager, &inv.assets.lang)),
+        autocomplete: None,
+        fallback: Some(|_| "Error closing ticket.".to_string()),
+    },
+    CommandSpec {
+        key: "feedback_setup",
+        access: Access::Capability(Capability::ManageFeedback),
+        help: false,
+        build: |lang| slash_command(lang, "feedback_setup"),
+        handler: |inv| Box::pin(super::handle_feedback_setup_command(
+            inv.ctx, inv.command, &inv.assets.lang, inv.locale, &inv.assets.images, &inv.assets.emojis,
+        )),
+        autocomplete: None,
+        fallback: Some(|_| "Error setting up feedback system.".to_string()),
+    },
+    CommandSpec {
+        key: "update",
+        access: Access::Capability(Capability::UpdateBot),
+        help: false,
+        build: |lang| slash_command(lang, "update"),
+        handler: |inv| Box::pin(super::handle_update_command(inv.ctx, inv.command)),
+        autocomplete: None,
+        fallback: None,
+    },
+    CommandSpec {
+        key: "reload",
+        access: Access::Owner,
+        help: false,
+        build: |lang| slash_command(lang, "reload")
+            .add_option(option(lang, CommandOptionType::SubCommand, &["reload", "assets"])),
+        handler: |inv| Box::pin(super::handle_reload_command(inv.ctx, inv.command, inv.library, inv.data_manager)),
+        autocomplete: None,
+        fallback: None,
+    },
+    CommandSpec {
+        key: "github_channel",
+        access: Access::Owner,
+        help: false,
+        build: |lang| slash_command(lang, "github_channel")
+            .add_option(option(lang, CommandOptionType::Channel, &["github_channel", "channel"]).required(true)),
+        handler: |inv| Box::pin(super::handle_github_channel_command(inv.ctx, inv.command, inv.data_manager)),
+        autocomplete: None,
+        fallback: None,
+    },
+    CommandSpec {
+        key: "ai_channel",
+        access: Access::Capability(Capability::ConfigureAi),
+        help: false,
+        build: |lang| slash_command(lang, "ai_channel")
+            .add_option(option(lang, CommandOptionType::Channel, &["ai_channel", "channel"]).required(true)),
+        handler: |inv| Box::pin(super::handle_ai_channel_command(inv.ctx, inv.command, inv.data_manager)),
+        autocomplete: None,
+        fallback: None,
+    },
+    CommandSpec {
+        key: "capability",
+        access: Access::Owner,
+        help: false,
+        build: build_capability,
+        handler: |inv| Box::pin(super::handle_capability_command(inv.ctx, inv.command, inv.data_manager, &inv.assets.lang, inv.locale)),
+        autocomplete: None,
+        fallback: None,
+    },
+    CommandSpec {
+        key: "ai",
+        access: Access::Everyone,
+        help: false,
+        build: build_ai,
+        handler: |inv| Box::pin(super::handle_ai_command(
+            inv.ctx, inv.command, inv.data_manager, inv.ai_manager, &inv.assets.lang, inv.locale,
+        )),
+        autocomplete: None,
+        fallback: None,
+    },
+    CommandSpec {
+        key: "language",
+        access: Access::Everyone,
+        help: false,
+        build: build_language,
+        handler: |inv| Box::pin(super::handle_language_command(
+            inv.ctx, inv.command, inv.data_manager, &inv.assets.lang, inv.locale,
+        )),
+        autocomplete: None,
+        fallback: None,
+    },
+];
+
+fn build_purge(lang: &LanguageManager) -> CreateCommand {
+    slash_command(lang, "purge")
+        .add_option(option(lang, CommandOptionType::Integer, &["purge", "amount"])
+            .required(true)
+            .min_int_value(1)
+            .max_int_value(super::purge::MAX_AMOUNT as u64))
+        .add_option(option(lang, CommandOptionType::User, &["purge", "user"]))
+        .add_option(option(lang, CommandOptionType::Boolean, &["purge", "bots"]))
+        .add_option(option(lang, CommandOptionType::String, &["purge", "contains"])
+            .max_length(super::purge::MAX_CONTAINS_LENGTH))
+        .add_option(option(lang, CommandOptionType::String, &["purge", "regex"]).max_length(200))
+        .add_option(option(lang, CommandOptionType::Boolean, &["purge", "attachments"]))
+        .add_option(option(lang, CommandOptionType::String, &["purge", "before"]))
+        .add_option(option(lang, CommandOptionType::String, &["purge", "after"]))
+        .add_option(option(lang, CommandOptionType::Boolean, &["purge", "old"]))
+}
+
+fn build_reminder(lang: &LanguageManager) -> CreateCommand {
+    let choices = |option, name: &str, values: &[&str]| {
+        values.iter().fold(option, |option, value| choice(lang, option, &["reminder", name], value))
+    };
+    slash_command(lang, "reminder")
+        .add_option(option(lang, CommandOptionType::String, &["reminder", "time"]).required(true))
+        .add_option(option(lang, CommandOptionType::String, &["reminder", "message"]).required(true))
+        .add_option(choices(
+            option(lang, CommandOptionType::String, &["reminder", "visibility"]).required(false),
+            "visibility",
+            &["public", "private"],
+        ))
+        .add_option(choices(
+            option(lang, CommandOptionType::String, &["reminder", "mention_type"]).required(false),
+            "mention_type",
+            &["none", "creator", "everyone"],
+        ))
+        .add_option(option(lang, CommandOptionType::Boolean, &["reminder", "has_status"]).required(false))
+}
+
+fn build_commission(lang: &LanguageManager) -> CreateCommand {
+    let status_option = CommissionStatus::ALL.iter().fold(
+        option(lang, CommandOptionType::String, &["commission", "status", "status"]).required(true),
+        |option, status| labelled_choice(lang, option, status.as_str(), |locale| {
+            lang.commission_status_label(locale, *status).to_string()
+        }),
+    );
+    let availability_option = CommissionAvailability::ALL.iter().fold(
+        option(lang, CommandOptionType::String, &["commission", "availability", "state"]).required(true),
+        |option, availability| labelled_choice(lang, option, availability.as_str(), |locale| {
+            lang.commission_availability_label(locale, *availability).to_string()
+        }),
+    );
+    slash_command(lang, "commission")
+        .add_option(option(lang, CommandOptionType::SubCommand, &["commission", "quote"])
+            .add_sub_option(option(lang, CommandOptionType::String, &["commission", "quote", "amount"]).required(true))
+            .add_sub_option(option(lang, CommandOptionType::String, &["commission", "quote", "details"]).required(false)))
+        .add_option(option(lang, CommandOptionType::SubCommand, &["commission", "milestone"])
+            .add_sub_option(option(lang, CommandOptionType::String, &["commission", "milestone", "title"]).required(true))
+            .add_sub_option(option(lang, CommandOptionType::String, &["commission", "milestone", "due"]).required(false)))
+        .add_option(option(lang, CommandOptionType::SubCommand, &["commission", "complete"])
+            .add_sub_option(option(lang, CommandOptionType::Integer, &["commission", "complete", "number"])
+                .required(true)
+                .min_int_value(1)))
+        .add_option(option(lang, CommandOptionType::SubCommand, &["commission", "status"])
+            .add_sub_option(status_option))
+        .add_option(option(lang, CommandOptionType::SubCommand, &["commission", "availability"])
+            .add_sub_option(availability_option))
+        .add_option(option(lang, CommandOptionType::SubCommand, &["commission", "queue"]))
+}
+
+fn build_ai(lang: &LanguageManager) -> CreateCommand {
+    slash_command(lang, "ai")
+        .add_option(option(lang, CommandOptionType::SubCommandGroup, &["ai", "memory"])
+            .add_sub_option(option(lang, CommandOptionType::SubCommand, &["ai", "memory", "show"]))
+            .add_sub_option(option(lang, CommandOptionType::SubCommand, &["ai", "memory", "export"]))
+            .add_sub_option(option(lang, CommandOptionType::SubCommand, &["ai", "memory", "forget"]))
+            .add_sub_option(option(lang, CommandOptionType::SubCommand, &["ai", "memory", "opt-out"])
+                .add_sub_option(option(lang, CommandOptionType::Boolean, &["ai", "memory", "opt-out", "enabled"]).required(true))))
+        .add_option(option(lang, CommandOptionType::SubCommand, &["ai", "usage"]))
+        .add_option(option(lang, CommandOptionType::SubCommand, &["ai", "quota"])
+            .add_sub_option(option(lang, CommandOptionType::Integer, &["ai", "quota", "daily"])
+                .required(true)
+                .min_int_value(0))
+            .add_sub_option(option(lang, CommandOptionType::Role, &["ai", "quota", "role"]).required(false)))
+        .add_option(option(lang, CommandOptionType::SubCommand, &["ai", "persona"])
+            .add_sub_option(option(lang, CommandOptionType::String, &["ai", "persona", "name"]).required(false))
+            .add_sub_option(option(lang, CommandOptionType::Channel, &["ai", "persona", "channel"]).required(false)))
+        .add_option(option(lang, CommandOptionType::SubCommand, &["ai", "flagged"]))
+        .add_option(option(lang, CommandOptionType::SubCommandGroup, &["ai", "replies"])
+            .add_sub_option(option(lang, CommandOptionType::SubCommand, &["ai", "replies", "allow"])
+                .add_sub_option(option(lang, CommandOptionType::Channel, &["ai", "replies", "allow", "channel"]).required(true)))
+            .add_sub_option(option(lang, CommandOptionType::SubCommand, &["ai", "replies", "disallow"])
+                .add_sub_option(option(lang, CommandOptionType::Channel, &["ai", "replies", "disallow", "channel"]).required(true)))
+            .add_sub_option(option(lang, CommandOptionType::SubCommand, &["ai", "replies", "list"])))
+        .add_option(option(lang, CommandOptionType::SubCommand, &["ai", "dms"])
+            .add_sub_option(option(lang, CommandOptionType::Boolean, &["ai", "dms", "enabled"]).required(true)))
+}
+
+fn build_capability(lang: &LanguageManager) -> CreateCommand {
+    let subcommand = |name: &str| {
+        let capability = Capability::ALL
+            .iter()
+            .filter(|capability| capability.is_delegable())
+            .fold(
+                option(lang, CommandOptionType::String, &["capability", name, "capability"]).required(true),
+                |option, capability| choice(lang, option, &["capability", name, "capability"], capability.as_str()),
+            );
+        option(lang, CommandOptionType::SubCommand, &["capability", name])
+            .add_sub_option(capability)
+            .add_sub_option(option(lang, CommandOptionType::Role, &["capability", name, "role"]).required(false))
+            .add_sub_option(option(lang, CommandOptionType::User, &["capability", name, "user"]).required(false))
+    };
+    slash_command(lang, "capability")
+        .add_option(subcommand("grant"))
+        .add_option(subcommand("revoke"))
+        .add_option(option(lang, CommandOptionType::SubCommand, &["capability", "list"]))
+}
+
+fn build_language(lang: &LanguageManager) -> CreateCommand {
+    // Language names stay in their own language; only "automatic" is translated
+    let language_option = |subcommand: &str| {
+        let option = option(lang, CommandOptionType::String, &["language", subcommand, "language"])
+            .required(true);
+        let option = labelled_choice(lang, option, super::LANGUAGE_AUTO, |locale| {
+            lang.get(locale).language.auto.clone()
+        });
+        lang.locales().fold(option, |option, (locale, messages)| option.add_string_choice(&messages.language.name, locale))
+    };
+    slash_command(lang, "language")
+        .add_option(option(lang, CommandOptionType::SubCommand, &["language", "me"])
+            .add_sub_option(language_option("me")))
+        .add_option(option(lang, CommandOptionType::SubCommand, &["language", "server"])
+            .add_sub_option(language_option("server")))
+}
+
+/// Every command, localized into the loaded languages and hidden from
+/// members who can't use it
+pub fn all_commands(lang: &LanguageManager) -> Vec<CreateCommand> {
+    COMMANDS
+        .iter()
+        .map(|spec| {
+            let command = (spec.build)(lang);
+            match spec.access.default_permissions() {
+                Some(permissions) => command.default_member_permissions(permissions),
+                None => command,
+            }
+        })
+        .collect()
+}
+
+/// The command Discord named in an interaction; it always sends the English name
+pub fn find(lang: &LanguageManager, name: &str) -> Option<&'static CommandSpec> {
+    COMMANDS.iter().find(|spec| {
+        lang.command_info(DEFAULT_LOCALE, &[spec.key])
+            .map_or(spec.key == name, |info| info.name == name)
+    })
+}
+
+/// Commands listed in the /help menu that the caller may use
+pub fn help_entries<'a>(data_manager: &'a DataManager, caller: &'a Caller<'a>) -> impl Iterator<Item = &'static CommandSpec> + 'a {
+    COMMANDS
+        .iter()
+        .filter(move |spec| spec.help && spec.access.allows(data_manager, caller))
+}
+
+/// Run the handler for a slash or context menu command
+pub async fn dispatch(invocation: &Invocation<'_>) {
+    let lang = &invocation.assets.lang;
+    let messages = lang.get(invocation.locale);
+    let Some(spec) = find(lang, &invocation.command.data.name) else {
+        let data = CreateInteractionResponseMessage::new().content(&messages.responses.unknown_command);
+        let _ = invocation.command.create_response(&invocation.ctx.http, CreateInteractionResponse::Message(data)).await;
+        return;
+    };
+
+    // Access is checked here only; handlers assume the caller may use the command
+    if !spec.access.allows(invocation.data_manager, &Caller::of_command(invocation.command)) {
+        let data = CreateInteractionResponseMessage::new()
+            .content(spec.access.denial(lang, invocation.locale))
+            .ephemeral(true);
+        let _ = invocation.command.create_response(&invocation.ctx.http, CreateInteractionResponse::Message(data)).await;
+        return;
+    }
+
+    if let Err(e) = (spec.handler)(invocation).await {
+        eprintln!("Error handling {} command: {}", spec.key, e);
+        if let Some(fallback) = spec.fallback {
+            let data = CreateInteractionResponseMessage::new().content(fallback(messages));
+            let _ = invocation.command.create_response(&invocation.ctx.http, CreateInteractionResponse::Message(data)).await;
+        }
+    }
+}
+
+/// Answer an autocomplete request with the command's suggestions
+pub async fn autocomplete(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    data_manager: &DataManager,
+    lang: &LanguageManager,
+    locale: &str,
+) -> CommandResult {
+    let Some(suggest) = find(lang, &interaction.data.name).and_then(|spec| spec.autocomplete) else {
+        return Ok(());
+    };
+    let choices = suggest(data_manager, lang, locale, interaction)
+        .into_iter()
+        .take(MAX_CHOICES)
+        .map(|(label, value)| AutocompleteChoice::new(label, value))
+        .collect();
+    let response = CreateAutocompleteResponse::new().set_choices(choices);
+    interaction
+        .create_response(&ctx.http, CreateInteractionResponse::Autocomplete(response))
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn every_command_has_unique_lang_entries() {
+        let lang = LanguageManager::new().unwrap();
+        let mut names = HashSet::new();
+        for spec in COMMANDS {
+            let info = lang.command_info(DEFAULT_LOCALE, &[spec.key])
+                .unwrap_or_else(|| panic!("[commands.{}] missing from en.toml", spec.key));
+            assert!(names.insert(info.name.clone()), "two commands are named {}", info.name);
+            assert_eq!(find(&lang, &info.name).map(|found| found.key), Some(spec.key));
+        }
+        assert!(find(&lang, "nonexistent").is_none());
+    }
+
+    #[test]
+    fn help_pages_match_the_listed_commands() {
+        let lang = LanguageManager::new().unwrap();
+        let pages = &lang.get(DEFAULT_LOCALE).embeds.help.commands;
+        let listed: HashSet<&str> = COMMANDS.iter().filter(|spec| spec.help).map(|spec| spec.key).collect();
+        let documented: HashSet<&str> = pages.keys().map(String::as_str).collect();
+        assert_eq!(listed, documented);
+    }
+
+    #[test]
+    fn only_everyone_commands_are_visible_by_default() {
+        let lang = LanguageManager::new().unwrap();
+        let commands = serde_json::to_value(all_commands(&lang)).unwrap();
+        for (spec, command) in COMMANDS.iter().zip(commands.as_array().unwrap()) {
+            let permissions = command.get("default_member_permissions").and_then(|value| value.as_str());
+            match spec.access {
+                Access::Everyone => assert_eq!(permissions, None, "{} is hidden", spec.key),
+                Access::Owner | Access::Capability(Capability::UpdateBot | Capability::ConfigureAi) => {
+                    assert_eq!(permissions, Some("0"), "{} is visible", spec.key)
+                }
+                Access::Capability(capability) => assert_eq!(
+                    permissions,
+                    Some(capability.default_permissions().bits().to_string().as_str()),
+                    "{} is visible to everyone",
+                    spec.key
+                ),
+                Access::Permission(_) => assert!(permissions.is_some(), "{} is visible to everyone", spec.key),
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+//! /reload command handler
+
+use serenity::all::{CommandDataOption, CommandDataOptionValue, CommandInteraction, Context};
+use crate::data::DataManager;
+use crate::lang::assets::AssetLibrary;
+use super::sync;
+use super::respond_ephemeral;
+
+/// Longest error text shown, keeping the reply under Discord's 2000 characters
+const MAX_ERROR_LENGTH: usize = 1800;
+
+/// Handle /reload assets (owner only)
+pub async fn handle_reload_command(
+    ctx: &Context,
+    command: &CommandInteraction,
+    assets: &AssetLibrary,
+    data_manager: &DataManager,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let Some(CommandDataOption { name, value: CommandDataOptionValue::SubCommand(_), .. }) = command.data.options.first() else {
+        return respond_ephemeral(ctx, command, "❌ Missing subcommand.").await;
+    };
+    if name != "assets" {
+        return respond_ephemeral(ctx, command, "❌ Unknown subcommand.").await;
+    }
+
+    match assets.reload() {
+        Ok(report) => {
+            respond_ephemeral(ctx, command, &format!(
+                "✅ Reloaded assets from `{}`: {} files from disk, {} languages, {} lint warnings.",
+                assets.dir().display(),
+                report.from_disk,
+                report.locales,
+                report.warnings,
+            )).await?;
+            // Command names and descriptions come from the lang files too
+            sync::sync_commands(&ctx.http, &assets.current().lang, data_manager).await;
+            Ok(())
+        }
+        Err(e) => {
+            let mut errors: String = e.chars().take(MAX_ERROR_LENGTH).collect();
+            if errors.len() < e.len() {
+                errors.push('…');
+            }
+            respond_ephemeral(ctx, command, &format!(
+                "❌ Reload failed, the current assets stay in use:\n```\n{}\n```",
+                errors,
+            )).await
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Keeping the commands registered with Discord in step with the registry
+//!
+//! Global commands take a while to reach every client, so dev and test bots
+//! can register into specific guilds instead (`COMMAND_GUILD_IDS`), where
+//! changes show up at once. A scope is only overwritten when its commands
+//! changed or no longer match what Discord has; overwriting also removes
+//! commands that left the registry.
+
+use std::collections::HashSet;
+use std::fmt;
+use serenity::all::{Command, CreateCommand, GuildId, Http};
+use sha2::{Digest, Sha256};
+use crate::data::DataManager;
+use crate::lang::LanguageManager;
+use super::registry;
+
+const GLOBAL_SCOPE: &str = "global";
+
+/// Guilds to register commands in instead of globally (`COMMAND_GUILD_IDS`, comma separated)
+pub fn command_guilds() -> Vec<GuildId> {
+    std::env::var("COMMAND_GUILD_IDS")
+        .map(|ids| {
+            ids.split(',')
+                .filter_map(|id| id.trim().parse::<u64>().ok())
+                .filter(|id| *id != 0)
+                .map(GuildId::new)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Scope {
+    Global,
+    Guild(GuildId),
+}
+
+impl Scope {
+    fn key(self) -> String {
+        match self {
+            Scope::Global => GLOBAL_SCOPE.to_string(),
+            Scope::Guild(guild_id) => guild_id.to_string(),
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        match key {
+            GLOBAL_SCOPE => Some(Scope::Global),
+            id => id.parse::<u64>().ok().filter(|id| *id != 0).map(|id| Scope::Guild(GuildId::new(id))),
+        }
+    }
+
+    async fn registered(self, http: &Http) -> serenity::Result<Vec<Command>> {
+        match self {
+            Scope::Global => Command::get_global_commands(http).await,
+            Scope::Guild(guild_id) => guild_id.get_commands(http).await,
+        }
+    }
+
+    async fn overwrite(self, http: &Http, commands: Vec<CreateCommand>) -> serenity::Result<Vec<Command>> {
+        match self {
+            Scope::Global => Command::set_global_commands(http, commands).await,
+            Scope::Guild(guild_id) => guild_id.set_commands(http, commands).await,
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scope::Global => write!(f, "globally"),
+            Scope::Guild(guild_id) => write!(f, "in guild {}", guild_id),
+        }
+    }
+}
+
+/// Stable hash of a command set; JSON objects serialize with sorted keys
+fn fingerprint(commands: &[CreateCommand]) -> String {
+    let json = serde_json::to_value(commands).map(|value| value.to_string()).unwrap_or_default();
+    format!("{:x}", Sha256::digest(json.as_bytes()))
+}
+
+fn names(commands: &[CreateCommand]) -> HashSet<String> {
+    serde_json::to_value(commands)
+        .ok()
+        .and_then(|value| value.as_array().cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|command| command["name"].as_str().map(str::to_string))
+        .collect()
+}
+
+/// Overwrite a scope when its commands changed; returns whether it was overwritten
+async fn sync_scope(
+    http: &Http,
+    data_manager: &DataManager,
+    scope: Scope,
+    commands: Vec<CreateCommand>,
+) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+    let fingerprint = (!commands.is_empty()).then(|| fingerprint(&commands));
+    let registered = match scope.registered(http).await {
+        Ok(registered) => Some(registered.into_iter().map(|command| command.name).collect::<HashSet<_>>()),
+        Err(e) => {
+            eprintln!("⚠️  Could not fetch the commands registered {}: {}", scope, e);
+            None
+        }
+    };
+    let unchanged = data_manager.get_command_fingerprint(&scope.key()) == fingerprint
+        && registered.is_some_and(|registered| registered == names(&commands));
+    if unchanged {
+        return Ok(false);
+    }
+
+    scope.overwrite(http, commands).await?;
+    data_manager
+        .set_command_fingerprint(&scope.key(), fingerprint)
+        .map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+/// Register the registry's commands where they belong and clear them elsewhere
+pub async fn sync_commands(http: &Http, lang: &LanguageManager, data_manager: &DataManager) {
+    let commands = registry::all_commands(lang);
+    let guilds = command_guilds();
+
+    let mut scopes: Vec<(Scope, Vec<CreateCommand>)> = if guilds.is_empty() {
+        vec![(Scope::Global, commands.clone())]
+    } else {
+        // Global copies would show up twice next to the guild ones
+        guilds
+            .iter()
+            .map(|guild_id| (Scope::Guild(*guild_id), commands.clone()))
+            .chain([(Scope::Global, Vec::new())])
+            .collect()
+    };
+    // Guilds registered into before that are no longer configured
+    for scope in data_manager.get_command_scopes().iter().filter_map(|key| Scope::from_key(key)) {
+        if !scopes.iter().any(|(configured, _)| *configured == scope) {
+            scopes.push((scope, Vec::new()));
+        }
+    }
+
+    for (scope, commands) in scopes {
+        let count = commands.len();
+        match sync_scope(http, data_manager, scope, commands).await {
+            Ok(true) if count == 0 => println!("🧹 Removed stale commands registered {}", scope),
+            Ok(true) => println!("✅ Registered {} commands {}", count, scope),
+            Ok(false) => {}
+            Err(e) => eprintln!("❌ Failed to register commands {}: {}", scope, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprints_are_stable_and_follow_changes() {
+        let lang = LanguageManager::new().unwrap();
+        let commands = registry::all_commands(&lang);
+        assert_eq!(fingerprint(&commands), fingerprint(&registry::all_commands(&lang)));
+        assert_ne!(fingerprint(&commands), fingerprint(&commands[1..]));
+        assert!(names(&commands).contains("ping"));
+        assert_eq!(names(&commands).len(), commands.len());
+    }
+
+    #[test]
+    fn scopes_round_trip_through_their_keys() {
+        for scope in [Scope::Global, Scope::Guild(GuildId::new(42))] {
+            assert_eq!(Scope::from_key(&scope.key()), Some(scope));
+        }
+        assert_eq!(Scope::from_key("0"), None);
+        assert_eq!(Scope::from_key("abc"), None);
+    }
+}
@@ -6,6 +6,7 @@ use serenity::all::{
     Permissions, CreateMessage, ChannelId
 };
 use serenity::model::prelude::*;
+use crate::auth::{self, Caller, Capability};
 use crate::data::{DataManager, ButtonMessageData};
 use crate::data::message_data::{MessageType, ButtonAction};
 use crate::lang::{LanguageManager, ImageManager, EmojiManager};
@@ -23,33 +24,6 @@ pub async fn handle_ticket_setup_command(
     images: &ImageManager,
     emojis: &EmojiManager,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    // Check if user has administrator permission
-    if let Some(guild_id) = command.guild_id {
-        if let Ok(member) = guild_id.member(&ctx.http, command.user.id).await {
-            let has_permission = {
-                if let Some(guild) = ctx.cache.guild(guild_id) {
-                    let permissions = guild.member_permissions(&member);
-                    permissions.contains(Permissions::ADMINISTRATOR)
-                } else {
-                    false
-                }
-            };
-            
-            if !has_permission {
-                let embed = CreateEmbed::new()
-                    .title("🎫 Ticket System Setup")
-                    .description("You don't have permission to setup ticket messages. Only administrators can use this command.")
-                    .color(Color::RED);
-                
-                let response = CreateInteractionResponse::Message(
-                    CreateInteractionResponseMessage::new().embed(embed)
-                );
-                command.create_response(&ctx.http, response).await?;
-                return Ok(());
-            }
-        }
-    }
-
     // Get the ticket channel
     let ticket_channel_id = ChannelId::new(TICKET_CHANNEL_ID.parse::<u64>()?);
     
@@ -146,6 +120,53 @@ pub async fn handle_ticket_create(
         return Ok(());
     }
 
+    match create_ticket_channel(ctx, guild_id, &component.user, data_manager, images).await {
+        Ok(ticket_channel) => {
+            // Respond to the component interaction
+            let success_embed = CreateEmbed::new()
+                .title("✅ Ticket Created")
+                .description(&format!("Your ticket has been created: <#{}>", ticket_channel.id))
+                .color(Color::from_rgb(0, 255, 127));
+
+            let response = CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .embed(success_embed)
+                    .ephemeral(true)
+            );
+            component.create_response(&ctx.http, response).await?;
+
+            println!("✅ Created ticket channel {} for user {}", ticket_channel.name, component.user.name);
+        }
+        Err(e) => {
+            let error_embed = CreateEmbed::new()
+                .title("❌ Ticket Creation Failed")
+                .description(&format!("Failed to create ticket channel: {}", e))
+                .color(Color::RED);
+
+            let response = CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .embed(error_embed)
+                    .ephemeral(true)
+            );
+            component.create_response(&ctx.http, response).await?;
+
+            eprintln!("❌ Failed to create ticket channel for user {}: {}", component.user.name, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Create a private ticket channel for `user` with its welcome message and close button
+pub async fn create_ticket_channel(
+    ctx: &Context,
+    guild_id: GuildId,
+    user: &User,
+    data_manager: &DataManager,
+    images: &ImageManager,
+) -> Result<GuildChannel, serenity::Error> {
+    let user_id = user.id;
+
     // Generate ticket ID
     let ticket_id = format!("ticket-{}-{}", user_id, Uuid::new_v4().to_string()[..8].to_lowercase());
 
@@ -184,103 +205,84 @@ pub async fn handle_ticket_create(
         kind: PermissionOverwriteType::Member(UserId::new(crate::config::OWNER_ID)),
     });
 
+    // Add ticket staff permissions
+    let staff = data_manager.get_capability_grants(&guild_id.to_string(), Capability::ManageTickets.as_str());
+    let staff_kinds = staff.roles.iter().map(|id| PermissionOverwriteType::Role(RoleId::new(*id)))
+        .chain(staff.users.iter().map(|id| PermissionOverwriteType::Member(UserId::new(*id))));
+    permission_overwrites.extend(staff_kinds.map(|kind| PermissionOverwrite {
+        allow: Permissions::VIEW_CHANNEL
+            | Permissions::SEND_MESSAGES
+            | Permissions::READ_MESSAGE_HISTORY
+            | Permissions::MANAGE_MESSAGES,
+        deny: Permissions::empty(),
+        kind,
+    }));
+
     // Create the ticket channel
     let channel_builder = CreateChannel::new(&ticket_id)
         .kind(ChannelType::Text)
-        .topic(&format!("Support ticket for {}", component.user.name))
+        .topic(&format!("Support ticket for {}", user.name))
         .permissions(permission_overwrites);
 
-    match guild_id.create_channel(&ctx.http, channel_builder).await {
-        Ok(ticket_channel) => {
-            // Create welcome message in the ticket channel
-            let thumbnail_url = images.get_image("reactions", "happy")
-                .or_else(|| images.get_default_image("success"))
-                .unwrap_or(&"https://cdn.discordapp.com/embed/avatars/0.png".to_string())
-                .clone();
-
-            let welcome_embed = CreateEmbed::new()
-                .title("🎫 Ticket Created Successfully")
-                .description(&format!(
-                    "Welcome to your support ticket, {}! Please describe your issue or question in detail. Our team has been notified and will respond shortly.\n\n<@{}>",
-                    component.user.name,
-                    crate::config::OWNER_ID
-                ))
-                .color(Color::from_rgb(138, 43, 226))
-                .thumbnail(thumbnail_url)
-                .field("👤 Ticket Creator", format!("<@{}>", user_id), true)
-                .field("🆔 Ticket ID", &ticket_id, true)
-                .field("🕒 Created", format!("<t:{}:F>", Utc::now().timestamp()), true)
-                .footer(CreateEmbedFooter::new(&format!("Support Ticket • {}", &ticket_id[..16])))
-                .timestamp(Utc::now());
-
-            // Create close button
-            let close_button = CreateButton::new(&format!("ticket_close_{}", ticket_id))
-                .label("🗑️ Close Ticket")
-                .style(ButtonStyle::Danger);
-
-            let action_row = CreateActionRow::Buttons(vec![close_button]);
+    let ticket_channel = guild_id.create_channel(&ctx.http, channel_builder).await?;
 
-            let welcome_message = CreateMessage::new()
-                .embed(welcome_embed)
-                .components(vec![action_row]);
+    // Create welcome message in the ticket channel
+    let thumbnail_url = images.get_image("reactions", "happy")
+        .or_else(|| images.get_default_image("success"))
+        .unwrap_or(&"https://cdn.discordapp.com/embed/avatars/0.png".to_string())
+        .clone();
 
-            let sent_message = ticket_channel.send_message(&ctx.http, welcome_message).await?;
+    let welcome_embed = CreateEmbed::new()
+        .title("🎫 Ticket Created Successfully")
+        .description(&format!(
+            "Welcome to your support ticket, {}! Please describe your issue or question in detail. Our team has been notified and will respond shortly.\n\n<@{}>",
+            user.name,
+            crate::config::OWNER_ID
+        ))
+        .color(Color::from_rgb(138, 43, 226))
+        .thumbnail(thumbnail_url)
+        .field("👤 Ticket Creator", format!("<@{}>", user_id), true)
+        .field("🆔 Ticket ID", &ticket_id, true)
+        .field("🕒 Created", format!("<t:{}:F>", Utc::now().timestamp()), true)
+        .footer(CreateEmbedFooter::new(&format!("Support Ticket • {}", &ticket_id[..16])))
+        .timestamp(Utc::now());
 
-            // Store ticket button data
-            let mut ticket_button_data = ButtonMessageData::new(
-                sent_message.id.to_string(),
-                ticket_channel.id.to_string(),
-                MessageType::Ticket,
-            );
+    // Create close button
+    let close_button = CreateButton::new(&format!("ticket_close_{}", ticket_id))
+        .label("🗑️ Close Ticket")
+        .style(ButtonStyle::Danger);
 
-            ticket_button_data.add_button_action(
-                format!("ticket_close_{}", ticket_id),
-                ButtonAction::CloseTicket {
-                    ticket_channel_id: ticket_channel.id.to_string(),
-                    creator_id: user_id.to_string(),
-                },
-            );
+    let action_row = CreateActionRow::Buttons(vec![close_button]);
 
-            ticket_button_data.add_metadata("ticket_id".to_string(), ticket_id.clone());
-            ticket_button_data.add_metadata("creator_id".to_string(), user_id.to_string());
+    let welcome_message = CreateMessage::new()
+        .embed(welcome_embed)
+        .components(vec![action_row]);
 
-            if let Err(e) = data_manager.add_button_message(sent_message.id.to_string(), ticket_button_data) {
-                eprintln!("Error storing ticket button message data: {}", e);
-            }
+    let sent_message = ticket_channel.send_message(&ctx.http, welcome_message).await?;
 
-            // Respond to the component interaction
-            let success_embed = CreateEmbed::new()
-                .title("✅ Ticket Created")
-                .description(&format!("Your ticket has been created: <#{}>", ticket_channel.id))
-                .color(Color::from_rgb(0, 255, 127));
-
-            let response = CreateInteractionResponse::Message(
-                CreateInteractionResponseMessage::new()
-                    .embed(success_embed)
-                    .ephemeral(true)
-            );
-            component.create_response(&ctx.http, response).await?;
+    // Store ticket button data
+    let mut ticket_button_data = ButtonMessageData::new(
+        sent_message.id.to_string(),
+        ticket_channel.id.to_string(),
+        MessageType::Ticket,
+    );
 
-            println!("✅ Created ticket channel {} for user {}", ticket_id, component.user.name);
-        }
-        Err(e) => {
-            let error_embed = CreateEmbed::new()
-                .title("❌ Ticket Creation Failed")
-                .description(&format!("Failed to create ticket channel: {}", e))
-                .color(Color::RED);
+    ticket_button_data.add_button_action(
+        format!("ticket_close_{}", ticket_id),
+        ButtonAction::CloseTicket {
+            ticket_channel_id: ticket_channel.id.to_string(),
+            creator_id: user_id.to_string(),
+        },
+    );
 
-            let response = CreateInteractionResponse::Message(
-                CreateInteractionResponseMessage::new()
-                    .embed(error_embed)
-                    .ephemeral(true)
-            );
-            component.create_response(&ctx.http, response).await?;
+    ticket_button_data.add_metadata("ticket_id".to_string(), ticket_id.clone());
+    ticket_button_data.add_metadata("creator_id".to_string(), user_id.to_string());
 
-            eprintln!("❌ Failed to create ticket channel for user {}: {}", component.user.name, e);
-        }
+    if let Err(e) = data_manager.add_button_message(sent_message.id.to_string(), ticket_button_data) {
+        eprintln!("Error storing ticket button message data: {}", e);
     }
 
-    Ok(())
+    Ok(ticket_channel)
 }
 
 /// Handle ticket closing when button is clicked
@@ -300,11 +302,11 @@ pub async fn handle_ticket_close(
         if let Some(creator_id) = button_data.get_metadata("creator_id") {
             let creator_id = creator_id.parse::<u64>()?;
 
-            // Check if user has permission to close (creator or owner)
-            if user_id.get() != creator_id && user_id.get() != crate::config::OWNER_ID {
+            // Check if user has permission to close (creator or ticket staff)
+            if user_id.get() != creator_id && !auth::allowed(data_manager, &Caller::of_component(component), Capability::ManageTickets) {
                 let embed = CreateEmbed::new()
                     .title("❌ Permission Denied")
-                    .description("You don't have permission to close this ticket. Only the ticket creator or server owner can close tickets.")
+                    .description("You don't have permission to close this ticket. Only the ticket creator or ticket staff can close tickets.")
                     .color(Color::RED);
 
                 let response = CreateInteractionResponse::Message(
@@ -377,14 +379,14 @@ pub async fn handle_ticket_close_command(
         return Ok(());
     }
 
-    // Check permissions (creator or owner)
-    let has_permission = user_id.get() == crate::config::OWNER_ID ||
-        data_manager.is_ticket_creator(&channel_id.to_string(), &user_id.to_string());
+    // Check permissions (creator or ticket staff)
+    let has_permission = data_manager.is_ticket_creator(&channel_id.to_string(), &user_id.to_string()) ||
+        auth::allowed(data_manager, &Caller::of_command(command), Capability::ManageTickets);
 
     if !has_permission {
         let embed = CreateEmbed::new()
             .title("❌ Permission Denied")
-            .description("You don't have permission to close this ticket. Only the ticket creator or server owner can close tickets.")
+            .description("You don't have permission to close this ticket. Only the ticket creator or ticket staff can close tickets.")
             .color(Color::RED);
 
         let response = CreateInteractionResponse::Message(
@@ -6,11 +6,12 @@
 /// The Discord user ID of the bot owner.
 ///
 /// This is the single source of truth for owner authority across the entire bot.
-/// All owner-gated operations (e.g., /update, commission management, ticket management)
-/// must check against this constant.
+/// All owner-gated operations (e.g., /update, commission management) must check
+/// against this constant; the capabilities in `auth` start from it too.
 ///
-/// **Security**: This value defines the trust boundary. Only this user can perform
-/// privileged operations. Changing this value requires a code change and redeploy.
+/// **Security**: This value defines the trust boundary. Only this user can run
+/// /update or delegate the other privileged operations (see `auth::Capability`).
+/// Changing this value requires a code change and redeploy.
 pub const OWNER_ID: u64 = 670_362_326_746_267_678;
 
 /// The GitHub repository for official releases.
@@ -36,6 +37,18 @@ pub const ASSET_BASE_NAME: &str = "lorian-discord-bot";
 /// `https://api.github.com/users/{GITHUB_USER}/events/public`.
 pub const GITHUB_USER: &str = "Solar2004";
 
+/// Minimum time between edits of a streaming AI reply, in milliseconds.
+///
+/// Discord allows roughly five message edits per five seconds in a channel;
+/// streamed chunks arriving faster than this are batched into the next edit.
+pub const AI_STREAM_EDIT_INTERVAL_MS: u64 = 1_500;
+
+/// Knowledge base passages injected into the AI system instruction per message.
+pub const AI_KNOWLEDGE_PASSAGES: usize = 3;
+
+/// Flagged AI exchanges kept for owner review; older ones are dropped.
+pub const AI_MODERATION_LOG_LIMIT: usize = 200;
+
 /// Parsed auto-update configuration.
 ///
 /// Behaviour (precedence — first match wins):
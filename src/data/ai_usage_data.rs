@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Daily AI messages allowed when no quota is configured
+pub const DEFAULT_DAILY_AI_QUOTA: u32 = 50;
+
+fn default_daily_quota() -> u32 {
+    DEFAULT_DAILY_AI_QUOTA
+}
+
+/// Daily AI message quotas; 0 means unlimited
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AIQuotaSettings {
+    /// Quota for users without a role-specific one
+    #[serde(default = "default_daily_quota")]
+    pub default_daily: u32,
+    /// Quotas by role ID
+    #[serde(default)]
+    pub roles: HashMap<String, u32>,
+}
+
+impl Default for AIQuotaSettings {
+    fn default() -> Self {
+        Self {
+            default_daily: DEFAULT_DAILY_AI_QUOTA,
+            roles: HashMap::new(),
+        }
+    }
+}
+
+impl AIQuotaSettings {
+    /// Daily quota for a member with the given roles: the most generous of their
+    /// role quotas, or the default when none of their roles has one
+    pub fn daily_limit(&self, role_ids: &[String]) -> u32 {
+        let role_limits: Vec<u32> = role_ids
+            .iter()
+            .filter_map(|role_id| self.roles.get(role_id).copied())
+            .collect();
+        if role_limits.is_empty() {
+            return self.default_daily;
+        }
+        if role_limits.contains(&0) {
+            return 0;
+        }
+        role_limits.into_iter().max().unwrap_or(self.default_daily)
+    }
+}
+
+/// AI usage of a single user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AIUserUsage {
+    pub user_name: String,
+    /// UTC day `messages_today` counts
+    pub day: NaiveDate,
+    pub messages_today: u32,
+    pub total_messages: u64,
+    pub prompt_tokens: u64,
+    pub response_tokens: u64,
+    pub last_used: DateTime<Utc>,
+}
+
+impl AIUserUsage {
+    /// Messages sent on `day` (0 once the day has rolled over)
+    pub fn messages_on(&self, day: NaiveDate) -> u32 {
+        if self.day == day {
+            self.messages_today
+        } else {
+            0
+        }
+    }
+
+    pub fn total_tokens(&self) -> u64 {
+        self.prompt_tokens + self.response_tokens
+    }
+}
+
+/// Per-user AI usage and quota configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AIUsageData {
+    #[serde(default)]
+    pub quotas: AIQuotaSettings,
+    /// Usage keyed by user ID
+    #[serde(default)]
+    pub users: HashMap<String, AIUserUsage>,
+}
+
+impl AIUsageData {
+    /// Count one AI reply and its tokens for a user
+    pub fn record(&mut self, user_id: String, user_name: String, prompt_tokens: u64, response_tokens: u64, now: DateTime<Utc>) {
+        let today = now.date_naive();
+        let usage = self.record_tokens(user_id, user_name, prompt_tokens, response_tokens, now);
+        usage.messages_today = usage.messages_on(today) + 1;
+        usage.day = today;
+        usage.total_messages += 1;
+    }
+
+    /// Count tokens spent for a user without counting a reply (summaries and other background calls)
+    pub fn record_tokens(&mut self, user_id: String, user_name: String, prompt_tokens: u64, response_tokens: u64, now: DateTime<Utc>) -> &mut AIUserUsage {
+        let today = now.date_naive();
+        let usage = self.users.entry(user_id).or_insert_with(|| AIUserUsage {
+            user_name: user_name.clone(),
+            day: today,
+            messages_today: 0,
+            total_messages: 0,
+            prompt_tokens: 0,
+            response_tokens: 0,
+            last_used: now,
+        });
+
+        usage.user_name = user_name;
+        usage.prompt_tokens += prompt_tokens;
+        usage.response_tokens += response_tokens;
+        usage.last_used = now;
+        usage
+    }
+
+    /// Messages a user sent today
+    pub fn messages_today(&self, user_id: &str, now: DateTime<Utc>) -> u32 {
+        self.users
+            .get(user_id)
+            .map(|usage| usage.messages_on(now.date_naive()))
+            .unwrap_or(0)
+    }
+
+    /// Users with the most tokens used, highest first
+    pub fn top_consumers(&self, limit: usize) -> Vec<(&String, &AIUserUsage)> {
+        let mut users: Vec<_> = self.users.iter().collect();
+        users.sort_by(|a, b| b.1.total_tokens().cmp(&a.1.total_tokens()).then(b.1.total_messages.cmp(&a.1.total_messages)));
+        users.truncate(limit);
+        users
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn role_quota_picks_most_generous() {
+        let mut quotas = AIQuotaSettings::default();
+        quotas.roles.insert("1".to_string(), 100);
+        quotas.roles.insert("2".to_string(), 20);
+        assert_eq!(quotas.daily_limit(&[]), DEFAULT_DAILY_AI_QUOTA);
+        assert_eq!(quotas.daily_limit(&["3".to_string()]), DEFAULT_DAILY_AI_QUOTA);
+        assert_eq!(quotas.daily_limit(&["2".to_string()]), 20);
+        assert_eq!(quotas.daily_limit(&["1".to_string(), "2".to_string()]), 100);
+
+        quotas.roles.insert("4".to_string(), 0);
+        assert_eq!(quotas.daily_limit(&["2".to_string(), "4".to_string()]), 0);
+    }
+
+    #[test]
+    fn daily_count_resets_but_totals_accumulate() {
+        let mut usage = AIUsageData::default();
+        let day_one = Utc.with_ymd_and_hms(2025, 3, 1, 23, 0, 0).unwrap();
+        let day_two = Utc.with_ymd_and_hms(2025, 3, 2, 1, 0, 0).unwrap();
+
+        usage.record("1".to_string(), "a".to_string(), 100, 20, day_one);
+        usage.record("1".to_string(), "a".to_string(), 50, 10, day_one);
+        assert_eq!(usage.messages_today("1", day_one), 2);
+        assert_eq!(usage.messages_today("1", day_two), 0);
+
+        usage.record("1".to_string(), "a".to_string(), 10, 5, day_two);
+        let user = &usage.users["1"];
+        assert_eq!(user.messages_today, 1);
+        assert_eq!(user.total_messages, 3);
+        assert_eq!(user.total_tokens(), 195);
+    }
+
+    #[test]
+    fn background_tokens_do_not_count_as_messages() {
+        let mut usage = AIUsageData::default();
+        let now = Utc::now();
+        usage.record("1".to_string(), "a".to_string(), 100, 20, now);
+        usage.record_tokens("1".to_string(), "a".to_string(), 300, 40, now);
+        usage.record_tokens("2".to_string(), "b".to_string(), 10, 5, now);
+
+        assert_eq!(usage.messages_today("1", now), 1);
+        assert_eq!(usage.users["1"].total_messages, 1);
+        assert_eq!(usage.users["1"].total_tokens(), 460);
+        assert_eq!(usage.messages_today("2", now), 0);
+        assert_eq!(usage.users["2"].total_tokens(), 15);
+    }
+
+    #[test]
+    fn top_consumers_sorted_by_tokens() {
+        let mut usage = AIUsageData::default();
+        let now = Utc::now();
+        usage.record("1".to_string(), "a".to_string(), 10, 10, now);
+        usage.record("2".to_string(), "b".to_string(), 500, 100, now);
+        usage.record("3".to_string(), "c".to_string(), 50, 50, now);
+
+        let top: Vec<&str> = usage.top_consumers(2).into_iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(top, vec!["2", "3"]);
+    }
+}
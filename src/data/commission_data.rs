@@ -0,0 +1,472 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Lifecycle status of a commission
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CommissionStatus {
+    /// Brief submitted, waiting for a quote
+    #[default]
+    Inquiry,
+    /// Quote sent, waiting for the client to answer
+    Quoted,
+    /// Client accepted the quote
+    Accepted,
+    /// Work has started (first milestone planned)
+    InProgress,
+    /// Work delivered to the client
+    Delivered,
+    /// Commission finished or cancelled
+    Closed,
+}
+
+impl CommissionStatus {
+    /// All statuses in lifecycle order
+    pub const ALL: [CommissionStatus; 6] = [
+        CommissionStatus::Inquiry,
+        CommissionStatus::Quoted,
+        CommissionStatus::Accepted,
+        CommissionStatus::InProgress,
+        CommissionStatus::Delivered,
+        CommissionStatus::Closed,
+    ];
+
+    /// Stable identifier used in slash command choices
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CommissionStatus::Inquiry => "inquiry",
+            CommissionStatus::Quoted => "quoted",
+            CommissionStatus::Accepted => "accepted",
+            CommissionStatus::InProgress => "in_progress",
+            CommissionStatus::Delivered => "delivered",
+            CommissionStatus::Closed => "closed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|status| status.as_str() == value)
+    }
+}
+
+/// Whether new commissions are being accepted
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CommissionAvailability {
+    /// New requests open the intake form
+    #[default]
+    Open,
+    /// New requests are queued on the waitlist
+    Waitlist,
+    /// Commissions are closed; clicks still join the waitlist
+    Closed,
+}
+
+impl CommissionAvailability {
+    pub const ALL: [CommissionAvailability; 3] = [
+        CommissionAvailability::Open,
+        CommissionAvailability::Waitlist,
+        CommissionAvailability::Closed,
+    ];
+
+    /// Stable identifier used in slash command choices
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CommissionAvailability::Open => "open",
+            CommissionAvailability::Waitlist => "waitlist",
+            CommissionAvailability::Closed => "closed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|availability| availability.as_str() == value)
+    }
+}
+
+/// A user waiting for commissions to open
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommissionWaitlistEntry {
+    pub user_id: String,
+    pub user_name: String,
+    pub joined_at: DateTime<Utc>,
+}
+
+/// Commission panel, availability and waitlist state
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CommissionBoardData {
+    /// Whether new commissions are being accepted
+    #[serde(default)]
+    pub availability: CommissionAvailability,
+    /// Channel holding the setup panel posted by /commission_setup
+    #[serde(default)]
+    pub panel_channel_id: Option<String>,
+    /// Setup panel message, edited in place when availability changes
+    #[serde(default)]
+    pub panel_message_id: Option<String>,
+    /// Users waiting for a slot, in the order they joined
+    #[serde(default)]
+    pub waitlist: Vec<CommissionWaitlistEntry>,
+}
+
+impl CommissionBoardData {
+    /// Add a user to the waitlist; returns their 1-based position and whether they were newly added
+    pub fn join_waitlist(&mut self, user_id: String, user_name: String) -> (usize, bool) {
+        if let Some(index) = self.waitlist.iter().position(|entry| entry.user_id == user_id) {
+            return (index + 1, false);
+        }
+
+        self.waitlist.push(CommissionWaitlistEntry {
+            user_id,
+            user_name,
+            joined_at: Utc::now(),
+        });
+        (self.waitlist.len(), true)
+    }
+
+    /// Remove a user from the waitlist (e.g. once their commission is opened)
+    pub fn leave_waitlist(&mut self, user_id: &str) -> bool {
+        let before = self.waitlist.len();
+        self.waitlist.retain(|entry| entry.user_id != user_id);
+        self.waitlist.len() != before
+    }
+}
+
+/// Why a lifecycle transition was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommissionTransitionError {
+    /// The commission is not in a status that allows this action
+    InvalidStatus(CommissionStatus),
+    /// No milestone exists with the given number
+    UnknownMilestone,
+    /// The milestone was already marked as completed
+    MilestoneAlreadyCompleted,
+    /// The quote being answered was replaced by a newer one
+    OutdatedQuote,
+}
+
+/// Answers collected by the commission intake modal
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CommissionBrief {
+    /// Kind of project (website, bot, design, ...)
+    pub project_type: String,
+    /// Budget range as written by the client
+    pub budget_range: String,
+    /// Desired deadline as written by the client
+    pub deadline: String,
+    /// Free-form project description
+    pub description: String,
+}
+
+/// Quote sent by the owner for a commission
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommissionQuote {
+    /// Quoted price as written by the owner (e.g. "150 USD")
+    pub amount: String,
+    /// Scope, payment terms or other notes
+    #[serde(default)]
+    pub details: String,
+    /// When the quote was sent
+    pub quoted_at: DateTime<Utc>,
+    /// Sequence number within the commission, carried by the quote's buttons
+    #[serde(default)]
+    pub number: u32,
+    /// Message with the quote and its Accept / Decline buttons
+    #[serde(default)]
+    pub message_id: Option<String>,
+}
+
+/// A planned piece of work within a commission
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommissionMilestone {
+    pub title: String,
+    #[serde(default)]
+    pub due_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl CommissionMilestone {
+    pub fn is_completed(&self) -> bool {
+        self.completed_at.is_some()
+    }
+}
+
+/// A commission opened through the intake form
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Commission {
+    /// Short commission identifier shown in embeds
+    pub id: String,
+    /// Private channel created for this commission
+    pub channel_id: String,
+    /// Discord ID of the client who requested the commission
+    pub client_id: String,
+    /// Display name of the client at creation time
+    pub client_name: String,
+    /// Structured brief submitted through the intake modal
+    pub brief: CommissionBrief,
+    /// Current lifecycle status
+    #[serde(default)]
+    pub status: CommissionStatus,
+    /// Latest quote sent to the client
+    #[serde(default)]
+    pub quote: Option<CommissionQuote>,
+    /// Quotes sent so far, numbering the next one
+    #[serde(default)]
+    pub quotes_sent: u32,
+    /// Planned milestones, in the order they were added
+    #[serde(default)]
+    pub milestones: Vec<CommissionMilestone>,
+    /// Pinned progress embed in the commission channel
+    #[serde(default)]
+    pub progress_message_id: Option<String>,
+    /// When the commission was requested
+    pub created_at: DateTime<Utc>,
+}
+
+impl Commission {
+    pub fn new(
+        id: String,
+        channel_id: String,
+        client_id: String,
+        client_name: String,
+        brief: CommissionBrief,
+    ) -> Self {
+        Self {
+            id,
+            channel_id,
+            client_id,
+            client_name,
+            brief,
+            status: CommissionStatus::Inquiry,
+            quote: None,
+            quotes_sent: 0,
+            milestones: Vec::new(),
+            progress_message_id: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Record a quote; re-quoting is allowed until the client accepts
+    pub fn send_quote(&mut self, amount: String, details: String) -> Result<(), CommissionTransitionError> {
+        match self.status {
+            CommissionStatus::Inquiry | CommissionStatus::Quoted => {
+                self.quotes_sent += 1;
+                self.quote = Some(CommissionQuote {
+                    amount,
+                    details,
+                    quoted_at: Utc::now(),
+                    number: self.quotes_sent,
+                    message_id: None,
+                });
+                self.status = CommissionStatus::Quoted;
+                Ok(())
+            }
+            status => Err(CommissionTransitionError::InvalidStatus(status)),
+        }
+    }
+
+    /// Client accepted the pending quote (`number` comes from the button pressed)
+    pub fn accept_quote(&mut self, number: u32) -> Result<(), CommissionTransitionError> {
+        match self.status {
+            CommissionStatus::Quoted => {
+                self.check_quote(number)?;
+                self.status = CommissionStatus::Accepted;
+                Ok(())
+            }
+            status => Err(CommissionTransitionError::InvalidStatus(status)),
+        }
+    }
+
+    /// Client declined the pending quote; the commission goes back to inquiry
+    pub fn decline_quote(&mut self, number: u32) -> Result<(), CommissionTransitionError> {
+        match self.status {
+            CommissionStatus::Quoted => {
+                self.check_quote(number)?;
+                self.quote = None;
+                self.status = CommissionStatus::Inquiry;
+                Ok(())
+            }
+            status => Err(CommissionTransitionError::InvalidStatus(status)),
+        }
+    }
+
+    /// Buttons of a replaced quote must not answer the current one
+    fn check_quote(&self, number: u32) -> Result<(), CommissionTransitionError> {
+        match &self.quote {
+            Some(quote) if quote.number == number => Ok(()),
+            _ => Err(CommissionTransitionError::OutdatedQuote),
+        }
+    }
+
+    /// Plan a milestone; the first one after acceptance starts the work
+    pub fn add_milestone(&mut self, title: String, due_date: Option<NaiveDate>) -> Result<(), CommissionTransitionError> {
+        match self.status {
+            CommissionStatus::Delivered | CommissionStatus::Closed => {
+                Err(CommissionTransitionError::InvalidStatus(self.status))
+            }
+            _ => {
+                self.milestones.push(CommissionMilestone {
+                    title,
+                    due_date,
+                    completed_at: None,
+                });
+                if self.status == CommissionStatus::Accepted {
+                    self.status = CommissionStatus::InProgress;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Mark a milestone as completed (`number` is 1-based, as shown in the embed)
+    pub fn complete_milestone(&mut self, number: usize) -> Result<(), CommissionTransitionError> {
+        let milestone = number
+            .checked_sub(1)
+            .and_then(|index| self.milestones.get_mut(index))
+            .ok_or(CommissionTransitionError::UnknownMilestone)?;
+
+        if milestone.is_completed() {
+            return Err(CommissionTransitionError::MilestoneAlreadyCompleted);
+        }
+        milestone.completed_at = Some(Utc::now());
+        Ok(())
+    }
+
+    /// Number of completed milestones and total milestones
+    pub fn progress(&self) -> (usize, usize) {
+        let completed = self.milestones.iter().filter(|m| m.is_completed()).count();
+        (completed, self.milestones.len())
+    }
+
+    /// Whether the commission still needs work (not delivered or closed)
+    pub fn is_active(&self) -> bool {
+        !matches!(self.status, CommissionStatus::Delivered | CommissionStatus::Closed)
+    }
+
+    /// Estimated completion: the latest due date among open milestones
+    pub fn eta(&self) -> Option<NaiveDate> {
+        self.milestones
+            .iter()
+            .filter(|m| !m.is_completed())
+            .filter_map(|m| m.due_date)
+            .max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commission() -> Commission {
+        Commission::new(
+            "abcd1234".to_string(),
+            "1".to_string(),
+            "2".to_string(),
+            "client".to_string(),
+            CommissionBrief {
+                project_type: "Discord bot".to_string(),
+                budget_range: "100-200 USD".to_string(),
+                deadline: "next month".to_string(),
+                description: "A bot".to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn quote_accept_and_first_milestone_start_work() {
+        let mut c = commission();
+        c.send_quote("150 USD".to_string(), String::new()).unwrap();
+        assert_eq!(c.status, CommissionStatus::Quoted);
+        c.accept_quote(1).unwrap();
+        assert_eq!(c.status, CommissionStatus::Accepted);
+        c.add_milestone("Design".to_string(), None).unwrap();
+        assert_eq!(c.status, CommissionStatus::InProgress);
+    }
+
+    #[test]
+    fn decline_returns_to_inquiry_and_clears_quote() {
+        let mut c = commission();
+        c.send_quote("150 USD".to_string(), String::new()).unwrap();
+        c.decline_quote(1).unwrap();
+        assert_eq!(c.status, CommissionStatus::Inquiry);
+        assert!(c.quote.is_none());
+    }
+
+    #[test]
+    fn accept_requires_pending_quote() {
+        let mut c = commission();
+        assert_eq!(
+            c.accept_quote(0),
+            Err(CommissionTransitionError::InvalidStatus(CommissionStatus::Inquiry))
+        );
+    }
+
+    #[test]
+    fn only_the_latest_quote_can_be_answered() {
+        let mut c = commission();
+        c.send_quote("150 USD".to_string(), String::new()).unwrap();
+        c.send_quote("120 USD".to_string(), String::new()).unwrap();
+        assert_eq!(c.accept_quote(1), Err(CommissionTransitionError::OutdatedQuote));
+        assert_eq!(c.decline_quote(1), Err(CommissionTransitionError::OutdatedQuote));
+        assert_eq!(c.status, CommissionStatus::Quoted);
+        c.accept_quote(2).unwrap();
+        assert_eq!(c.quote.unwrap().amount, "120 USD");
+    }
+
+    #[test]
+    fn cannot_requote_after_acceptance() {
+        let mut c = commission();
+        c.send_quote("150 USD".to_string(), String::new()).unwrap();
+        c.accept_quote(1).unwrap();
+        assert!(c.send_quote("300 USD".to_string(), String::new()).is_err());
+    }
+
+    #[test]
+    fn complete_milestone_is_one_based_and_not_repeatable() {
+        let mut c = commission();
+        c.add_milestone("Design".to_string(), None).unwrap();
+        c.add_milestone("Build".to_string(), None).unwrap();
+        assert_eq!(c.complete_milestone(0), Err(CommissionTransitionError::UnknownMilestone));
+        assert_eq!(c.complete_milestone(3), Err(CommissionTransitionError::UnknownMilestone));
+        c.complete_milestone(2).unwrap();
+        assert_eq!(c.progress(), (1, 2));
+        assert_eq!(
+            c.complete_milestone(2),
+            Err(CommissionTransitionError::MilestoneAlreadyCompleted)
+        );
+    }
+
+    #[test]
+    fn eta_uses_latest_open_milestone() {
+        let mut c = commission();
+        assert_eq!(c.eta(), None);
+        let date = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        c.add_milestone("Design".to_string(), Some(date(10))).unwrap();
+        c.add_milestone("Build".to_string(), Some(date(20))).unwrap();
+        c.add_milestone("Polish".to_string(), None).unwrap();
+        assert_eq!(c.eta(), Some(date(20)));
+        c.complete_milestone(2).unwrap();
+        assert_eq!(c.eta(), Some(date(10)));
+    }
+
+    #[test]
+    fn waitlist_keeps_order_and_ignores_duplicates() {
+        let mut board = CommissionBoardData::default();
+        assert_eq!(board.join_waitlist("1".to_string(), "a".to_string()), (1, true));
+        assert_eq!(board.join_waitlist("2".to_string(), "b".to_string()), (2, true));
+        assert_eq!(board.join_waitlist("1".to_string(), "a".to_string()), (1, false));
+        assert!(board.leave_waitlist("1"));
+        assert!(!board.leave_waitlist("1"));
+        assert_eq!(board.join_waitlist("2".to_string(), "b".to_string()), (1, false));
+    }
+
+    #[test]
+    fn status_round_trips_through_str() {
+        for status in CommissionStatus::ALL {
+            assert_eq!(CommissionStatus::parse(status.as_str()), Some(status));
+        }
+        assert_eq!(CommissionStatus::parse("unknown"), None);
+        for availability in CommissionAvailability::ALL {
+            assert_eq!(CommissionAvailability::parse(availability.as_str()), Some(availability));
+        }
+    }
+}
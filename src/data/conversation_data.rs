@@ -1,9 +1,6 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 
-/// Maximum number of messages to keep in context
-pub const MAX_CONTEXT_MESSAGES: usize = 15;
-
 /// Role of the message sender
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum MessageRole {
@@ -57,7 +54,7 @@ impl AIMessage {
 pub struct ConversationContext {
     /// User name this context belongs to
     pub user_name: String,
-    /// List of messages in the conversation (limited to MAX_CONTEXT_MESSAGES)
+    /// List of messages in the conversation; older turns are folded into the running summary
     pub messages: Vec<AIMessage>,
     /// When this context was last updated
     pub last_updated: DateTime<Utc>,
@@ -69,6 +66,9 @@ pub struct ConversationContext {
     /// Counter for messages to trigger summary analysis (resets every 20 messages)
     #[serde(default)]
     pub message_count_for_summary: u32,
+    /// Running summary of older turns removed from `messages`
+    #[serde(default)]
+    pub conversation_summary: String,
 }
 
 impl ConversationContext {
@@ -80,6 +80,7 @@ impl ConversationContext {
             settings: std::collections::HashMap::new(),
             user_summary: String::new(),
             message_count_for_summary: 0,
+            conversation_summary: String::new(),
         }
     }
 
@@ -92,12 +93,6 @@ impl ConversationContext {
     pub fn add_message(&mut self, message: AIMessage) {
         self.messages.push(message);
         self.last_updated = Utc::now();
-
-        // Keep only the last MAX_CONTEXT_MESSAGES
-        if self.messages.len() > MAX_CONTEXT_MESSAGES {
-            let excess = self.messages.len() - MAX_CONTEXT_MESSAGES;
-            self.messages.drain(0..excess);
-        }
     }
 
     /// Get the most recent messages
@@ -188,10 +183,60 @@ impl ConversationContext {
     pub fn has_summary(&self) -> bool {
         !self.user_summary.is_empty()
     }
+
+    /// Check if older turns have been summarized
+    pub fn has_conversation_summary(&self) -> bool {
+        !self.conversation_summary.is_empty()
+    }
+
+    /// Replace the summarized messages with an updated running summary
+    ///
+    /// Messages are matched by timestamp and content, since the context may have
+    /// changed while the summary was written; returns whether anything was folded.
+    pub fn fold_into_summary(&mut self, summarized: &[AIMessage], summary: String) -> bool {
+        let before = self.messages.len();
+        self.messages.retain(|message| {
+            !summarized.iter().any(|folded| folded.timestamp == message.timestamp && folded.content == message.content)
+        });
+        if self.messages.len() == before {
+            // Cleared in the meantime; the summary describes turns that are gone
+            return false;
+        }
+        self.conversation_summary = summary;
+        self.last_updated = Utc::now();
+        true
+    }
 }
 
 impl Default for ConversationContext {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_summarized_messages_are_folded() {
+        let mut context = ConversationContext::new();
+        for text in ["first", "second", "third"] {
+            context.add_message(AIMessage::new(MessageRole::User, text.to_string()));
+        }
+        let summarized = context.get_all_messages()[..2].to_vec();
+
+        // A turn is dropped and another arrives while the summary is written
+        context.messages.remove(0);
+        context.add_message(AIMessage::new(MessageRole::Assistant, "fourth".to_string()));
+
+        assert!(context.fold_into_summary(&summarized, "summary".to_string()));
+        let left: Vec<&str> = context.get_all_messages().iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(left, ["third", "fourth"]);
+        assert_eq!(context.conversation_summary, "summary");
+
+        context.clear_messages();
+        assert!(!context.fold_into_summary(&summarized, "stale".to_string()));
+        assert_eq!(context.conversation_summary, "summary");
+    }
+}
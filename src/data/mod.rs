@@ -1,14 +1,23 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
 
 pub mod persistence;
 pub mod message_data;
 pub mod conversation_data;
+pub mod commission_data;
+pub mod ai_usage_data;
 
 pub use persistence::DataManager;
 pub use message_data::ButtonMessageData;
+use message_data::MessageType;
 pub use conversation_data::{ConversationContext, AIMessage, MessageRole};
+pub use commission_data::{
+    Commission, CommissionAvailability, CommissionBoardData, CommissionBrief, CommissionStatus,
+    CommissionTransitionError,
+};
+pub use ai_usage_data::{AIUsageData, AIUserUsage};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VerificationPending {
@@ -113,6 +122,59 @@ pub struct GithubFeedData {
     pub last_event_id: Option<u64>,
 }
 
+/// Local image uploaded to the image storage channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredImage {
+    pub channel_id: u64,
+    pub message_id: u64,
+    /// Size and modification time of the file when it was uploaded
+    pub fingerprint: String,
+}
+
+/// Roles and users a capability is delegated to in one guild
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CapabilityGrants {
+    #[serde(default)]
+    pub roles: HashSet<u64>,
+    #[serde(default)]
+    pub users: HashSet<u64>,
+}
+
+impl CapabilityGrants {
+    pub fn is_empty(&self) -> bool {
+        self.roles.is_empty() && self.users.is_empty()
+    }
+}
+
+/// AI exchange flagged by moderation, kept for owner review
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AIModerationEntry {
+    pub user_id: String,
+    pub user_name: String,
+    pub channel_id: String,
+    pub stage: String, // "input" or "output"
+    pub action: String, // "refused" or "redacted"
+    pub reason: String,
+    /// The user's message
+    pub message: String,
+    /// The model's reply as generated, if the output was flagged
+    pub response: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AIModerationEntry {
+    /// Replace the message and reply with hashes, for users who opted out of AI memory
+    ///
+    /// The hash still shows when the same text is flagged again.
+    pub fn anonymize(&mut self) {
+        fn hash(text: &str) -> String {
+            format!("[not stored • sha256 {:.12}]", format!("{:x}", Sha256::digest(text.as_bytes())))
+        }
+        self.message = hash(&self.message);
+        self.response = self.response.as_deref().map(hash);
+    }
+}
+
 /// Reminder data structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Reminder {
@@ -163,6 +225,45 @@ pub struct BotData {
     /// Owner-configured AI channel override (None = built-in default)
     #[serde(default)]
     pub ai_channel_id: Option<u64>,
+    /// Commissions opened through the intake form, keyed by channel ID
+    #[serde(default)]
+    pub commissions: HashMap<String, Commission>,
+    /// Commission availability, setup panel location and waitlist
+    #[serde(default)]
+    pub commission_board: CommissionBoardData,
+    /// AI usage per user and daily quotas
+    #[serde(default)]
+    pub ai_usage: AIUsageData,
+    /// Users who opted out of AI memory; their messages are never stored or summarized
+    #[serde(default)]
+    pub ai_memory_opt_outs: HashSet<String>,
+    /// AI persona name by channel ID (channels not listed use the default persona)
+    #[serde(default)]
+    pub ai_personas: HashMap<String, String>,
+    /// Flagged AI exchanges, oldest first
+    #[serde(default)]
+    pub ai_moderation_log: Vec<AIModerationEntry>,
+    /// Channels where the AI answers mentions and replies, by guild ID
+    #[serde(default)]
+    pub ai_reply_channels: HashMap<String, HashSet<u64>>,
+    /// Whether the AI answers direct messages
+    #[serde(default)]
+    pub ai_dm_enabled: bool,
+    /// Language chosen with /language me, by user ID
+    #[serde(default)]
+    pub user_locales: HashMap<String, String>,
+    /// Server default language chosen with /language server, by guild ID
+    #[serde(default)]
+    pub guild_locales: HashMap<String, String>,
+    /// Uploaded copies of local images, by `category.name`
+    #[serde(default)]
+    pub stored_images: HashMap<String, StoredImage>,
+    /// Delegated capabilities, by guild ID then capability name
+    #[serde(default)]
+    pub capability_grants: HashMap<String, HashMap<String, CapabilityGrants>>,
+    /// Fingerprint of the commands last registered, by scope ("global" or a guild ID)
+    #[serde(default)]
+    pub command_fingerprints: HashMap<String, String>,
     /// Last update timestamp
     pub last_updated: DateTime<Utc>,
 }
@@ -177,6 +278,19 @@ impl BotData {
             safety: SafetyData::default(),
             github_feed: GithubFeedData::default(),
             ai_channel_id: None,
+            commissions: HashMap::new(),
+            commission_board: CommissionBoardData::default(),
+            ai_usage: AIUsageData::default(),
+            ai_memory_opt_outs: HashSet::new(),
+            ai_personas: HashMap::new(),
+            ai_moderation_log: Vec::new(),
+            ai_reply_channels: HashMap::new(),
+            ai_dm_enabled: false,
+            user_locales: HashMap::new(),
+            guild_locales: HashMap::new(),
+            stored_images: HashMap::new(),
+            capability_grants: HashMap::new(),
+            command_fingerprints: HashMap::new(),
             last_updated: Utc::now(),
         }
     }
@@ -263,6 +377,57 @@ impl BotData {
         self.last_updated = Utc::now();
     }
 
+    /// Add or update a commission record
+    pub fn add_commission(&mut self, commission: Commission) {
+        self.commissions.insert(commission.channel_id.clone(), commission);
+        self.last_updated = Utc::now();
+    }
+
+    /// Get the commission record for a channel
+    pub fn get_commission(&self, channel_id: &str) -> Option<&Commission> {
+        self.commissions.get(channel_id)
+    }
+
+    /// Get the active commission opened by a user, if any (delivered and closed ones don't count)
+    pub fn get_user_commission(&self, user_id: &str) -> Option<&Commission> {
+        self.commissions.values().find(|c| c.client_id == user_id && c.is_active())
+    }
+
+    /// Remove the commission record for a channel and the button messages posted in it
+    pub fn remove_commission(&mut self, channel_id: &str) -> Option<Commission> {
+        self.button_messages.retain(|_, button_data| {
+            !(button_data.message_type == MessageType::Commission && button_data.channel_id == channel_id)
+        });
+        self.last_updated = Utc::now();
+        self.commissions.remove(channel_id)
+    }
+
+    /// Commission channels that only exist as button data (opened before records were kept):
+    /// (channel_id, creator_id, creator_name)
+    pub fn untracked_commission_channels(&self) -> Vec<(String, String, String)> {
+        let mut untracked: Vec<(String, String, String)> = Vec::new();
+        for button_data in self.button_messages.values() {
+            if button_data.message_type != MessageType::Commission
+                || self.commissions.contains_key(&button_data.channel_id)
+                || untracked.iter().any(|(channel_id, _, _)| channel_id == &button_data.channel_id)
+            {
+                continue;
+            }
+            if let Some(creator_id) = button_data.get_metadata("commission_creator") {
+                let creator_name = button_data.get_metadata("commission_creator_name").cloned().unwrap_or_default();
+                untracked.push((button_data.channel_id.clone(), creator_id.clone(), creator_name));
+            }
+        }
+        untracked
+    }
+
+    /// Commissions that still need work, oldest first
+    pub fn active_commissions(&self) -> Vec<&Commission> {
+        let mut active: Vec<&Commission> = self.commissions.values().filter(|c| c.is_active()).collect();
+        active.sort_by_key(|c| c.created_at);
+        active
+    }
+
     /// Add message to user's conversation context
     pub fn add_message_to_conversation(&mut self, user_id: &str, message: AIMessage) {
         if let Some(context) = self.conversations.get_mut(user_id) {
@@ -321,6 +486,13 @@ impl BotData {
         self.reminders.remove(reminder_id)
     }
 
+    /// Count a user's reminders that haven't been sent yet
+    pub fn count_user_pending_reminders(&self, user_id: &str) -> usize {
+        self.reminders.values()
+            .filter(|r| !r.is_sent && r.user_id == user_id)
+            .count()
+    }
+
     /// Get all pending reminders (not sent and time has passed)
     pub fn get_pending_reminders(&self) -> Vec<&Reminder> {
         let now = Utc::now();
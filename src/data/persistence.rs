@@ -185,11 +185,27 @@ impl DataManager {
         })
     }
 
-    /// Clear conversation for a user
+    /// Forget a user's conversation (messages, user summary and running summary) and their flagged exchanges
     pub fn clear_conversation(&self, user_id: &str) -> Result<(), Box<dyn std::error::Error>> {
         self.update_data(|data| {
-            if let Some(context) = data.get_conversation_context_mut(user_id) {
-                context.clear_messages();
+            data.conversations.remove(user_id);
+            data.ai_moderation_log.retain(|entry| entry.user_id != user_id);
+        })
+    }
+
+    /// Whether a user opted out of AI memory
+    pub fn is_ai_memory_opted_out(&self, user_id: &str) -> bool {
+        let data = self.data.lock().unwrap();
+        data.ai_memory_opt_outs.contains(user_id)
+    }
+
+    /// Opt a user out of (or back into) AI memory
+    pub fn set_ai_memory_opt_out(&self, user_id: &str, opted_out: bool) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_data(|data| {
+            if opted_out {
+                data.ai_memory_opt_outs.insert(user_id.to_string());
+            } else {
+                data.ai_memory_opt_outs.remove(user_id);
             }
         })
     }
@@ -234,6 +250,12 @@ impl DataManager {
         data.get_pending_reminders().into_iter().cloned().collect()
     }
 
+    /// Count a user's reminders that haven't been sent yet
+    pub fn count_user_pending_reminders(&self, user_id: &str) -> usize {
+        let data = self.data.lock().unwrap();
+        data.count_user_pending_reminders(user_id)
+    }
+
     /// Mark reminder as sent
     pub fn mark_reminder_sent(&self, reminder_id: &str) -> Result<(), Box<dyn std::error::Error>> {
         self.update_data(|data| {
@@ -281,6 +303,394 @@ impl DataManager {
         })
     }
 
+    /// AI persona assigned to a channel
+    pub fn get_ai_persona(&self, channel_id: &str) -> Option<String> {
+        let data = self.data.lock().unwrap();
+        data.ai_personas.get(channel_id).cloned()
+    }
+
+    /// AI persona names by channel ID
+    pub fn get_ai_personas(&self) -> std::collections::HashMap<String, String> {
+        let data = self.data.lock().unwrap();
+        data.ai_personas.clone()
+    }
+
+    /// Assign a persona to a channel (None = back to the default persona)
+    pub fn set_ai_persona(&self, channel_id: &str, persona: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_data(|data| match persona {
+            Some(persona) => {
+                data.ai_personas.insert(channel_id.to_string(), persona);
+            }
+            None => {
+                data.ai_personas.remove(channel_id);
+            }
+        })
+    }
+
+    /// Log a flagged AI exchange, dropping the oldest past the log limit
+    ///
+    /// Users who opted out of AI memory only get hashes of the text logged.
+    pub fn log_ai_moderation(&self, mut entry: crate::data::AIModerationEntry) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_data(|data| {
+            if data.ai_memory_opt_outs.contains(&entry.user_id) {
+                entry.anonymize();
+            }
+            data.ai_moderation_log.push(entry);
+            let excess = data.ai_moderation_log.len().saturating_sub(crate::config::AI_MODERATION_LOG_LIMIT);
+            data.ai_moderation_log.drain(..excess);
+        })
+    }
+
+    /// The most recent flagged AI exchanges, newest first
+    pub fn get_ai_moderation_log(&self, limit: usize) -> Vec<crate::data::AIModerationEntry> {
+        let data = self.data.lock().unwrap();
+        data.ai_moderation_log.iter().rev().take(limit).cloned().collect()
+    }
+
+    /// Channels of a guild where the AI answers mentions and replies
+    pub fn get_ai_reply_channels(&self, guild_id: &str) -> std::collections::HashSet<u64> {
+        let data = self.data.lock().unwrap();
+        data.ai_reply_channels.get(guild_id).cloned().unwrap_or_default()
+    }
+
+    /// Allow or disallow AI mention replies in a channel
+    pub fn set_ai_reply_channel(&self, guild_id: &str, channel_id: u64, allowed: bool) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_data(|data| {
+            let channels = data.ai_reply_channels.entry(guild_id.to_string()).or_default();
+            if allowed {
+                channels.insert(channel_id);
+            } else {
+                channels.remove(&channel_id);
+            }
+            if channels.is_empty() {
+                data.ai_reply_channels.remove(guild_id);
+            }
+        })
+    }
+
+    /// Whether the AI answers direct messages
+    pub fn is_ai_dm_enabled(&self) -> bool {
+        let data = self.data.lock().unwrap();
+        data.ai_dm_enabled
+    }
+
+    /// Turn AI replies to direct messages on or off
+    pub fn set_ai_dm_enabled(&self, enabled: bool) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_data(|data| {
+            data.ai_dm_enabled = enabled;
+        })
+    }
+
+    /// Language a user chose for the bot's replies
+    pub fn get_user_locale(&self, user_id: &str) -> Option<String> {
+        let data = self.data.lock().unwrap();
+        data.user_locales.get(user_id).cloned()
+    }
+
+    /// Set a user's language (None = follow their Discord client)
+    pub fn set_user_locale(&self, user_id: &str, locale: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_data(|data| match locale {
+            Some(locale) => {
+                data.user_locales.insert(user_id.to_string(), locale);
+            }
+            None => {
+                data.user_locales.remove(user_id);
+            }
+        })
+    }
+
+    /// Roles and users a capability is delegated to in a guild
+    pub fn get_capability_grants(&self, guild_id: &str, capability: &str) -> crate::data::CapabilityGrants {
+        let data = self.data.lock().unwrap();
+        data.capability_grants
+            .get(guild_id)
+            .and_then(|grants| grants.get(capability))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Every capability delegated in a guild, by capability name
+    pub fn get_guild_capability_grants(&self, guild_id: &str) -> std::collections::HashMap<String, crate::data::CapabilityGrants> {
+        let data = self.data.lock().unwrap();
+        data.capability_grants.get(guild_id).cloned().unwrap_or_default()
+    }
+
+    /// Delegate a capability to a role or user, or take it back
+    pub fn set_capability_grant(
+        &self,
+        guild_id: &str,
+        capability: &str,
+        role_id: Option<u64>,
+        user_id: Option<u64>,
+        granted: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_data(|data| {
+            let guild = data.capability_grants.entry(guild_id.to_string()).or_default();
+            let grants = guild.entry(capability.to_string()).or_default();
+            for (ids, id) in [(&mut grants.roles, role_id), (&mut grants.users, user_id)] {
+                match id {
+                    Some(id) if granted => {
+                        ids.insert(id);
+                    }
+                    Some(id) => {
+                        ids.remove(&id);
+                    }
+                    None => {}
+                }
+            }
+            if grants.is_empty() {
+                guild.remove(capability);
+            }
+            if guild.is_empty() {
+                data.capability_grants.remove(guild_id);
+            }
+        })
+    }
+
+    /// Fingerprint of the commands last registered in a scope
+    pub fn get_command_fingerprint(&self, scope: &str) -> Option<String> {
+        let data = self.data.lock().unwrap();
+        data.command_fingerprints.get(scope).cloned()
+    }
+
+    /// Scopes commands were registered in
+    pub fn get_command_scopes(&self) -> Vec<String> {
+        let data = self.data.lock().unwrap();
+        data.command_fingerprints.keys().cloned().collect()
+    }
+
+    /// Record what was registered in a scope (None = nothing is registered there)
+    pub fn set_command_fingerprint(&self, scope: &str, fingerprint: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_data(|data| match fingerprint {
+            Some(fingerprint) => {
+                data.command_fingerprints.insert(scope.to_string(), fingerprint);
+            }
+            None => {
+                data.command_fingerprints.remove(scope);
+            }
+        })
+    }
+
+    /// Default language of a server
+    pub fn get_guild_locale(&self, guild_id: &str) -> Option<String> {
+        let data = self.data.lock().unwrap();
+        data.guild_locales.get(guild_id).cloned()
+    }
+
+    /// Set a server's default language (None = English)
+    pub fn set_guild_locale(&self, guild_id: &str, locale: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_data(|data| match locale {
+            Some(locale) => {
+                data.guild_locales.insert(guild_id.to_string(), locale);
+            }
+            None => {
+                data.guild_locales.remove(guild_id);
+            }
+        })
+    }
+
+    /// Storage channel message holding the uploaded copy of an image
+    pub fn get_stored_image(&self, key: &str) -> Option<crate::data::StoredImage> {
+        let data = self.data.lock().unwrap();
+        data.stored_images.get(key).cloned()
+    }
+
+    /// Record the upload of an image
+    pub fn set_stored_image(&self, key: &str, image: crate::data::StoredImage) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_data(|data| {
+            data.stored_images.insert(key.to_string(), image);
+        })
+    }
+
+    /// Get AI usage per user and the quota configuration
+    pub fn get_ai_usage(&self) -> crate::data::AIUsageData {
+        let data = self.data.lock().unwrap();
+        data.ai_usage.clone()
+    }
+
+    /// AI messages a user sent today and their daily quota (0 = unlimited)
+    pub fn get_ai_quota_status(&self, user_id: &str, role_ids: &[String]) -> (u32, u32) {
+        let data = self.data.lock().unwrap();
+        (
+            data.ai_usage.messages_today(user_id, Utc::now()),
+            data.ai_usage.quotas.daily_limit(role_ids),
+        )
+    }
+
+    /// Count an AI reply and its tokens for a user
+    pub fn record_ai_usage(&self, user_id: String, user_name: String, prompt_tokens: u64, response_tokens: u64) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_data(|data| {
+            data.ai_usage.record(user_id, user_name, prompt_tokens, response_tokens, Utc::now());
+        })
+    }
+
+    /// Count tokens spent on a user's behalf outside a reply (conversation and profile summaries)
+    pub fn record_ai_tokens(&self, user_id: String, user_name: String, prompt_tokens: u64, response_tokens: u64) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_data(|data| {
+            data.ai_usage.record_tokens(user_id, user_name, prompt_tokens, response_tokens, Utc::now());
+        })
+    }
+
+    /// Set the daily AI quota for a role, or the default quota when `role_id` is None
+    pub fn set_ai_quota(&self, role_id: Option<String>, daily: u32) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_data(|data| match role_id {
+            Some(role_id) => {
+                data.ai_usage.quotas.roles.insert(role_id, daily);
+            }
+            None => data.ai_usage.quotas.default_daily = daily,
+        })
+    }
+
+    /// Add or update a commission record
+    pub fn add_commission(&self, commission: crate::data::Commission) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_data(|data| {
+            data.add_commission(commission);
+        })
+    }
+
+    /// Apply a lifecycle change to a channel's commission under the data lock
+    ///
+    /// A rejected change leaves the record untouched. Returns None when the channel
+    /// has no commission, otherwise the change's result with the updated record.
+    #[allow(clippy::type_complexity)]
+    pub fn update_commission<T, E>(
+        &self,
+        channel_id: &str,
+        change: impl FnOnce(&mut crate::data::Commission) -> Result<T, E>,
+    ) -> Result<Option<Result<(T, crate::data::Commission), E>>, Box<dyn std::error::Error>> {
+        let mut outcome = None;
+        self.update_data(|data| {
+            if let Some(stored) = data.commissions.get_mut(channel_id) {
+                let mut commission = stored.clone();
+                outcome = Some(change(&mut commission).map(|value| {
+                    *stored = commission.clone();
+                    (value, commission)
+                }));
+            }
+        })?;
+        Ok(outcome)
+    }
+
+    /// Remember the pinned progress embed of a commission
+    pub fn set_commission_progress_message(&self, channel_id: &str, message_id: String) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_data(|data| {
+            if let Some(commission) = data.commissions.get_mut(channel_id) {
+                commission.progress_message_id = Some(message_id);
+            }
+        })
+    }
+
+    /// Remember the message holding a quote's buttons, unless a newer quote replaced it
+    pub fn set_commission_quote_message(&self, channel_id: &str, number: u32, message_id: String) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_data(|data| {
+            let quote = data.commissions.get_mut(channel_id).and_then(|commission| commission.quote.as_mut());
+            if let Some(quote) = quote.filter(|quote| quote.number == number) {
+                quote.message_id = Some(message_id);
+            }
+        })
+    }
+
+    /// Get the commission record for a channel
+    pub fn get_commission(&self, channel_id: &str) -> Option<crate::data::Commission> {
+        let data = self.data.lock().unwrap();
+        data.get_commission(channel_id).cloned()
+    }
+
+    /// Commissions that still need work, oldest first
+    pub fn get_active_commissions(&self) -> Vec<crate::data::Commission> {
+        let data = self.data.lock().unwrap();
+        data.active_commissions().into_iter().cloned().collect()
+    }
+
+    /// Get commission availability, panel location and waitlist
+    pub fn get_commission_board(&self) -> crate::data::CommissionBoardData {
+        let data = self.data.lock().unwrap();
+        data.commission_board.clone()
+    }
+
+    /// Set whether new commissions are being accepted
+    pub fn set_commission_availability(&self, availability: crate::data::CommissionAvailability) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_data(|data| {
+            data.commission_board.availability = availability;
+        })
+    }
+
+    /// Remember where the commission setup panel was posted
+    pub fn set_commission_panel(&self, channel_id: String, message_id: String) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_data(|data| {
+            data.commission_board.panel_channel_id = Some(channel_id);
+            data.commission_board.panel_message_id = Some(message_id);
+        })
+    }
+
+    /// Add a user to the commission waitlist; returns their position and whether they were newly added
+    pub fn join_commission_waitlist(&self, user_id: String, user_name: String) -> Result<(usize, bool), Box<dyn std::error::Error>> {
+        let result = {
+            let mut data = self.data.lock().unwrap();
+            let result = data.commission_board.join_waitlist(user_id, user_name);
+            data.last_updated = Utc::now();
+            result
+        };
+
+        if self.auto_save {
+            self.save()?;
+        }
+
+        Ok(result)
+    }
+
+    /// Remove a user from the commission waitlist
+    pub fn leave_commission_waitlist(&self, user_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_data(|data| {
+            data.commission_board.leave_waitlist(user_id);
+        })
+    }
+
+    /// Get the active commission opened by a user, if any
+    pub fn get_user_commission(&self, user_id: &str) -> Option<crate::data::Commission> {
+        let data = self.data.lock().unwrap();
+        data.get_user_commission(user_id).cloned()
+    }
+
+    /// Create records for commission channels that only exist as button data
+    pub fn adopt_untracked_commissions(&self) -> Result<usize, Box<dyn std::error::Error>> {
+        let mut adopted = 0;
+        self.update_data(|data| {
+            for (channel_id, client_id, client_name) in data.untracked_commission_channels() {
+                let id = uuid::Uuid::new_v4().to_string()[..8].to_lowercase();
+                data.add_commission(crate::data::Commission::new(
+                    id,
+                    channel_id,
+                    client_id,
+                    client_name,
+                    crate::data::CommissionBrief::default(),
+                ));
+                adopted += 1;
+            }
+        })?;
+        Ok(adopted)
+    }
+
+    /// Get the channel IDs of all commission records
+    pub fn get_commission_channel_ids(&self) -> Vec<String> {
+        let data = self.data.lock().unwrap();
+        data.commissions.keys().cloned().collect()
+    }
+
+    /// Remove the commission record for a channel and its button data
+    pub fn remove_commission(&self, channel_id: &str) -> Result<Option<crate::data::Commission>, Box<dyn std::error::Error>> {
+        let removed = {
+            let mut data = self.data.lock().unwrap();
+            data.remove_commission(channel_id)
+        };
+        
+        if self.auto_save {
+            self.save()?;
+        }
+        
+        Ok(removed)
+    }
+
     /// Add feedback message
     pub fn add_feedback_message(&self, feedback_message: crate::data::FeedbackMessage) -> Result<(), Box<dyn std::error::Error>> {
         let message_id = feedback_message.message_id.clone();
@@ -364,6 +364,7 @@ impl SafetyService {
         component: &ComponentInteraction,
         data: &DataManager,
         lang: &LanguageManager,
+        locale: &str,
     ) -> bool {
         if !component.data.custom_id.starts_with("safety:") {
             return false;
@@ -394,7 +395,7 @@ impl SafetyService {
             self.edit_interaction(
                 component,
                 ctx,
-                &lang.get().safety.responses.invalid_interaction,
+                &lang.get(locale).safety.responses.invalid_interaction,
             )
             .await;
             return true;
@@ -414,7 +415,7 @@ impl SafetyService {
                 self.edit_interaction(
                     component,
                     ctx,
-                    &lang.get().safety.responses.invalid_interaction,
+                    &lang.get(locale).safety.responses.invalid_interaction,
                 )
                 .await;
                 return true;
@@ -422,10 +423,10 @@ impl SafetyService {
         };
 
         let response = match custom_id.action {
-            SafetyAction::Verify => self.verify_member(ctx, &member, data, lang).await,
-            SafetyAction::NotNow => lang.get().safety.responses.not_now.clone(),
-            SafetyAction::Subscribe => self.subscribe(ctx, &member, data, lang).await,
-            SafetyAction::Unsubscribe => self.unsubscribe(ctx, &member, data, lang).await,
+            SafetyAction::Verify => self.verify_member(ctx, &member, data, lang, locale).await,
+            SafetyAction::NotNow => lang.get(locale).safety.responses.not_now.clone(),
+            SafetyAction::Subscribe => self.subscribe(ctx, &member, data, lang, locale).await,
+            SafetyAction::Unsubscribe => self.unsubscribe(ctx, &member, data, lang, locale).await,
         };
         self.edit_interaction(component, ctx, &response).await;
         true
@@ -437,6 +438,7 @@ impl SafetyService {
         member: &Member,
         data: &DataManager,
         lang: &LanguageManager,
+        locale: &str,
     ) -> String {
         let already_verified = member.roles.contains(&self.config.verified_role_id);
         if !already_verified {
@@ -445,7 +447,7 @@ impl SafetyService {
                 .await
             {
                 eprintln!("SAFETY add Verified role failed: {error}");
-                return lang.get().safety.responses.role_update_failed.clone();
+                return lang.get(locale).safety.responses.role_update_failed.clone();
             }
         }
 
@@ -465,7 +467,7 @@ impl SafetyService {
                         );
                     }
                 }
-                return lang.get().safety.responses.role_update_failed.clone();
+                return lang.get(locale).safety.responses.role_update_failed.clone();
             }
         }
 
@@ -476,9 +478,9 @@ impl SafetyService {
             eprintln!("SAFETY verified pending cleanup failed: {error}");
         }
         if already_verified {
-            lang.get().safety.responses.already_verified.clone()
+            lang.get(locale).safety.responses.already_verified.clone()
         } else {
-            lang.get().safety.responses.verified.clone()
+            lang.get(locale).safety.responses.verified.clone()
         }
     }
 
@@ -488,6 +490,7 @@ impl SafetyService {
         member: &Member,
         data: &DataManager,
         lang: &LanguageManager,
+        locale: &str,
     ) -> String {
         let already_subscriber = member.roles.contains(&self.config.subscriber_role_id);
         if !already_subscriber {
@@ -496,7 +499,7 @@ impl SafetyService {
                 .await
             {
                 eprintln!("SAFETY add Subscriber role failed: {error}");
-                return lang.get().safety.responses.subscription_failed.clone();
+                return lang.get(locale).safety.responses.subscription_failed.clone();
             }
         }
 
@@ -532,10 +535,10 @@ impl SafetyService {
                     eprintln!("CRITICAL SAFETY Subscriber-role rollback failed: {rollback_error}");
                 }
             }
-            return lang.get().safety.responses.subscription_failed.clone();
+            return lang.get(locale).safety.responses.subscription_failed.clone();
         }
 
-        lang.get().safety.responses.subscribed.clone()
+        lang.get(locale).safety.responses.subscribed.clone()
     }
 
     async fn unsubscribe(
@@ -544,6 +547,7 @@ impl SafetyService {
         member: &Member,
         data: &DataManager,
         lang: &LanguageManager,
+        locale: &str,
     ) -> String {
         let key = member_key(member.guild_id, member.user.id);
         let persist_result = data
@@ -565,7 +569,7 @@ impl SafetyService {
             .map_err(display_error);
         if let Err(error) = &persist_result {
             eprintln!("CRITICAL SAFETY persist unsubscribe suppression failed: {error}");
-            return lang.get().safety.responses.subscription_failed.clone();
+            return lang.get(locale).safety.responses.subscription_failed.clone();
         }
         if let Err(error) = member
             .remove_role(&ctx.http, self.config.subscriber_role_id)
@@ -574,7 +578,7 @@ impl SafetyService {
             eprintln!("SAFETY remove Subscriber role failed after ledger suppression: {error}");
         }
 
-        lang.get().safety.responses.unsubscribed.clone()
+        lang.get(locale).safety.responses.unsubscribed.clone()
     }
 
     async fn edit_interaction(
@@ -602,6 +606,7 @@ impl SafetyService {
         message: &Message,
         data: &DataManager,
         lang: &LanguageManager,
+        locale: &str,
         images: &ImageManager,
     ) -> bool {
         match classify_channel(message.channel_id.get()) {
@@ -637,7 +642,7 @@ impl SafetyService {
                     return true;
                 }
                 if is_human_message(message) {
-                    if let Err(error) = self.process_honeypot(ctx, message, data, lang).await {
+                    if let Err(error) = self.process_honeypot(ctx, message, data, lang, locale).await {
                         eprintln!("SAFETY honeypot stage failed: {error}");
                     }
                 }
@@ -739,7 +744,8 @@ impl SafetyService {
             .map_err(display_error)?;
         }
 
-        self.deliver_announcement_job(ctx, message, data, lang)
+        let locale = guild_locale(lang, data, guild_id);
+        self.deliver_announcement_job(ctx, message, data, lang, locale)
             .await
     }
 
@@ -749,6 +755,7 @@ impl SafetyService {
         source: &Message,
         data: &DataManager,
         lang: &LanguageManager,
+        locale: &str,
     ) -> SafetyResult<()> {
         let key = source.id.to_string();
         let job = data
@@ -758,7 +765,7 @@ impl SafetyService {
             .get(&key)
             .cloned()
             .ok_or_else(|| "announcement delivery job missing".to_string())?;
-        let embed = safe_announcement_embed(source, lang);
+        let embed = safe_announcement_embed(source, lang, locale);
 
         for user_id in pending_delivery_recipients(&job) {
             let user = UserId::new(user_id);
@@ -882,6 +889,7 @@ impl SafetyService {
         message: &Message,
         data: &DataManager,
         lang: &LanguageManager,
+        locale: &str,
     ) -> SafetyResult<()> {
         let guild_id = message
             .guild_id
@@ -930,7 +938,7 @@ impl SafetyService {
         if initial_stage == HoneypotStage::Received {
             self.update_honeypot_stage(data, &key, honeypot_transition(initial_stage, true), None)?;
             let security_notice = lang
-                .get()
+                .get(locale)
                 .safety
                 .honeypot
                 .security_dm
@@ -1082,6 +1090,7 @@ impl SafetyService {
         data: &DataManager,
         lang: &LanguageManager,
     ) -> SafetyResult<()> {
+        let locale = guild_locale(lang, data, guild_id);
         let source_ids: Vec<u64> = data
             .get_data()
             .safety
@@ -1119,7 +1128,7 @@ impl SafetyService {
                 }
             };
             if let Err(error) = self
-                .deliver_announcement_job(ctx, &source, data, lang)
+                .deliver_announcement_job(ctx, &source, data, lang, locale)
                 .await
             {
                 recovery_errors.push(error);
@@ -1242,6 +1251,7 @@ impl SafetyService {
         data: &DataManager,
         lang: &LanguageManager,
     ) -> SafetyResult<()> {
+        let locale = guild_locale(lang, data, guild_id);
         let key = guild_key(guild_id);
         let stored_id = data
             .get_data()
@@ -1249,8 +1259,8 @@ impl SafetyService {
             .verification_message_ids
             .get(&key)
             .copied();
-        let embed = verification_embed(lang);
-        let components = verification_components(guild_id, lang);
+        let embed = verification_embed(lang, locale);
+        let components = verification_components(guild_id, lang, locale);
         let current_id = self
             .upsert_canonical(
                 ctx,
@@ -1283,6 +1293,7 @@ impl SafetyService {
         lang: &LanguageManager,
         images: &ImageManager,
     ) -> SafetyResult<()> {
+        let locale = guild_locale(lang, data, guild_id);
         let channel_id = ChannelId::new(HONEYPOT_CHANNEL_ID);
         let key = guild_key(guild_id);
         let stored_id = data
@@ -1291,7 +1302,7 @@ impl SafetyService {
             .honeypot_message_ids
             .get(&key)
             .copied();
-        let embed = honeypot_embed(lang, images);
+        let embed = honeypot_embed(lang, locale, images);
         let current_id = self
             .upsert_canonical(
                 ctx,
@@ -1463,8 +1474,8 @@ fn effective_role_permissions(
     permissions
 }
 
-fn verification_embed(lang: &LanguageManager) -> CreateEmbed {
-    let text = &lang.get().safety.verification;
+fn verification_embed(lang: &LanguageManager, locale: &str) -> CreateEmbed {
+    let text = &lang.get(locale).safety.verification;
     CreateEmbed::new()
         .title(&text.title)
         .description(&text.description)
@@ -1475,8 +1486,8 @@ fn verification_embed(lang: &LanguageManager) -> CreateEmbed {
         )))
 }
 
-fn verification_components(guild_id: GuildId, lang: &LanguageManager) -> Vec<CreateActionRow> {
-    let text = &lang.get().safety.verification;
+fn verification_components(guild_id: GuildId, lang: &LanguageManager, locale: &str) -> Vec<CreateActionRow> {
+    let text = &lang.get(locale).safety.verification;
     vec![
         CreateActionRow::Buttons(vec![
             CreateButton::new(SafetyCustomId::new(SafetyAction::Verify, guild_id).encode())
@@ -1497,18 +1508,15 @@ fn verification_components(guild_id: GuildId, lang: &LanguageManager) -> Vec<Cre
     ]
 }
 
-fn honeypot_embed(lang: &LanguageManager, images: &ImageManager) -> CreateEmbed {
-    let text = &lang.get().safety.honeypot;
+fn honeypot_embed(lang: &LanguageManager, locale: &str, images: &ImageManager) -> CreateEmbed {
+    let text = &lang.get(locale).safety.honeypot;
     let mut embed = CreateEmbed::new()
         .title(&text.title)
         .description(&text.intro)
         .color(0xe74c3c)
-        .field("English", &text.english, false)
-        .field("Español", &text.spanish, false)
-        .field("Português (Brasil)", &text.portuguese_brazil, false)
-        .field("Русский", &text.russian, false)
-        .field("简体中文", &text.chinese_simplified, false)
-        .field("Français", &text.french, false)
+        .fields(lang.locales().map(|(_, messages)| {
+            (&messages.language.name, &messages.safety.honeypot.warning, false)
+        }))
         .footer(CreateEmbedFooter::new(&text.footer));
     if let Some(url) = images
         .get_image("safety", "barrier")
@@ -1525,8 +1533,8 @@ fn is_valid_http_image_url(raw: &str) -> bool {
         .is_some_and(|url| matches!(url.scheme(), "http" | "https") && url.host_str().is_some())
 }
 
-fn safe_announcement_embed(message: &Message, lang: &LanguageManager) -> CreateEmbed {
-    let text = &lang.get().safety.announcement;
+fn safe_announcement_embed(message: &Message, lang: &LanguageManager, locale: &str) -> CreateEmbed {
+    let text = &lang.get(locale).safety.announcement;
     let description = truncate_chars(
         if message.content.trim().is_empty() {
             "(No text content)"
@@ -1681,6 +1689,11 @@ fn guild_key(guild_id: GuildId) -> String {
     guild_id.to_string()
 }
 
+/// Locale for server-wide posts: panels and announcement DMs
+fn guild_locale<'a>(lang: &'a LanguageManager, data: &DataManager, guild_id: GuildId) -> &'a str {
+    lang.resolve(None, None, data.get_guild_locale(&guild_key(guild_id)).as_deref())
+}
+
 fn member_key(guild_id: GuildId, user_id: UserId) -> String {
     format!("{guild_id}:{user_id}")
 }
@@ -2105,22 +2118,27 @@ mod tests {
     #[test]
     fn six_warning_languages_are_complete_and_embed_is_under_limit() {
         let manager = LanguageManager::new().expect("language config should parse");
-        let warning = &manager.get().safety.honeypot;
-        let values = [
-            &warning.english,
-            &warning.spanish,
-            &warning.portuguese_brazil,
-            &warning.russian,
-            &warning.chinese_simplified,
-            &warning.french,
-        ];
-        let total = warning.title.chars().count()
-            + warning.intro.chars().count()
-            + warning.footer.chars().count()
-            + values
-                .iter()
-                .map(|value| value.chars().count())
-                .sum::<usize>();
-        assert!(values.iter().all(|value| !value.trim().is_empty()) && total <= 6000);
+        let (_, english) = manager.locales().next().expect("English is always loaded");
+        let warnings: Vec<_> = manager
+            .locales()
+            .map(|(_, messages)| (&messages.language.name, &messages.safety.honeypot.warning))
+            .collect();
+        assert!(warnings.len() >= 6);
+        assert!(warnings.iter().all(|(_, warning)| !warning.trim().is_empty()));
+        // Every locale translates its own line instead of repeating the English one
+        assert!(warnings.iter().skip(1).all(|(_, warning)| **warning != english.safety.honeypot.warning));
+
+        let fields = warnings
+            .iter()
+            .map(|(name, warning)| name.chars().count() + warning.chars().count())
+            .sum::<usize>();
+        for (_, messages) in manager.locales() {
+            let honeypot = &messages.safety.honeypot;
+            let total = honeypot.title.chars().count()
+                + honeypot.intro.chars().count()
+                + honeypot.footer.chars().count()
+                + fields;
+            assert!(total <= 6000);
+        }
     }
 }
@@ -0,0 +1,362 @@
+//! Images, emojis and language files read from a directory at runtime, so a
+//! typo or an expired image URL can be fixed without a rebuild. Files missing
+//! from the directory use the copies compiled into the binary.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::SystemTime;
+use serenity::all::{EmojiId, GuildId, Http};
+use super::check::{self, Severity};
+use super::{embedded_locales, EmojiManager, ImageManager, LanguageManager, EMBEDDED_EMOJIS, EMBEDDED_IMAGES, LOCALES};
+
+const IMAGES_FILE: &str = "bot_images.toml";
+const EMOJIS_FILE: &str = "bot_emojis.toml";
+const LANG_DIR: &str = "lang";
+
+/// How often the asset files are checked for changes
+pub const POLL_INTERVAL_SECS: u64 = 5;
+
+/// Directory to read assets from: `ASSETS_DIR`, or the working directory
+pub fn assets_dir() -> PathBuf {
+    std::env::var("ASSETS_DIR").unwrap_or_else(|_| ".".to_string()).into()
+}
+
+/// Language, image and emoji managers loaded together
+pub struct Assets {
+    pub lang: LanguageManager,
+    pub images: ImageManager,
+    pub emojis: EmojiManager,
+}
+
+/// What a reload picked up
+#[derive(Debug)]
+pub struct AssetReport {
+    /// Files read from the directory rather than the built-in copies
+    pub from_disk: usize,
+    pub locales: usize,
+    /// Lint warnings (untranslated or unused keys)
+    pub warnings: usize,
+}
+
+/// What the bot learned at runtime that changes loaded assets
+#[derive(Debug, Clone, Default)]
+struct Overrides {
+    /// Fresh URLs of images uploaded from local files, by `category.name`
+    image_urls: HashMap<String, String>,
+    /// Custom emojis the bot can use, once checked
+    usable_emojis: Option<HashSet<EmojiId>>,
+}
+
+/// File contents to build `Assets` from
+struct AssetSources {
+    images: String,
+    emojis: String,
+    /// English first, then the other built-in locales, then extra files found on disk
+    locales: Vec<(String, String)>,
+    from_disk: usize,
+}
+
+impl AssetSources {
+    fn embedded() -> Self {
+        AssetSources {
+            images: EMBEDDED_IMAGES.to_string(),
+            emojis: EMBEDDED_EMOJIS.to_string(),
+            locales: embedded_locales(),
+            from_disk: 0,
+        }
+    }
+
+    fn read(dir: &Path) -> Self {
+        let mut sources = Self::embedded();
+        if let Some(images) = read_file(&dir.join(IMAGES_FILE)) {
+            sources.images = images;
+            sources.from_disk += 1;
+        }
+        if let Some(emojis) = read_file(&dir.join(EMOJIS_FILE)) {
+            sources.emojis = emojis;
+            sources.from_disk += 1;
+        }
+        for (path, _, _) in lang_files(dir) {
+            let Some(locale) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let Some(content) = read_file(&path) else {
+                continue;
+            };
+            match sources.locales.iter_mut().find(|(key, _)| key == locale) {
+                Some((_, existing)) => *existing = content,
+                None => sources.locales.push((locale.to_string(), content)),
+            }
+            sources.from_disk += 1;
+        }
+        sources
+    }
+}
+
+impl Assets {
+    /// Lint and parse everything; any error rejects the whole set
+    ///
+    /// `overrides` replaces image URLs and unusable custom emojis.
+    fn load(sources: &AssetSources, overrides: &Overrides) -> Result<(Self, AssetReport), String> {
+        let issues = check::check_sources(&sources.locales);
+        let errors: Vec<String> = issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Error)
+            .map(|issue| issue.to_string())
+            .collect();
+        if !errors.is_empty() {
+            return Err(errors.join("\n"));
+        }
+
+        let mut images = ImageManager::parse(&sources.images).map_err(|e| format!("Invalid {}: {}", IMAGES_FILE, e))?;
+        for (key, url) in &overrides.image_urls {
+            if let Some((category, name)) = key.split_once('.') {
+                images.set_url(category, name, url.clone());
+            }
+        }
+        let mut emojis = EmojiManager::parse(&sources.emojis).map_err(|e| format!("Invalid {}: {}", EMOJIS_FILE, e))?;
+        if let Some(usable) = &overrides.usable_emojis {
+            emojis.apply_usable(usable);
+        }
+        let assets = Assets {
+            lang: LanguageManager::from_sources(&sources.locales).map_err(|e| e.to_string())?,
+            images,
+            emojis,
+        };
+        let report = AssetReport {
+            from_disk: sources.from_disk,
+            locales: sources.locales.len(),
+            warnings: issues.len(),
+        };
+        Ok((assets, report))
+    }
+}
+
+/// Assets from a directory, reloaded when its files change (see `refresh`) or on request
+pub struct AssetLibrary {
+    dir: PathBuf,
+    current: RwLock<Arc<Assets>>,
+    snapshot: Mutex<Vec<(PathBuf, Option<SystemTime>, u64)>>,
+    overrides: Mutex<Overrides>,
+}
+
+impl AssetLibrary {
+    /// Load from `dir`, falling back to the built-in copies if its files are invalid
+    pub fn new(dir: PathBuf) -> Result<Self, Box<dyn std::error::Error>> {
+        let snapshot = snapshot(&dir);
+        let assets = match Assets::load(&AssetSources::read(&dir), &Overrides::default()) {
+            Ok((assets, report)) => {
+                if report.from_disk > 0 {
+                    println!("🗂️  Loaded {} asset files from {}", report.from_disk, dir.display());
+                }
+                assets
+            }
+            Err(e) => {
+                eprintln!("⚠️  Assets in {} are invalid, using the built-in copies:\n{}", dir.display(), e);
+                Assets {
+                    lang: LanguageManager::new()?,
+                    images: ImageManager::new()?,
+                    emojis: EmojiManager::new()?,
+                }
+            }
+        };
+        Ok(AssetLibrary {
+            dir,
+            current: RwLock::new(Arc::new(assets)),
+            snapshot: Mutex::new(snapshot),
+            overrides: Mutex::new(Overrides::default()),
+        })
+    }
+
+    /// Directory the assets are read from
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The current assets
+    ///
+    /// Callers keep the returned snapshot for the whole event, so a reload
+    /// never mixes old and new text in one reply.
+    pub fn current(&self) -> Arc<Assets> {
+        self.current.read().unwrap().clone()
+    }
+
+    /// Reload if any asset file was added, removed or modified since the last load
+    ///
+    /// Called every `POLL_INTERVAL_SECS` from a background task. Invalid files
+    /// are reported and the previous assets stay in use. Returns whether new
+    /// assets were loaded.
+    pub fn refresh(&self) -> bool {
+        let snapshot = snapshot(&self.dir);
+        {
+            let mut previous = self.snapshot.lock().unwrap();
+            if *previous == snapshot {
+                return false;
+            }
+            *previous = snapshot;
+        }
+        match self.reload() {
+            Ok(report) => {
+                println!("🗂️  Asset files changed, reloaded {} files from {}", report.from_disk, self.dir.display());
+                true
+            }
+            Err(e) => {
+                eprintln!("⚠️  Asset files changed but are invalid, keeping the current ones:\n{}", e);
+                false
+            }
+        }
+    }
+
+    /// Re-read every file now; on error the current assets stay in use
+    pub fn reload(&self) -> Result<AssetReport, String> {
+        *self.snapshot.lock().unwrap() = snapshot(&self.dir);
+        let overrides = self.overrides.lock().unwrap().clone();
+        let (assets, report) = Assets::load(&AssetSources::read(&self.dir), &overrides)?;
+        *self.current.write().unwrap() = Arc::new(assets);
+        Ok(report)
+    }
+
+    /// Use these URLs for images (by `category.name`) from now on, over `bot_images.toml`
+    pub fn set_image_urls(&self, urls: HashMap<String, String>) -> Result<(), String> {
+        self.overrides.lock().unwrap().image_urls = urls;
+        self.reload().map(|_| ())
+    }
+
+    /// Check which custom emojis the bot can use: application emojis and those
+    /// of the servers it is in. The rest show their Unicode fallback from now on.
+    pub async fn check_emojis(&self, http: &Http, guild_ids: &[GuildId]) -> Result<(), String> {
+        let mut usable: HashSet<EmojiId> = http
+            .get_application_emojis()
+            .await
+            .map_err(|e| format!("Failed to list application emojis: {}", e))?
+            .into_iter()
+            .map(|emoji| emoji.id)
+            .collect();
+        for guild_id in guild_ids {
+            match http.get_emojis(*guild_id).await {
+                Ok(emojis) => usable.extend(emojis.into_iter().filter(|emoji| emoji.available).map(|emoji| emoji.id)),
+                Err(e) => eprintln!("⚠️  Failed to list emojis of guild {}: {}", guild_id, e),
+            }
+        }
+
+        self.overrides.lock().unwrap().usable_emojis = Some(usable);
+        self.reload()?;
+        let assets = self.current();
+        if !assets.emojis.substituted().is_empty() {
+            eprintln!("⚠️  Custom emojis unavailable, using fallbacks: {}", assets.emojis.substituted().join(", "));
+        }
+        if !assets.emojis.unusable().is_empty() {
+            eprintln!("⚠️  Custom emojis unavailable and without a fallback: {}", assets.emojis.unusable().join(", "));
+        }
+        Ok(())
+    }
+}
+
+/// Locale files `--check-lang` and the bot would load from `dir`
+pub fn lang_sources(dir: &Path) -> Vec<(String, String)> {
+    AssetSources::read(dir).locales
+}
+
+/// A file's contents, or None when it doesn't exist or can't be read
+fn read_file(path: &Path) -> Option<String> {
+    match fs::read_to_string(path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == ErrorKind::NotFound => None,
+        Err(e) => {
+            eprintln!("⚠️  Failed to read {}, using the built-in copy: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// `.toml` files in `dir/lang`, built-in locales first, with modification time and size
+fn lang_files(dir: &Path) -> Vec<(PathBuf, Option<SystemTime>, u64)> {
+    let Ok(entries) = fs::read_dir(dir.join(LANG_DIR)) else {
+        return Vec::new();
+    };
+    let mut files: Vec<_> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("toml"))
+        .filter_map(|path| {
+            let metadata = fs::metadata(&path).ok()?;
+            Some((path, metadata.modified().ok(), metadata.len()))
+        })
+        .collect();
+    let builtin = |path: &Path| {
+        let stem = path.file_stem().and_then(|s| s.to_str());
+        LOCALES.iter().position(|(locale, _)| Some(*locale) == stem).unwrap_or(LOCALES.len())
+    };
+    files.sort_by(|(a, ..), (b, ..)| builtin(a).cmp(&builtin(b)).then_with(|| a.cmp(b)));
+    files
+}
+
+/// Every asset file in `dir` with its modification time and size
+fn snapshot(dir: &Path) -> Vec<(PathBuf, Option<SystemTime>, u64)> {
+    let mut files = lang_files(dir);
+    for name in [IMAGES_FILE, EMOJIS_FILE] {
+        let path = dir.join(name);
+        if let Ok(metadata) = fs::metadata(&path) {
+            files.push((path, metadata.modified().ok(), metadata.len()));
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("lorian-assets-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join(LANG_DIR)).unwrap();
+        dir
+    }
+
+    #[test]
+    fn empty_directory_uses_the_builtin_copies() {
+        let dir = temp_dir("empty");
+        let library = AssetLibrary::new(dir.clone()).unwrap();
+        let assets = library.current();
+        assert_eq!(assets.lang.format_hello("en", "Ana"), "Hello, Ana!");
+        assert_eq!(library.reload().unwrap().from_disk, 0);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn files_on_disk_override_and_add_locales() {
+        let dir = temp_dir("override");
+        fs::write(dir.join(LANG_DIR).join("es.toml"), "[responses]\nhello = \"¡Buenas, {username}!\"").unwrap();
+        fs::write(dir.join(LANG_DIR).join("de.toml"), "[language]\nname = \"Deutsch\"").unwrap();
+        let library = AssetLibrary::new(dir.clone()).unwrap();
+        let assets = library.current();
+        assert_eq!(assets.lang.format_hello("es", "Ana"), "¡Buenas, Ana!");
+        assert_eq!(assets.lang.get("de").language.name, "Deutsch");
+        assert_eq!(assets.lang.find("de-DE"), Some("de"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn invalid_reload_keeps_the_current_assets() {
+        let dir = temp_dir("invalid");
+        let library = AssetLibrary::new(dir.clone()).unwrap();
+
+        fs::write(dir.join(LANG_DIR).join("es.toml"), "[responses]\nhello = \"Hola, {user}!\"").unwrap();
+        assert!(library.reload().unwrap_err().contains("responses.hello"));
+        fs::write(dir.join(IMAGES_FILE), "not toml [").unwrap();
+        assert!(library.reload().is_err());
+        assert_eq!(library.current().lang.format_hello("es", "Ana"), "¡Hola, Ana!");
+
+        fs::remove_file(dir.join(IMAGES_FILE)).unwrap();
+        fs::write(dir.join(LANG_DIR).join("es.toml"), "[responses]\nhello = \"Hola de nuevo, {username}!\"").unwrap();
+        // Picked up by the next poll without an explicit reload
+        assert!(library.refresh());
+        assert_eq!(library.current().lang.format_hello("es", "Ana"), "Hola de nuevo, Ana!");
+        assert!(!library.refresh());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
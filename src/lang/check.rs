@@ -0,0 +1,254 @@
+//! Lint for the lang/*.toml files, run by the test suite and `--check-lang`.
+//! Non-English files only need the keys they translate; everything is checked
+//! against en.toml, which must match the `Messages` schema exactly.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::sync::LazyLock;
+use regex::Regex;
+use super::{fill_missing, Messages, DEFAULT_LOCALE};
+
+static PLACEHOLDER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{[a-z_][a-z0-9_]*\}").unwrap());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Works, but falls back to English or carries dead text
+    Warning,
+    /// Fails to load, is silently ignored or breaks a Discord limit
+    Error,
+}
+
+#[derive(Debug)]
+pub struct Issue {
+    pub severity: Severity,
+    pub locale: String,
+    /// Dotted key, empty for problems with the whole file
+    pub key: String,
+    pub message: String,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        if self.key.is_empty() {
+            write!(f, "{}: lang/{}.toml: {}", severity, self.locale, self.message)
+        } else {
+            write!(f, "{}: lang/{}.toml [{}]: {}", severity, self.locale, self.key, self.message)
+        }
+    }
+}
+
+/// Check `(locale, file contents)` pairs; English must come first
+pub fn check_sources(sources: &[(String, String)]) -> Vec<Issue> {
+    let english_content = match sources.first() {
+        Some((locale, content)) if locale == DEFAULT_LOCALE => content,
+        _ => return vec![issue(Severity::Error, DEFAULT_LOCALE, "", "is missing".to_string())],
+    };
+    let english = match toml::from_str::<toml::Value>(english_content) {
+        Ok(english) => english,
+        Err(e) => return vec![issue(Severity::Error, DEFAULT_LOCALE, "", format!("does not parse: {}", e))],
+    };
+    sources
+        .iter()
+        .flat_map(|(locale, content)| check_locale(locale, content, &english))
+        .collect()
+}
+
+/// Check one locale file against the parsed English file
+pub fn check_locale(locale: &str, content: &str, english: &toml::Value) -> Vec<Issue> {
+    let mut value: toml::Value = match toml::from_str(content) {
+        Ok(value) => value,
+        Err(e) => return vec![issue(Severity::Error, locale, "", format!("does not parse: {}", e))],
+    };
+
+    let mut issues = Vec::new();
+    compare(locale, &mut Vec::new(), &value, english, &mut issues);
+
+    fill_missing(&mut value, english);
+    match value.clone().try_into::<Messages>() {
+        Ok(messages) if locale == DEFAULT_LOCALE => {
+            // Keys serde skipped over never reach the bot
+            if let Ok(loaded) = toml::Value::try_from(&messages) {
+                unused(locale, &mut Vec::new(), &value, &loaded, &mut issues);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => issues.push(issue(Severity::Error, locale, "", format!("does not match the schema: {}", e))),
+    }
+    issues
+}
+
+fn issue(severity: Severity, locale: &str, key: &str, message: String) -> Issue {
+    Issue { severity, locale: locale.to_string(), key: key.to_string(), message }
+}
+
+/// Walk `value` alongside `english`: missing and extra keys, placeholders and length limits
+fn compare<'a>(
+    locale: &str,
+    path: &mut Vec<&'a str>,
+    value: &'a toml::Value,
+    english: &toml::Value,
+    issues: &mut Vec<Issue>,
+) {
+    match (value, english) {
+        (toml::Value::Table(table), toml::Value::Table(english_table)) => {
+            for key in english_table.keys().filter(|key| !table.contains_key(*key)) {
+                let key = path.iter().copied().chain([key.as_str()]).collect::<Vec<_>>().join(".");
+                issues.push(issue(Severity::Warning, locale, &key, "untranslated, English is used".to_string()));
+            }
+            for (key, child) in table {
+                path.push(key);
+                match english_table.get(key) {
+                    Some(english_child) => compare(locale, path, child, english_child, issues),
+                    None => issues.push(issue(Severity::Error, locale, &path.join("."), "not in en.toml, so it is never used".to_string())),
+                }
+                path.pop();
+            }
+        }
+        (toml::Value::String(text), toml::Value::String(english_text)) => {
+            let key = path.join(".");
+            let found = placeholders(text);
+            let expected = placeholders(english_text);
+            if found != expected {
+                issues.push(issue(
+                    Severity::Error,
+                    locale,
+                    &key,
+                    format!("placeholders {:?} differ from English {:?}", found, expected),
+                ));
+            }
+            if let Some(limit) = limit(path) {
+                let length = text.chars().count();
+                if length > limit {
+                    issues.push(issue(Severity::Error, locale, &key, format!("{} characters, Discord allows {}", length, limit)));
+                }
+            }
+        }
+        (value, english) if value.type_str() != english.type_str() => {
+            issues.push(issue(
+                Severity::Error,
+                locale,
+                &path.join("."),
+                format!("is a {} but English has a {}", value.type_str(), english.type_str()),
+            ));
+        }
+        _ => {}
+    }
+}
+
+/// Keys of the English file that deserializing into `Messages` dropped
+fn unused<'a>(
+    locale: &str,
+    path: &mut Vec<&'a str>,
+    value: &'a toml::Value,
+    loaded: &toml::Value,
+    issues: &mut Vec<Issue>,
+) {
+    let (toml::Value::Table(table), toml::Value::Table(loaded)) = (value, loaded) else {
+        return;
+    };
+    for (key, child) in table {
+        path.push(key);
+        match loaded.get(key) {
+            Some(loaded_child) => unused(locale, path, child, loaded_child, issues),
+            None => issues.push(issue(Severity::Warning, locale, &path.join("."), "not read by the bot".to_string())),
+        }
+        path.pop();
+    }
+}
+
+fn placeholders(text: &str) -> BTreeSet<&str> {
+    PLACEHOLDER.find_iter(text).map(|m| m.as_str()).collect()
+}
+
+/// Discord's length limit for the text at `path`, where one applies
+fn limit(path: &[&str]) -> Option<usize> {
+    let last = *path.last()?;
+    if path.first() == Some(&"commands") {
+        return Some(match last {
+            _ if path.len() >= 2 && path[path.len() - 2] == "choices" => 100,
+            "name" => 32,
+            _ => 100,
+        });
+    }
+    if path.contains(&"embeds") {
+        return Some(match last {
+            "title" => 256,
+            _ if last.ends_with("_title") || last.ends_with("_field") => 256,
+            "footer" => 2048,
+            "description" => 4096,
+            _ => 1024,
+        });
+    }
+    // System prompts are sent to the model, not to Discord
+    if path.starts_with(&["ai", "prompt"]) {
+        return None;
+    }
+    Some(2000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang::{embedded_locales, LOCALES};
+
+    fn english() -> toml::Value {
+        toml::from_str(LOCALES[0].1).unwrap()
+    }
+
+    fn errors(issues: &[Issue]) -> Vec<String> {
+        issues.iter().filter(|issue| issue.severity == Severity::Error).map(|issue| issue.to_string()).collect()
+    }
+
+    #[test]
+    fn shipped_locale_files_have_no_errors() {
+        let issues = check_sources(&embedded_locales());
+        assert!(errors(&issues).is_empty(), "{:#?}", errors(&issues));
+        // English is the schema: nothing in it is missing or unread
+        assert!(issues.iter().all(|issue| issue.locale != DEFAULT_LOCALE), "{:#?}", issues);
+    }
+
+    #[test]
+    fn placeholder_mismatch_is_an_error() {
+        let issues = check_locale("xx", "[responses]\nhello = \"Hola, {user}!\"", &english());
+        let issue = issues.iter().find(|issue| issue.key == "responses.hello").unwrap();
+        assert_eq!(issue.severity, Severity::Error);
+        assert!(issue.message.contains("{username}"));
+    }
+
+    #[test]
+    fn extra_keys_are_errors_and_missing_tables_are_reported_once() {
+        let issues = check_locale("xx", "[responses]\nhelo = \"Hola\"", &english());
+        let extra = issues.iter().find(|issue| issue.key == "responses.helo").unwrap();
+        assert_eq!(extra.severity, Severity::Error);
+
+        let missing: Vec<_> = issues.iter().filter(|issue| issue.key.starts_with("embeds")).collect();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].key, "embeds");
+        assert_eq!(missing[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn discord_limits_and_wrong_types_are_errors() {
+        let long_title = "x".repeat(257);
+        let content = format!("[embeds.ping]\ntitle = \"{}\"\n[commands.ping]\nname = \"{}\"\n[responses]\ninfo = 1", long_title, "p".repeat(33));
+        let issues = check_locale("xx", &content, &english());
+        for key in ["embeds.ping.title", "commands.ping.name", "responses.info"] {
+            let issue = issues.iter().find(|issue| issue.key == key).unwrap_or_else(|| panic!("{} not reported", key));
+            assert_eq!(issue.severity, Severity::Error);
+        }
+        // The integer can't become a String either
+        assert!(issues.iter().any(|issue| issue.key.is_empty() && issue.message.contains("schema")));
+    }
+
+    #[test]
+    fn english_keys_outside_the_schema_are_unused() {
+        let content = format!("{}\n[retired]\ntext = \"old\"\n", LOCALES[0].1);
+        let issues = check_locale(DEFAULT_LOCALE, &content, &toml::from_str(&content).unwrap());
+        let issue = issues.iter().find(|issue| issue.key == "retired").unwrap();
+        assert_eq!(issue.severity, Severity::Warning);
+    }
+}
@@ -0,0 +1,202 @@
+//! Discord attachment URLs in bot_images.toml are signed and stop working once
+//! their `ex=` time passes. Images kept as files under `assets/images/` are
+//! uploaded once to a storage channel; fetching that message again returns
+//! freshly signed URLs, which replace the ones from bot_images.toml.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+use chrono::{DateTime, Utc};
+use serenity::all::{ChannelId, CreateAttachment, CreateMessage, Http, MessageId};
+use tokio::task::JoinSet;
+use crate::data::{DataManager, StoredImage};
+use super::assets::AssetLibrary;
+use super::ImageManager;
+
+/// Where local copies live, as `<category>/<name>.<ext>` inside the assets directory
+const IMAGES_DIR: &str = "assets/images";
+const EXTENSIONS: &[&str] = &["png", "webp", "gif", "jpg", "jpeg"];
+
+/// How often signed URLs are refreshed; Discord signs them for about a day
+pub const REFRESH_INTERVAL_SECS: u64 = 12 * 60 * 60;
+
+/// How long a reachability check may take before the image counts as unreachable
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Channel the bot uploads local images to (`IMAGE_STORAGE_CHANNEL_ID`)
+pub fn storage_channel() -> Option<ChannelId> {
+    std::env::var("IMAGE_STORAGE_CHANNEL_ID")
+        .ok()
+        .and_then(|id| id.trim().parse::<u64>().ok())
+        .filter(|id| *id != 0)
+        .map(ChannelId::new)
+}
+
+/// When a signed Discord CDN URL expires, from its hex `ex=` parameter
+pub fn url_expiry(url: &str) -> Option<DateTime<Utc>> {
+    let query = url.split_once('?')?.1;
+    let expiry = query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("ex="))?;
+    let seconds = i64::from_str_radix(expiry, 16).ok()?;
+    DateTime::from_timestamp(seconds, 0)
+}
+
+/// Local copy of an image, if one exists
+pub fn local_file(dir: &Path, category: &str, name: &str) -> Option<PathBuf> {
+    let base = dir.join(IMAGES_DIR).join(category);
+    EXTENSIONS
+        .iter()
+        .map(|extension| base.join(format!("{}.{}", name, extension)))
+        .find(|path| path.is_file())
+}
+
+/// Size and modification time, to notice a replaced file
+fn fingerprint(path: &Path) -> Option<String> {
+    let metadata = fs::metadata(path).ok()?;
+    let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some(format!("{}-{}", metadata.len(), modified))
+}
+
+/// Upload new or changed local images and point every uploaded image at a fresh URL
+///
+/// Returns how many images now use an uploaded copy.
+pub async fn sync_images(
+    http: &Http,
+    assets: &AssetLibrary,
+    data_manager: &DataManager,
+    channel_id: ChannelId,
+) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+    let entries: Vec<(String, String)> = assets
+        .current()
+        .images
+        .entries()
+        .into_iter()
+        .map(|(category, name, _)| (category.to_string(), name.to_string()))
+        .collect();
+
+    let mut urls = HashMap::new();
+    for (category, name) in entries {
+        let Some(path) = local_file(assets.dir(), &category, &name) else {
+            continue;
+        };
+        let key = format!("{}.{}", category, name);
+        let fingerprint = fingerprint(&path).unwrap_or_default();
+
+        let stored = data_manager
+            .get_stored_image(&key)
+            .filter(|stored| stored.channel_id == channel_id.get() && stored.fingerprint == fingerprint);
+        let url = match stored {
+            Some(stored) => match channel_id.message(http, MessageId::new(stored.message_id)).await {
+                Ok(message) => message.attachments.first().map(|attachment| attachment.url.clone()),
+                // Deleted from the channel: upload it again below
+                Err(_) => None,
+            },
+            None => None,
+        };
+        let url = match url {
+            Some(url) => url,
+            None => match upload(http, channel_id, &key, &path).await {
+                Ok((message_id, url)) => {
+                    data_manager
+                        .set_stored_image(&key, StoredImage {
+                            channel_id: channel_id.get(),
+                            message_id,
+                            fingerprint,
+                        })
+                        .map_err(|e| e.to_string())?;
+                    url
+                }
+                // Keep the remaining images moving; this one is retried on the next refresh
+                Err(e) => {
+                    eprintln!("⚠️  Could not upload image {}: {}", key, e);
+                    continue;
+                }
+            },
+        };
+        urls.insert(key, url);
+    }
+
+    let count = urls.len();
+    assets.set_image_urls(urls)?;
+    Ok(count)
+}
+
+/// Upload a local image to the storage channel: the message ID and the attachment's URL
+async fn upload(
+    http: &Http,
+    channel_id: ChannelId,
+    key: &str,
+    path: &Path,
+) -> Result<(u64, String), Box<dyn std::error::Error + Send + Sync>> {
+    let attachment = CreateAttachment::path(path).await?;
+    let message = channel_id
+        .send_message(http, CreateMessage::new().content(format!("`{}`", key)).add_file(attachment))
+        .await?;
+    let url = message.attachments.first().map(|attachment| attachment.url.clone()).ok_or("the upload has no attachment")?;
+    Ok((message.id.get(), url))
+}
+
+/// Expired and unreachable image URLs
+///
+/// With `uploading`, images that have a local copy are skipped: their URL gets replaced.
+/// URLs are checked concurrently, each within `CHECK_TIMEOUT`.
+pub async fn check_image_urls(images: &ImageManager, dir: &Path, uploading: bool) -> Vec<String> {
+    let client = match reqwest::Client::builder().timeout(CHECK_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => return vec![format!("URLs could not be checked ({})", e)],
+    };
+
+    let now = Utc::now();
+    let mut problems = Vec::new();
+    let mut checks = JoinSet::new();
+    for (category, name, url) in images.entries() {
+        if uploading && local_file(dir, category, name).is_some() {
+            continue;
+        }
+        if let Some(expiry) = url_expiry(url).filter(|expiry| *expiry <= now) {
+            problems.push(format!("{}.{} expired on {}", category, name, expiry.format("%Y-%m-%d")));
+            continue;
+        }
+        let request = client.head(url).send();
+        let key = format!("{}.{}", category, name);
+        checks.spawn(async move {
+            match request.await {
+                Ok(response) if response.status().is_success() => None,
+                Ok(response) => Some(format!("{} is unreachable ({})", key, response.status())),
+                Err(e) => Some(format!("{} is unreachable ({})", key, e)),
+            }
+        });
+    }
+    while let Some(result) = checks.join_next().await {
+        problems.extend(result.ok().flatten());
+    }
+    problems.sort();
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expiry_is_read_from_the_signed_url() {
+        let url = "https://cdn.discordapp.com/attachments/1/2/Pointing.png?ex=689098ab&is=688f472b&hm=abc&";
+        assert_eq!(url_expiry(url).unwrap().timestamp(), 0x689098ab);
+        assert_eq!(url_expiry("https://example.com/image.png"), None);
+        assert_eq!(url_expiry("https://example.com/image.png?ex=zz"), None);
+    }
+
+    #[test]
+    fn local_files_are_found_by_category_and_name() {
+        let dir = std::env::temp_dir().join(format!("lorian-images-{}", std::process::id()));
+        fs::create_dir_all(dir.join(IMAGES_DIR).join("avatar")).unwrap();
+        fs::write(dir.join(IMAGES_DIR).join("avatar").join("pointing.webp"), b"image").unwrap();
+
+        assert_eq!(local_file(&dir, "avatar", "pointing"), Some(dir.join(IMAGES_DIR).join("avatar").join("pointing.webp")));
+        assert_eq!(local_file(&dir, "avatar", "head"), None);
+        assert!(fingerprint(&dir.join(IMAGES_DIR).join("avatar").join("pointing.webp")).unwrap().starts_with("5-"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
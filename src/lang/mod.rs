@@ -1,8 +1,19 @@
-use serde::Deserialize;
-use std::collections::HashMap;
-
-#[derive(Debug, Deserialize)]
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use serenity::model::channel::ReactionType;
+use serenity::model::id::EmojiId;
+use chrono::NaiveDate;
+use crate::ai::TokenUsage;
+use crate::ai::tools::ToolDenied;
+use crate::data::{AIUserUsage, CommissionAvailability, CommissionStatus};
+
+pub mod assets;
+pub mod check;
+pub mod image_store;
+
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Messages {
+    pub language: LanguageInfo,
     pub commands: Commands,
     pub responses: Responses,
     pub embeds: Embeds,
@@ -16,31 +27,34 @@ pub struct Messages {
     pub safety: SafetySystem,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct Commands {
-    pub ping: CommandInfo,
-    pub info: CommandInfo,
-    pub hello: CommandInfo,
-    pub help: CommandInfo,
-    pub images: CommandInfo,
-    pub userinfo: CommandInfo,
-    pub stats: CommandInfo,
-    pub purge: CommandInfo,
-    pub reminder: CommandInfo,
-    pub commission_setup: CommandInfo,
-    pub commission_close: CommandInfo,
-    pub ticket_setup: CommandInfo,
-    pub ticket_close: CommandInfo,
-    pub feedback_setup: CommandInfo,
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LanguageInfo {
+    /// Name of the language in itself ("Español")
+    pub name: String,
+    pub auto: String,
+    pub user_set: String,
+    pub user_auto: String,
+    pub server_set: String,
+    pub server_auto: String,
+    pub server_only: String,
 }
 
-#[derive(Debug, Deserialize)]
+/// Slash command text keyed by the command's English name in code
+pub type Commands = HashMap<String, CommandInfo>;
+
+/// Name and description of a command, subcommand or option; `options` nests
+/// the same way Discord does and `choices` maps a choice value to its label
+#[derive(Debug, Deserialize, Serialize)]
 pub struct CommandInfo {
     pub name: String,
     pub description: String,
+    #[serde(default)]
+    pub options: HashMap<String, CommandInfo>,
+    #[serde(default)]
+    pub choices: HashMap<String, String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Responses {
     #[allow(dead_code)]
     pub ping: String,
@@ -48,9 +62,15 @@ pub struct Responses {
     pub hello: String,
     pub help: String,
     pub unknown_command: String,
+    pub denied_owner: String,
+    pub denied_permission: String,
+    pub denied_capability: String,
+    pub missing_subcommand: String,
+    pub unknown_subcommand: String,
+    pub missing_option: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Embeds {
     pub ping: PingEmbed,
     pub images: ImagesEmbed,
@@ -62,9 +82,10 @@ pub struct Embeds {
     pub commission: CommissionEmbed,
     pub commission_created: CommissionCreatedEmbed,
     pub commission_closed: CommissionClosedEmbed,
+    pub capability: CapabilityEmbed,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct PingEmbed {
     pub title: String,
     pub description: String,
@@ -78,14 +99,14 @@ pub struct PingEmbed {
     pub thumbnail: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct ImagesEmbed {
     pub title: String,
     pub description: String,
     pub footer: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct UserInfoEmbed {
     pub title: String,
     pub description: String,
@@ -105,18 +126,29 @@ pub struct UserInfoEmbed {
     pub thumbnail: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct PurgeEmbed {
     pub title: String,
     pub description: String,
     pub success_message: String,
-    pub error_permission: String,
     pub error_invalid_amount: String,
+    pub error_invalid_regex: String,
+    pub error_invalid_message_id: String,
     pub error_failed: String,
+    pub nothing_found: String,
+    pub filters_field: String,
+    pub no_filters: String,
+    pub scanned_field: String,
+    pub old_field: String,
+    pub old_deleted: String,
+    pub old_skipped: String,
+    pub old_limit: String,
+    pub log_title: String,
+    pub log_description: String,
     pub footer: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct ReminderEmbed {
     pub title: String,
     pub description: String,
@@ -129,7 +161,6 @@ pub struct ReminderEmbed {
     pub status_field: String,
     pub error_invalid_time: String,
     pub error_no_channel: String,
-    pub error_permission: String,
     pub error_failed: String,
     pub footer: String,
     pub visibility_public: String,
@@ -141,7 +172,7 @@ pub struct ReminderEmbed {
     pub status_disabled: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct ReminderNotificationEmbed {
     pub title: String,
     pub description: String,
@@ -150,7 +181,7 @@ pub struct ReminderNotificationEmbed {
     pub footer: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct HelpEmbed {
     pub title: String,
     pub description: String,
@@ -160,21 +191,10 @@ pub struct HelpEmbed {
     pub commands: HelpCommands,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct HelpCommands {
-    pub ping: HelpCommand,
-    pub info: HelpCommand,
-    pub hello: HelpCommand,
-    pub stats: HelpCommand,
-    pub images: HelpCommand,
-    pub userinfo: HelpCommand,
-    pub purge: HelpCommand,
-    pub reminder: HelpCommand,
-    pub commission_setup: HelpCommand,
-    pub commission_close: HelpCommand,
-}
+/// Help pages keyed like `[commands.*]`
+pub type HelpCommands = HashMap<String, HelpCommand>;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct HelpCommand {
     pub title: String,
     pub description: String,
@@ -182,7 +202,7 @@ pub struct HelpCommand {
     pub details: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Errors {
     #[allow(dead_code)]
     pub client_creation: String,
@@ -192,28 +212,36 @@ pub struct Errors {
     pub token_missing: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct System {
     pub bot_connected: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct AISystem {
     pub embeds: AIEmbeds,
     pub emotions: AIEmotions,
     pub prompt: AIPrompt,
     pub messages: AIMessages,
     pub data: AIData,
+    pub tools: AITools,
+    pub limits: AILimits,
+    pub memory: AIMemory,
+    pub moderation: AIModeration,
+    pub replies: AIReplies,
+    pub usage: AIUsageMessages,
+    pub personas: AIPersonaMessages,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct AIEmbeds {
     pub title_format: String,
     pub footer_format: String,
     pub no_context: String,
+    pub sources_field: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct AIEmotions {
     pub happy: String,
     pub excited: String,
@@ -227,7 +255,7 @@ pub struct AIEmotions {
     pub neutral: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct AIPrompt {
     pub system_intro: String,
     pub owner_name_label: String,
@@ -253,11 +281,10 @@ pub struct AIPrompt {
     pub emoji_star: String,
     pub emoji_stats: String,
     pub emojis_instruction: String,
-    pub context_header: String,
     pub user_message_format: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct AIMessages {
     pub context_saved: String,
     pub response_generated: String,
@@ -265,7 +292,7 @@ pub struct AIMessages {
     pub error_saving_context: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct AIData {
     pub stats_header: String,
     pub button_messages_label: String,
@@ -274,7 +301,118 @@ pub struct AIData {
     pub last_updated_label: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AITools {
+    pub confirm_title: String,
+    pub confirm_button: String,
+    pub cancel_button: String,
+    pub confirm_footer: String,
+    pub reminder_confirm: String,
+    pub ticket_confirm: String,
+    pub ticket_confirm_reason: String,
+    pub reminder_created: String,
+    pub ticket_created: String,
+    pub ticket_failed: String,
+    pub ticket_reason_title: String,
+    pub cancelled: String,
+    pub not_requester: String,
+    pub expired: String,
+    pub denied_title: String,
+    pub denied_guild_only: String,
+    pub denied_ticket_open: String,
+    pub denied_too_many_reminders: String,
+    pub invalid_call: String,
+    pub github_empty: String,
+    pub github_failed: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AIMemory {
+    pub show_title: String,
+    pub summary_field: String,
+    pub conversation_field: String,
+    pub nothing_stored: String,
+    pub messages_field: String,
+    pub messages_value: String,
+    pub status_field: String,
+    pub status_on: String,
+    pub status_off: String,
+    pub footer: String,
+    pub export_dm: String,
+    pub export_sent: String,
+    pub export_dm_failed: String,
+    pub forget_done: String,
+    pub opt_out_on: String,
+    pub opt_out_off: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AIReplies {
+    pub thread_name: String,
+    pub title: String,
+    pub guild_only: String,
+    pub allowed: String,
+    pub disallowed: String,
+    pub list_none: String,
+    pub list_channels: String,
+    pub dms_on_label: String,
+    pub dms_off_label: String,
+    pub dms_on: String,
+    pub dms_off: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AIModeration {
+    pub title: String,
+    pub input_refused: String,
+    pub output_refused: String,
+    pub flagged_title: String,
+    pub flagged_empty: String,
+    pub flagged_footer: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AILimits {
+    pub title: String,
+    pub cooldown: String,
+    pub busy: String,
+    pub quota_reached: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AIUsageMessages {
+    pub title: String,
+    pub nothing_recorded: String,
+    pub ranking_line: String,
+    pub messages_field: String,
+    pub messages_value: String,
+    pub tokens_field: String,
+    pub tokens_value: String,
+    pub cost_field: String,
+    pub cost_value: String,
+    pub quotas_field: String,
+    pub quota_default: String,
+    pub unlimited: String,
+    pub footer: String,
+    pub quota_invalid: String,
+    pub quota_title: String,
+    pub quota_role_set: String,
+    pub quota_default_set: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AIPersonaMessages {
+    pub list_title: String,
+    pub default_marker: String,
+    pub channels_field: String,
+    pub no_assignments: String,
+    pub list_footer: String,
+    pub unknown: String,
+    pub updated_title: String,
+    pub updated: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 pub struct CommissionEmbed {
     pub title: String,
     pub description: String,
@@ -293,7 +431,7 @@ pub struct CommissionEmbed {
     pub button_text: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct CommissionCreatedEmbed {
     pub title: String,
     pub description: String,
@@ -309,7 +447,7 @@ pub struct CommissionCreatedEmbed {
     pub footer: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct CommissionClosedEmbed {
     pub title: String,
     pub description: String,
@@ -319,15 +457,30 @@ pub struct CommissionClosedEmbed {
     pub footer: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CapabilityEmbed {
+    pub guild_only: String,
+    pub unknown_capability: String,
+    pub missing_target: String,
+    pub list_title: String,
+    pub list_description: String,
+    pub nobody: String,
+    pub holders_separator: String,
+    pub granted: String,
+    pub revoked: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 pub struct CommissionSystem {
     pub messages: CommissionMessages,
+    pub intake: CommissionIntake,
+    pub lifecycle: CommissionLifecycle,
+    pub availability: CommissionAvailabilityMessages,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct CommissionMessages {
     pub setup_success: String,
-    pub setup_error_permission: String,
     pub setup_error_channel: String,
     pub channel_created: String,
     pub channel_creation_failed: String,
@@ -338,19 +491,99 @@ pub struct CommissionMessages {
     pub already_has_commission: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CommissionIntake {
+    pub modal_title: String,
+    pub project_type_label: String,
+    pub project_type_placeholder: String,
+    pub budget_label: String,
+    pub budget_placeholder: String,
+    pub deadline_label: String,
+    pub deadline_placeholder: String,
+    pub description_label: String,
+    pub description_placeholder: String,
+    pub brief_title: String,
+    pub brief_description: String,
+    pub project_type_field: String,
+    pub budget_field: String,
+    pub deadline_field: String,
+    pub description_field: String,
+    pub brief_footer: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CommissionLifecycle {
+    pub status_inquiry: String,
+    pub status_quoted: String,
+    pub status_accepted: String,
+    pub status_in_progress: String,
+    pub status_delivered: String,
+    pub status_closed: String,
+    pub not_commission_channel: String,
+    pub not_client: String,
+    pub invalid_status: String,
+    pub unknown_milestone: String,
+    pub milestone_already_completed: String,
+    pub invalid_date: String,
+    pub quote_title: String,
+    pub quote_description: String,
+    pub quote_amount_field: String,
+    pub quote_details_field: String,
+    pub quote_accept_button: String,
+    pub quote_decline_button: String,
+    pub quote_sent: String,
+    pub quote_accepted: String,
+    pub quote_declined: String,
+    pub quote_outdated: String,
+    pub progress_title: String,
+    pub progress_status_field: String,
+    pub progress_quote_field: String,
+    pub progress_milestones_field: String,
+    pub progress_no_milestones: String,
+    pub progress_due: String,
+    pub progress_footer: String,
+    pub milestone_added: String,
+    pub milestone_completed: String,
+    pub status_updated: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CommissionAvailabilityMessages {
+    pub status_open: String,
+    pub status_waitlist: String,
+    pub status_closed: String,
+    pub panel_field: String,
+    pub panel_open: String,
+    pub panel_waitlist: String,
+    pub panel_closed: String,
+    pub waitlist_button: String,
+    pub waitlist_joined: String,
+    pub waitlist_already: String,
+    pub updated: String,
+    pub panel_missing: String,
+    pub queue_title: String,
+    pub queue_empty: String,
+    pub queue_entry: String,
+    pub queue_eta_requested: String,
+    pub queue_waitlist_field: String,
+    pub queue_waitlist_entry: String,
+    pub queue_waitlist_empty: String,
+    pub queue_footer: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 pub struct TicketSystem {
     pub embeds: TicketEmbeds,
     pub messages: TicketMessages,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct TicketEmbeds {
     pub setup: TicketSetupEmbed,
     pub created: TicketCreatedEmbed,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct TicketSetupEmbed {
     pub title: String,
     pub description: String,
@@ -359,7 +592,7 @@ pub struct TicketSetupEmbed {
     pub thumbnail: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct TicketCreatedEmbed {
     pub title: String,
     pub description: String,
@@ -367,7 +600,7 @@ pub struct TicketCreatedEmbed {
     pub close_button_text: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct TicketMessages {
     pub setup_success: String,
     pub setup_error_permission: String,
@@ -382,19 +615,19 @@ pub struct TicketMessages {
     pub owner_notification: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct FeedbackSystem {
     pub embeds: FeedbackEmbeds,
     pub messages: FeedbackMessages,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct FeedbackEmbeds {
     pub setup: FeedbackSetupEmbed,
     pub message: FeedbackMessageEmbed,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct FeedbackSetupEmbed {
     pub title: String,
     pub description: String,
@@ -411,17 +644,15 @@ pub struct FeedbackSetupEmbed {
     pub thumbnail: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct FeedbackMessageEmbed {
     pub title: String,
     pub rating_field: String,
     pub footer: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct FeedbackMessages {
-    pub setup_error_permission_title: String,
-    pub setup_error_permission: String,
     pub setup_success_title: String,
     pub setup_success: String,
     pub setup_success_footer: String,
@@ -429,7 +660,7 @@ pub struct FeedbackMessages {
     pub no_votes_yet: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct SafetySystem {
     pub verification: SafetyVerification,
     pub responses: SafetyResponses,
@@ -437,7 +668,7 @@ pub struct SafetySystem {
     pub honeypot: SafetyHoneypot,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct SafetyVerification {
     pub title: String,
     pub description: String,
@@ -448,7 +679,7 @@ pub struct SafetyVerification {
     pub unsubscribe_button: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct SafetyResponses {
     pub verified: String,
     pub already_verified: String,
@@ -460,29 +691,74 @@ pub struct SafetyResponses {
     pub subscription_failed: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct SafetyAnnouncement {
     pub title: String,
     pub source_label: String,
     pub footer: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct SafetyHoneypot {
     pub title: String,
     pub intro: String,
-    pub english: String,
-    pub spanish: String,
-    pub portuguese_brazil: String,
-    pub russian: String,
-    pub chinese_simplified: String,
-    pub french: String,
+    /// One-line warning shown in every loaded language
+    pub warning: String,
     pub footer: String,
     pub security_dm: String,
 }
 
+/// Locale used for console output, command registration and anything without a user or server
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Every locale Discord accepts in name and description localizations
+const DISCORD_LOCALES: &[&str] = &[
+    "id", "da", "de", "en-GB", "en-US", "es-ES", "es-419", "fr", "hr", "it", "lt", "hu",
+    "nl", "no", "pl", "pt-BR", "ro", "fi", "sv-SE", "vi", "tr", "cs", "el", "bg", "ru",
+    "uk", "hi", "th", "zh-CN", "ja", "zh-TW", "ko",
+];
+
+/// Locale files compiled into the binary. English comes first: it is complete,
+/// and every other locale falls back to it for keys it doesn't translate.
+const LOCALES: &[(&str, &str)] = &[
+    (DEFAULT_LOCALE, include_str!("../../lang/en.toml")),
+    ("es", include_str!("../../lang/es.toml")),
+    ("pt-BR", include_str!("../../lang/pt-BR.toml")),
+    ("ru", include_str!("../../lang/ru.toml")),
+    ("zh-CN", include_str!("../../lang/zh-CN.toml")),
+    ("fr", include_str!("../../lang/fr.toml")),
+];
+
+/// `LOCALES` as owned `(locale, file contents)` pairs
+fn embedded_locales() -> Vec<(String, String)> {
+    LOCALES
+        .iter()
+        .map(|(locale, content)| (locale.to_string(), content.to_string()))
+        .collect()
+}
+
+/// Built-in copies of the image and emoji files
+const EMBEDDED_IMAGES: &str = include_str!("../../bot_images.toml");
+const EMBEDDED_EMOJIS: &str = include_str!("../../bot_emojis.toml");
+
+/// Copy every key of `base` that `value` is missing, recursing into tables
+fn fill_missing(value: &mut toml::Value, base: &toml::Value) {
+    let (toml::Value::Table(table), toml::Value::Table(base)) = (value, base) else {
+        return;
+    };
+    for (key, base_value) in base {
+        match table.get_mut(key) {
+            Some(value) => fill_missing(value, base_value),
+            None => {
+                table.insert(key.clone(), base_value.clone());
+            }
+        }
+    }
+}
+
 pub struct LanguageManager {
-    messages: Messages,
+    /// Loaded locales in `LOCALES` order
+    locales: Vec<(String, Messages)>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -501,27 +777,82 @@ pub struct BotImages {
 
 #[derive(Debug, Deserialize)]
 pub struct BotEmojis {
-    pub status: HashMap<String, String>,
-    pub confirmations: HashMap<String, String>,
-    pub emotions: HashMap<String, String>,
-    pub technology: HashMap<String, String>,
-    pub actions: HashMap<String, String>,
-    pub interface: HashMap<String, String>,
+    pub status: HashMap<String, Emoji>,
+    pub confirmations: HashMap<String, Emoji>,
+    pub emotions: HashMap<String, Emoji>,
+    pub technology: HashMap<String, Emoji>,
+    pub actions: HashMap<String, Emoji>,
+    pub interface: HashMap<String, Emoji>,
     pub defaults: HashMap<String, String>,
 }
 
+/// `bot_emojis.toml` entry: the emoji alone, or with a Unicode fallback
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum EmojiSpec {
+    Plain(String),
+    WithFallback { emoji: String, fallback: String },
+}
+
+/// An emoji parsed at load, with what to show in text and what to react with
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "EmojiSpec")]
+pub struct Emoji {
+    /// `<:name:id>` or the Unicode emoji
+    pub text: String,
+    pub reaction: ReactionType,
+    /// Unicode shown instead when the bot can't use a custom emoji
+    pub fallback: Option<String>,
+}
+
+impl TryFrom<EmojiSpec> for Emoji {
+    type Error = String;
+
+    fn try_from(spec: EmojiSpec) -> Result<Self, Self::Error> {
+        let (text, fallback) = match spec {
+            EmojiSpec::Plain(text) => (text, None),
+            EmojiSpec::WithFallback { emoji, fallback } => (emoji, Some(fallback)),
+        };
+        let reaction = ReactionType::try_from(text.as_str()).map_err(|_| format!("invalid emoji {:?}", text))?;
+        if let Some(fallback) = &fallback {
+            if !matches!(ReactionType::try_from(fallback.as_str()), Ok(ReactionType::Unicode(_))) {
+                return Err(format!("fallback {:?} of {:?} must be a Unicode emoji", fallback, text));
+            }
+        }
+        Ok(Emoji { text, reaction, fallback })
+    }
+}
+
+impl Emoji {
+    /// ID of a custom emoji, None for Unicode ones
+    pub fn custom_id(&self) -> Option<EmojiId> {
+        match &self.reaction {
+            ReactionType::Custom { id, .. } => Some(*id),
+            _ => None,
+        }
+    }
+}
+
 pub struct ImageManager {
     images: BotImages,
 }
 
 pub struct EmojiManager {
     emojis: BotEmojis,
+    /// `category.name` of custom emojis replaced by their fallback
+    substituted: Vec<String>,
+    /// `category.name` of unusable custom emojis without a fallback
+    unusable: Vec<String>,
 }
 
 impl ImageManager {
     pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
-        let images_content = include_str!("../../bot_images.toml");
-        let images: BotImages = toml::from_str(images_content)?;
+        Self::parse(EMBEDDED_IMAGES)
+    }
+
+    /// Images from the contents of a `bot_images.toml`
+    pub fn parse(content: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let images: BotImages = toml::from_str(content)?;
         
         Ok(ImageManager { images })
     }
@@ -569,6 +900,52 @@ impl ImageManager {
         vec!["avatar", "emotions", "reactions", "talking", "thinking", "showing", "misc", "safety"]
     }
     
+    /// Every `(category, name, url)` entry, excluding `defaults` references
+    pub fn entries(&self) -> Vec<(&str, &str, &str)> {
+        let mut entries: Vec<_> = self
+            .list_categories()
+            .into_iter()
+            .flat_map(|category| {
+                self.category(category)
+                    .into_iter()
+                    .flatten()
+                    .map(move |(name, url)| (category, name.as_str(), url.as_str()))
+            })
+            .collect();
+        entries.sort();
+        entries
+    }
+
+    /// Replace an image's URL; unknown categories are ignored
+    pub fn set_url(&mut self, category: &str, name: &str, url: String) {
+        let images = match category {
+            "avatar" => &mut self.images.avatar,
+            "emotions" => &mut self.images.emotions,
+            "reactions" => &mut self.images.reactions,
+            "talking" => &mut self.images.talking,
+            "thinking" => &mut self.images.thinking,
+            "showing" => &mut self.images.showing,
+            "misc" => &mut self.images.misc,
+            "safety" => &mut self.images.safety,
+            _ => return,
+        };
+        images.insert(name.to_string(), url);
+    }
+
+    fn category(&self, category: &str) -> Option<&HashMap<String, String>> {
+        match category {
+            "avatar" => Some(&self.images.avatar),
+            "emotions" => Some(&self.images.emotions),
+            "reactions" => Some(&self.images.reactions),
+            "talking" => Some(&self.images.talking),
+            "thinking" => Some(&self.images.thinking),
+            "showing" => Some(&self.images.showing),
+            "misc" => Some(&self.images.misc),
+            "safety" => Some(&self.images.safety),
+            _ => None,
+        }
+    }
+
     pub fn list_images_in_category(&self, category: &str) -> Vec<&String> {
         match category {
             "avatar" => self.images.avatar.keys().collect(),
@@ -586,171 +963,631 @@ impl ImageManager {
 
 impl LanguageManager {
     pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
-        let lang_content = include_str!("../../lang/en.toml");
-        let messages: Messages = toml::from_str(lang_content)?;
-        
-        Ok(LanguageManager { messages })
+        Self::from_sources(&embedded_locales())
     }
-    
-    pub fn get(&self) -> &Messages {
-        &self.messages
+
+    /// Locales from `(locale, file contents)` pairs, English first
+    pub fn from_sources(sources: &[(String, String)]) -> Result<Self, Box<dyn std::error::Error>> {
+        let base_content = match sources.first() {
+            Some((locale, content)) if locale == DEFAULT_LOCALE => content,
+            _ => return Err(format!("lang/{}.toml must be loaded first", DEFAULT_LOCALE).into()),
+        };
+        let base: toml::Value = toml::from_str(base_content)
+            .map_err(|e| format!("Failed to parse lang/{}.toml: {}", DEFAULT_LOCALE, e))?;
+
+        let mut locales = Vec::new();
+        for (locale, content) in sources {
+            let mut value: toml::Value = toml::from_str(content)
+                .map_err(|e| format!("Failed to parse lang/{}.toml: {}", locale, e))?;
+            fill_missing(&mut value, &base);
+            let messages: Messages = value
+                .try_into()
+                .map_err(|e| format!("Invalid lang/{}.toml: {}", locale, e))?;
+            locales.push((locale.to_string(), messages));
+        }
+
+        Ok(LanguageManager { locales })
+    }
+
+    /// Messages for a locale (unknown locales get English)
+    pub fn get(&self, locale: &str) -> &Messages {
+        self.locales
+            .iter()
+            .find(|(key, _)| key == locale)
+            .map(|(_, messages)| messages)
+            .unwrap_or(&self.locales[0].1)
+    }
+
+    /// Every loaded locale with its messages, English first
+    pub fn locales(&self) -> impl Iterator<Item = (&str, &Messages)> {
+        self.locales.iter().map(|(key, messages)| (key.as_str(), messages))
+    }
+
+    /// The loaded locale for a Discord locale tag such as `en-US`, `es-419` or `pt-BR`
+    ///
+    /// Tags without an exact match fall back to their language (`es-ES` → `es`).
+    pub fn find(&self, tag: &str) -> Option<&str> {
+        let language = tag.split('-').next().unwrap_or(tag);
+        let keys = || self.locales.iter().map(|(key, _)| key.as_str());
+        keys()
+            .find(|key| key.eq_ignore_ascii_case(tag))
+            .or_else(|| keys().find(|key| key.eq_ignore_ascii_case(language)))
+    }
+
+    /// Locale for a reply: the user's own choice, then their Discord client
+    /// locale, then the server default, then English
+    pub fn resolve(&self, user: Option<&str>, interaction: Option<&str>, guild: Option<&str>) -> &str {
+        [user, interaction, guild]
+            .into_iter()
+            .flatten()
+            .find_map(|tag| self.find(tag))
+            .unwrap_or(DEFAULT_LOCALE)
     }
     
-    pub fn format_hello(&self, username: &str) -> String {
-        self.messages.responses.hello.replace("{username}", username)
+    /// Command text at `path` (command, then subcommand/option names), e.g. `["commission", "quote", "amount"]`
+    pub fn command_info(&self, locale: &str, path: &[&str]) -> Option<&CommandInfo> {
+        let (command, options) = path.split_first()?;
+        options
+            .iter()
+            .try_fold(self.get(locale).commands.get(*command)?, |info, option| info.options.get(*option))
+    }
+
+    /// Discord localizations for a piece of text: every Discord locale served by
+    /// a loaded translation whose `text` differs from the English one
+    pub fn localizations(&self, text: impl Fn(&str) -> Option<String>) -> Vec<(&'static str, String)> {
+        let english = text(DEFAULT_LOCALE);
+        DISCORD_LOCALES
+            .iter()
+            .filter_map(|tag| {
+                let locale = self.find(tag).filter(|locale| *locale != DEFAULT_LOCALE)?;
+                let localized = text(locale).filter(|localized| Some(localized) != english.as_ref())?;
+                Some((*tag, localized))
+            })
+            .collect()
+    }
+
+    /// Reply to /language: `language` is the chosen locale, None for automatic
+    pub fn format_language_user_set(&self, locale: &str, language: Option<&str>) -> String {
+        let messages = &self.get(locale).language;
+        match language {
+            Some(language) => messages.user_set.replace("{language}", &self.get(language).language.name),
+            None => messages.user_auto.clone(),
+        }
+    }
+
+    pub fn format_language_server_set(&self, locale: &str, language: Option<&str>) -> String {
+        let messages = &self.get(locale).language;
+        match language {
+            Some(language) => messages.server_set.replace("{language}", &self.get(language).language.name),
+            None => messages.server_auto.clone(),
+        }
+    }
+
+    pub fn format_hello(&self, locale: &str, username: &str) -> String {
+        self.get(locale).responses.hello.replace("{username}", username)
+    }
+
+    pub fn format_denied_capability(&self, locale: &str, capability: &str) -> String {
+        self.get(locale).responses.denied_capability.replace("{capability}", capability)
+    }
+
+    pub fn format_missing_option(&self, locale: &str, option: &str) -> String {
+        self.get(locale).responses.missing_option.replace("{option}", option)
     }
     
-    pub fn format_bot_connected(&self, bot_name: &str) -> String {
-        self.messages.system.bot_connected.replace("{bot_name}", bot_name)
+    pub fn format_bot_connected(&self, locale: &str, bot_name: &str) -> String {
+        self.get(locale).system.bot_connected.replace("{bot_name}", bot_name)
     }
     
-    pub fn format_ping_embed_latency(&self, latency: u64) -> String {
-        self.messages.embeds.ping.latency_value.replace("{latency}", &latency.to_string())
+    pub fn format_ping_embed_latency(&self, locale: &str, latency: u64) -> String {
+        self.get(locale).embeds.ping.latency_value.replace("{latency}", &latency.to_string())
     }
     
-    pub fn format_ping_embed_uptime(&self, uptime: &str) -> String {
-        self.messages.embeds.ping.uptime_value.replace("{uptime}", uptime)
+    pub fn format_ping_embed_uptime(&self, locale: &str, uptime: &str) -> String {
+        self.get(locale).embeds.ping.uptime_value.replace("{uptime}", uptime)
     }
     
-    pub fn format_ping_embed_memory(&self, memory: f64) -> String {
-        self.messages.embeds.ping.memory_value.replace("{memory}", &format!("{:.1}", memory))
+    pub fn format_ping_embed_memory(&self, locale: &str, memory: f64) -> String {
+        self.get(locale).embeds.ping.memory_value.replace("{memory}", &format!("{:.1}", memory))
     }
     
-    pub fn format_images_embed_footer(&self, total_images: usize) -> String {
-        self.messages.embeds.images.footer.replace("{total_images}", &total_images.to_string())
+    pub fn format_images_embed_footer(&self, locale: &str, total_images: usize) -> String {
+        self.get(locale).embeds.images.footer.replace("{total_images}", &total_images.to_string())
     }
     
-    pub fn format_userinfo_embed_description(&self, username: &str) -> String {
-        self.messages.embeds.userinfo.description.replace("{username}", username)
+    pub fn format_userinfo_embed_description(&self, locale: &str, username: &str) -> String {
+        self.get(locale).embeds.userinfo.description.replace("{username}", username)
     }
     
-    pub fn format_userinfo_embed_footer(&self, requester: &str) -> String {
-        self.messages.embeds.userinfo.footer.replace("{requester}", requester)
+    pub fn format_userinfo_embed_footer(&self, locale: &str, requester: &str) -> String {
+        self.get(locale).embeds.userinfo.footer.replace("{requester}", requester)
     }
 
     // AI System formatting methods
-    pub fn format_ai_embed_title(&self, emoji: &str) -> String {
-        self.messages.ai.embeds.title_format.replace("{emoji}", emoji)
+    pub fn format_ai_embed_title(&self, locale: &str, emoji: &str) -> String {
+        self.get(locale).ai.embeds.title_format.replace("{emoji}", emoji)
     }
 
-    pub fn format_ai_embed_footer(&self, username: &str, emotion_emoji: &str) -> String {
-        self.messages.ai.embeds.footer_format
+    pub fn format_ai_embed_footer(&self, locale: &str, username: &str, emotion_emoji: &str) -> String {
+        self.get(locale).ai.embeds.footer_format
             .replace("{username}", username)
             .replace("{emotion_emoji}", emotion_emoji)
     }
 
-    pub fn format_ai_prompt_system_intro(&self, owner_name: &str) -> String {
-        self.messages.ai.prompt.system_intro.replace("{owner_name}", owner_name)
+    pub fn format_ai_prompt_system_intro(&self, locale: &str, owner_name: &str) -> String {
+        self.get(locale).ai.prompt.system_intro.replace("{owner_name}", owner_name)
     }
 
-    pub fn format_ai_prompt_assistant_instruction(&self, owner_name: &str) -> String {
-        self.messages.ai.prompt.assistant_instruction.replace("{owner_name}", owner_name)
+    pub fn format_ai_prompt_assistant_instruction(&self, locale: &str, owner_name: &str) -> String {
+        self.get(locale).ai.prompt.assistant_instruction.replace("{owner_name}", owner_name)
     }
 
-    pub fn format_ai_prompt_user_message(&self, message: &str) -> String {
-        self.messages.ai.prompt.user_message_format.replace("{message}", message)
+    pub fn format_ai_prompt_user_message(&self, locale: &str, message: &str) -> String {
+        self.get(locale).ai.prompt.user_message_format.replace("{message}", message)
     }
 
-    pub fn format_ai_context_saved(&self, user_id: &str) -> String {
-        self.messages.ai.messages.context_saved.replace("{user_id}", user_id)
+    pub fn format_ai_context_saved(&self, locale: &str, user_id: &str) -> String {
+        self.get(locale).ai.messages.context_saved.replace("{user_id}", user_id)
     }
 
-    pub fn format_ai_response_generated(&self, username: &str, channel_id: &str) -> String {
-        self.messages.ai.messages.response_generated
+    pub fn format_ai_response_generated(&self, locale: &str, username: &str, channel_id: &str) -> String {
+        self.get(locale).ai.messages.response_generated
             .replace("{username}", username)
             .replace("{channel_id}", channel_id)
     }
 
-    pub fn format_ai_error_generating(&self, error: &str) -> String {
-        self.messages.ai.messages.error_generating.replace("{error}", error)
+    pub fn format_ai_error_generating(&self, locale: &str, error: &str) -> String {
+        self.get(locale).ai.messages.error_generating.replace("{error}", error)
     }
 
-    pub fn format_ai_error_saving_context(&self, error: &str) -> String {
-        self.messages.ai.messages.error_saving_context.replace("{error}", error)
+    pub fn format_ai_error_saving_context(&self, locale: &str, error: &str) -> String {
+        self.get(locale).ai.messages.error_saving_context.replace("{error}", error)
     }
 
-    pub fn format_ai_data_stats(&self, button_count: usize, conversation_count: usize, total_messages: usize, timestamp: i64) -> String {
+    pub fn format_ai_data_stats(&self, locale: &str, button_count: usize, conversation_count: usize, total_messages: usize, timestamp: i64) -> String {
         format!(
             "{}\n{}\n{}\n{}\n{}",
-            &self.messages.ai.data.stats_header,
-            &self.messages.ai.data.button_messages_label.replace("{count}", &button_count.to_string()),
-            &self.messages.ai.data.conversations_label.replace("{count}", &conversation_count.to_string()),
-            &self.messages.ai.data.total_messages_label.replace("{count}", &total_messages.to_string()),
-            &self.messages.ai.data.last_updated_label.replace("{timestamp}", &timestamp.to_string())
+            &self.get(locale).ai.data.stats_header,
+            &self.get(locale).ai.data.button_messages_label.replace("{count}", &button_count.to_string()),
+            &self.get(locale).ai.data.conversations_label.replace("{count}", &conversation_count.to_string()),
+            &self.get(locale).ai.data.total_messages_label.replace("{count}", &total_messages.to_string()),
+            &self.get(locale).ai.data.last_updated_label.replace("{timestamp}", &timestamp.to_string())
         )
     }
 
+    pub fn format_ai_tool_confirm_footer(&self, locale: &str, user: &str) -> String {
+        self.get(locale).ai.tools.confirm_footer.replace("{user}", user)
+    }
+
+    pub fn format_ai_tool_reminder_confirm(&self, locale: &str, message: &str, timestamp: i64) -> String {
+        self.get(locale).ai.tools.reminder_confirm
+            .replace("{time}", &timestamp.to_string())
+            .replace("{message}", message)
+    }
+
+    pub fn format_ai_tool_ticket_confirm(&self, locale: &str, reason: Option<&str>) -> String {
+        match reason {
+            Some(reason) => self.get(locale).ai.tools.ticket_confirm_reason.replace("{reason}", reason),
+            None => self.get(locale).ai.tools.ticket_confirm.clone(),
+        }
+    }
+
+    pub fn format_ai_tool_reminder_created(&self, locale: &str, timestamp: i64) -> String {
+        self.get(locale).ai.tools.reminder_created.replace("{time}", &timestamp.to_string())
+    }
+
+    pub fn format_ai_tool_ticket_created(&self, locale: &str, channel_id: &str) -> String {
+        self.get(locale).ai.tools.ticket_created.replace("{channel}", channel_id)
+    }
+
+    pub fn format_ai_tool_ticket_failed(&self, locale: &str, error: &str) -> String {
+        self.get(locale).ai.tools.ticket_failed.replace("{error}", error)
+    }
+
+    pub fn format_ai_tool_denied(&self, locale: &str, denied: &ToolDenied) -> String {
+        let tools = &self.get(locale).ai.tools;
+        match denied {
+            ToolDenied::GuildOnly => tools.denied_guild_only.clone(),
+            ToolDenied::TicketAlreadyOpen(channel) => tools.denied_ticket_open.replace("{channel}", channel),
+            ToolDenied::TooManyReminders => tools
+                .denied_too_many_reminders
+                .replace("{max}", &crate::ai::tools::MAX_PENDING_REMINDERS.to_string()),
+        }
+    }
+
+    pub fn format_ai_tool_invalid_call(&self, locale: &str, error: &str) -> String {
+        self.get(locale).ai.tools.invalid_call.replace("{error}", error)
+    }
+
+    pub fn format_ai_memory_messages(&self, locale: &str, count: usize, since: i64) -> String {
+        self.get(locale).ai.memory.messages_value
+            .replace("{count}", &count.to_string())
+            .replace("{since}", &since.to_string())
+    }
+
+    pub fn format_ai_thread_name(&self, locale: &str, user: &str) -> String {
+        // Thread names are capped at 100 characters
+        self.get(locale).ai.replies.thread_name.replace("{user}", user).chars().take(100).collect()
+    }
+
+    pub fn format_ai_replies_allowed(&self, locale: &str, channel: &str) -> String {
+        self.get(locale).ai.replies.allowed.replace("{channel}", channel)
+    }
+
+    pub fn format_ai_replies_disallowed(&self, locale: &str, channel: &str) -> String {
+        self.get(locale).ai.replies.disallowed.replace("{channel}", channel)
+    }
+
+    /// Reply channels of a server (empty = only the AI channel) and whether DMs are answered
+    pub fn format_ai_replies_list(&self, locale: &str, channels: &str, dms_enabled: bool) -> String {
+        let replies = &self.get(locale).ai.replies;
+        let dms = if dms_enabled { &replies.dms_on_label } else { &replies.dms_off_label };
+        let template = if channels.is_empty() { &replies.list_none } else { &replies.list_channels };
+        template.replace("{channels}", channels).replace("{dms}", dms)
+    }
+
+    pub fn format_ai_limit_cooldown(&self, locale: &str, time: i64) -> String {
+        self.get(locale).ai.limits.cooldown.replace("{time}", &time.to_string())
+    }
+
+    pub fn format_ai_limit_busy(&self, locale: &str, time: i64) -> String {
+        self.get(locale).ai.limits.busy.replace("{time}", &time.to_string())
+    }
+
+    pub fn format_ai_limit_quota_reached(&self, locale: &str, limit: u32, time: i64) -> String {
+        self.get(locale).ai.limits.quota_reached
+            .replace("{limit}", &limit.to_string())
+            .replace("{time}", &time.to_string())
+    }
+
+    pub fn format_ai_usage_ranking_line(&self, locale: &str, rank: usize, user: &str, usage: &AIUserUsage, today: NaiveDate, cost: f64) -> String {
+        self.get(locale).ai.usage.ranking_line
+            .replace("{rank}", &rank.to_string())
+            .replace("{user}", user)
+            .replace("{messages}", &usage.total_messages.to_string())
+            .replace("{today}", &usage.messages_on(today).to_string())
+            .replace("{prompt}", &usage.prompt_tokens.to_string())
+            .replace("{response}", &usage.response_tokens.to_string())
+            .replace("{cost}", &format!("{:.4}", cost))
+    }
+
+    pub fn format_ai_usage_messages(&self, locale: &str, total: u64, today: u32) -> String {
+        self.get(locale).ai.usage.messages_value
+            .replace("{total}", &total.to_string())
+            .replace("{today}", &today.to_string())
+    }
+
+    pub fn format_ai_usage_tokens(&self, locale: &str, usage: &TokenUsage) -> String {
+        self.get(locale).ai.usage.tokens_value
+            .replace("{prompt}", &usage.prompt_tokens.to_string())
+            .replace("{response}", &usage.response_tokens.to_string())
+    }
+
+    pub fn format_ai_usage_cost(&self, locale: &str, cost: f64) -> String {
+        self.get(locale).ai.usage.cost_value.replace("{cost}", &format!("{:.4}", cost))
+    }
+
+    /// Daily quota as shown to admins; 0 means unlimited
+    pub fn format_ai_quota(&self, locale: &str, daily: u32) -> String {
+        if daily == 0 {
+            self.get(locale).ai.usage.unlimited.clone()
+        } else {
+            daily.to_string()
+        }
+    }
+
+    pub fn format_ai_quota_default(&self, locale: &str, daily: u32) -> String {
+        self.get(locale).ai.usage.quota_default.replace("{quota}", &self.format_ai_quota(locale, daily))
+    }
+
+    pub fn format_ai_quota_role_set(&self, locale: &str, role: &str, daily: u32) -> String {
+        self.get(locale).ai.usage.quota_role_set
+            .replace("{role}", role)
+            .replace("{quota}", &self.format_ai_quota(locale, daily))
+    }
+
+    pub fn format_ai_quota_default_set(&self, locale: &str, daily: u32) -> String {
+        self.get(locale).ai.usage.quota_default_set.replace("{quota}", &self.format_ai_quota(locale, daily))
+    }
+
+    pub fn format_ai_persona_unknown(&self, locale: &str, name: &str) -> String {
+        self.get(locale).ai.personas.unknown.replace("{name}", name)
+    }
+
+    pub fn format_ai_persona_updated(&self, locale: &str, channel: &str, name: &str) -> String {
+        self.get(locale).ai.personas.updated
+            .replace("{channel}", channel)
+            .replace("{name}", name)
+    }
+
+    pub fn format_ai_flagged_footer(&self, locale: &str, limit: usize) -> String {
+        self.get(locale).ai.moderation.flagged_footer.replace("{limit}", &limit.to_string())
+    }
+
     // Purge command formatting methods
-    pub fn format_purge_success(&self, count: u64) -> String {
-        self.messages.embeds.purge.success_message.replace("{count}", &count.to_string())
+    pub fn format_purge_success(&self, locale: &str, count: u64) -> String {
+        self.get(locale).embeds.purge.success_message.replace("{count}", &count.to_string())
+    }
+
+    pub fn format_purge_footer(&self, locale: &str, count: u64) -> String {
+        self.get(locale).embeds.purge.footer.replace("{count}", &count.to_string())
+    }
+
+    pub fn format_purge_error_failed(&self, locale: &str, error: &str) -> String {
+        self.get(locale).embeds.purge.error_failed.replace("{error}", error)
+    }
+
+    pub fn format_purge_error_invalid_regex(&self, locale: &str, error: &str) -> String {
+        self.get(locale).embeds.purge.error_invalid_regex.replace("{error}", error)
+    }
+
+    pub fn format_purge_old_deleted(&self, locale: &str, count: u64) -> String {
+        self.get(locale).embeds.purge.old_deleted.replace("{count}", &count.to_string())
+    }
+
+    pub fn format_purge_old_limit(&self, locale: &str, count: u64) -> String {
+        self.get(locale).embeds.purge.old_limit.replace("{count}", &count.to_string())
+    }
+
+    pub fn format_purge_log_description(&self, locale: &str, user: &str, count: u64, channel: &str) -> String {
+        self.get(locale).embeds.purge.log_description
+            .replace("{user}", user)
+            .replace("{count}", &count.to_string())
+            .replace("{channel}", channel)
     }
 
-    pub fn format_purge_footer(&self, count: u64) -> String {
-        self.messages.embeds.purge.footer.replace("{count}", &count.to_string())
+    // Capability command formatting methods
+    pub fn format_capability_list_description(&self, locale: &str, owner: &str) -> String {
+        self.get(locale).embeds.capability.list_description.replace("{owner}", owner)
     }
 
-    pub fn format_purge_error_failed(&self, error: &str) -> String {
-        self.messages.embeds.purge.error_failed.replace("{error}", error)
+    pub fn format_capability_granted(&self, locale: &str, holders: &str, capability: &str) -> String {
+        self.get(locale).embeds.capability.granted
+            .replace("{holders}", holders)
+            .replace("{capability}", capability)
+    }
+
+    pub fn format_capability_revoked(&self, locale: &str, holders: &str, capability: &str) -> String {
+        self.get(locale).embeds.capability.revoked
+            .replace("{holders}", holders)
+            .replace("{capability}", capability)
     }
 
     // Reminder command formatting methods
-    pub fn format_reminder_success(&self, time: &str) -> String {
-        self.messages.embeds.reminder.success_message.replace("{time}", time)
+    pub fn format_reminder_success(&self, locale: &str, time: &str) -> String {
+        self.get(locale).embeds.reminder.success_message.replace("{time}", time)
     }
 
-    pub fn format_reminder_footer(&self, id: &str) -> String {
-        self.messages.embeds.reminder.footer.replace("{id}", id)
+    pub fn format_reminder_footer(&self, locale: &str, id: &str) -> String {
+        self.get(locale).embeds.reminder.footer.replace("{id}", id)
     }
 
-    pub fn format_reminder_error_failed(&self, error: &str) -> String {
-        self.messages.embeds.reminder.error_failed.replace("{error}", error)
+    pub fn format_reminder_error_failed(&self, locale: &str, error: &str) -> String {
+        self.get(locale).embeds.reminder.error_failed.replace("{error}", error)
     }
 
     // Commission system formatting methods
-    pub fn format_commission_setup_success(&self, channel: &str) -> String {
-        self.messages.commission.messages.setup_success.replace("{channel}", channel)
+    pub fn format_commission_setup_success(&self, locale: &str, channel: &str) -> String {
+        self.get(locale).commission.messages.setup_success.replace("{channel}", channel)
+    }
+
+    pub fn format_commission_channel_created(&self, locale: &str, username: &str) -> String {
+        self.get(locale).commission.messages.channel_created.replace("{username}", username)
+    }
+
+    pub fn format_commission_channel_creation_failed(&self, locale: &str, error: &str) -> String {
+        self.get(locale).commission.messages.channel_creation_failed.replace("{error}", error)
+    }
+
+    pub fn format_commission_close_success(&self, locale: &str) -> String {
+        self.get(locale).commission.messages.close_success.clone()
+    }
+
+    pub fn format_commission_close_error_failed(&self, locale: &str, error: &str) -> String {
+        self.get(locale).commission.messages.close_error_failed.replace("{error}", error)
+    }
+
+    pub fn format_commission_already_exists(&self, locale: &str, channel: &str) -> String {
+        self.get(locale).commission.messages.already_has_commission.replace("{channel}", channel)
+    }
+
+    pub fn format_commission_brief_description(&self, locale: &str, username: &str) -> String {
+        self.get(locale).commission.intake.brief_description.replace("{username}", username)
+    }
+
+    pub fn format_commission_brief_footer(&self, locale: &str, id: &str) -> String {
+        self.get(locale).commission.intake.brief_footer.replace("{id}", id)
+    }
+
+    /// Display label for a commission status
+    pub fn commission_status_label(&self, locale: &str, status: CommissionStatus) -> &str {
+        let lifecycle = &self.get(locale).commission.lifecycle;
+        match status {
+            CommissionStatus::Inquiry => &lifecycle.status_inquiry,
+            CommissionStatus::Quoted => &lifecycle.status_quoted,
+            CommissionStatus::Accepted => &lifecycle.status_accepted,
+            CommissionStatus::InProgress => &lifecycle.status_in_progress,
+            CommissionStatus::Delivered => &lifecycle.status_delivered,
+            CommissionStatus::Closed => &lifecycle.status_closed,
+        }
+    }
+
+    pub fn format_commission_invalid_status(&self, locale: &str, status: CommissionStatus) -> String {
+        self.get(locale).commission.lifecycle.invalid_status.replace("{status}", self.commission_status_label(locale, status))
+    }
+
+    pub fn format_commission_status_updated(&self, locale: &str, status: CommissionStatus) -> String {
+        self.get(locale).commission.lifecycle.status_updated.replace("{status}", self.commission_status_label(locale, status))
+    }
+
+    pub fn format_commission_quote_description(&self, locale: &str, client: &str) -> String {
+        self.get(locale).commission.lifecycle.quote_description.replace("{client}", client)
+    }
+
+    pub fn format_commission_quote_accepted(&self, locale: &str, client: &str) -> String {
+        self.get(locale).commission.lifecycle.quote_accepted.replace("{client}", client)
+    }
+
+    pub fn format_commission_quote_declined(&self, locale: &str, client: &str) -> String {
+        self.get(locale).commission.lifecycle.quote_declined.replace("{client}", client)
+    }
+
+    pub fn format_commission_unknown_milestone(&self, locale: &str, number: usize) -> String {
+        self.get(locale).commission.lifecycle.unknown_milestone.replace("{number}", &number.to_string())
+    }
+
+    pub fn format_commission_milestone_already_completed(&self, locale: &str, number: usize) -> String {
+        self.get(locale).commission.lifecycle.milestone_already_completed.replace("{number}", &number.to_string())
+    }
+
+    pub fn format_commission_milestone_added(&self, locale: &str, number: usize, title: &str) -> String {
+        self.get(locale).commission.lifecycle.milestone_added
+            .replace("{number}", &number.to_string())
+            .replace("{title}", title)
+    }
+
+    pub fn format_commission_milestone_completed(&self, locale: &str, number: usize, title: &str) -> String {
+        self.get(locale).commission.lifecycle.milestone_completed
+            .replace("{number}", &number.to_string())
+            .replace("{title}", title)
+    }
+
+    pub fn format_commission_progress_due(&self, locale: &str, date: &str) -> String {
+        self.get(locale).commission.lifecycle.progress_due.replace("{date}", date)
+    }
+
+    pub fn format_commission_progress_footer(&self, locale: &str, id: &str) -> String {
+        self.get(locale).commission.lifecycle.progress_footer.replace("{id}", id)
+    }
+
+    /// Display label for commission availability
+    pub fn commission_availability_label(&self, locale: &str, availability: CommissionAvailability) -> &str {
+        let messages = &self.get(locale).commission.availability;
+        match availability {
+            CommissionAvailability::Open => &messages.status_open,
+            CommissionAvailability::Waitlist => &messages.status_waitlist,
+            CommissionAvailability::Closed => &messages.status_closed,
+        }
+    }
+
+    pub fn format_commission_availability_updated(&self, locale: &str, availability: CommissionAvailability) -> String {
+        self.get(locale).commission.availability.updated.replace("{availability}", self.commission_availability_label(locale, availability))
     }
 
-    pub fn format_commission_channel_created(&self, username: &str) -> String {
-        self.messages.commission.messages.channel_created.replace("{username}", username)
+    pub fn format_commission_waitlist_joined(&self, locale: &str, position: usize) -> String {
+        self.get(locale).commission.availability.waitlist_joined.replace("{position}", &position.to_string())
     }
 
-    pub fn format_commission_channel_creation_failed(&self, error: &str) -> String {
-        self.messages.commission.messages.channel_creation_failed.replace("{error}", error)
+    pub fn format_commission_waitlist_already(&self, locale: &str, position: usize) -> String {
+        self.get(locale).commission.availability.waitlist_already.replace("{position}", &position.to_string())
     }
 
-    pub fn format_commission_close_success(&self) -> String {
-        self.messages.commission.messages.close_success.clone()
+    pub fn format_commission_queue_entry(&self, locale: &str, position: usize, channel: &str, client: &str, status: CommissionStatus, eta: &str) -> String {
+        self.get(locale).commission.availability.queue_entry
+            .replace("{position}", &position.to_string())
+            .replace("{channel}", channel)
+            .replace("{client}", client)
+            .replace("{status}", self.commission_status_label(locale, status))
+            .replace("{eta}", eta)
     }
 
-    pub fn format_commission_close_error_failed(&self, error: &str) -> String {
-        self.messages.commission.messages.close_error_failed.replace("{error}", error)
+    pub fn format_commission_queue_eta_requested(&self, locale: &str, deadline: &str) -> String {
+        self.get(locale).commission.availability.queue_eta_requested.replace("{deadline}", deadline)
     }
 
-    pub fn format_commission_already_exists(&self, channel: &str) -> String {
-        self.messages.commission.messages.already_has_commission.replace("{channel}", channel)
+    pub fn format_commission_queue_waitlist_field(&self, locale: &str, count: usize) -> String {
+        self.get(locale).commission.availability.queue_waitlist_field.replace("{count}", &count.to_string())
+    }
+
+    pub fn format_commission_queue_waitlist_entry(&self, locale: &str, position: usize, user: &str, joined: i64) -> String {
+        self.get(locale).commission.availability.queue_waitlist_entry
+            .replace("{position}", &position.to_string())
+            .replace("{user}", user)
+            .replace("{joined}", &joined.to_string())
+    }
+
+    pub fn format_commission_queue_footer(&self, locale: &str, availability: CommissionAvailability) -> String {
+        self.get(locale).commission.availability.queue_footer.replace("{availability}", self.commission_availability_label(locale, availability))
     }
 }
 
 impl EmojiManager {
     pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
-        let emojis_content = include_str!("../../bot_emojis.toml");
-        let emojis: BotEmojis = toml::from_str(emojis_content)?;
+        Self::parse(EMBEDDED_EMOJIS)
+    }
+
+    /// Emojis from the contents of a `bot_emojis.toml`
+    pub fn parse(content: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let emojis: BotEmojis = toml::from_str(content)?;
         
-        Ok(EmojiManager { emojis })
+        Ok(EmojiManager { emojis, substituted: Vec::new(), unusable: Vec::new() })
     }
     
     pub fn get_emoji(&self, category: &str, name: &str) -> Option<&String> {
+        self.entry(category, name).map(|emoji| &emoji.text)
+    }
+
+    /// Reaction for an emoji (its fallback when the custom one is unusable)
+    pub fn get_reaction(&self, category: &str, name: &str) -> Option<ReactionType> {
+        self.entry(category, name).map(|emoji| emoji.reaction.clone())
+    }
+
+    fn entry(&self, category: &str, name: &str) -> Option<&Emoji> {
+        self.category(category)?.get(name)
+    }
+
+    fn category(&self, category: &str) -> Option<&HashMap<String, Emoji>> {
         match category {
-            "status" => self.emojis.status.get(name),
-            "confirmations" => self.emojis.confirmations.get(name),
-            "emotions" => self.emojis.emotions.get(name),
-            "technology" => self.emojis.technology.get(name),
-            "actions" => self.emojis.actions.get(name),
-            "interface" => self.emojis.interface.get(name),
+            "status" => Some(&self.emojis.status),
+            "confirmations" => Some(&self.emojis.confirmations),
+            "emotions" => Some(&self.emojis.emotions),
+            "technology" => Some(&self.emojis.technology),
+            "actions" => Some(&self.emojis.actions),
+            "interface" => Some(&self.emojis.interface),
             _ => None,
         }
     }
+
+    /// Swap custom emojis whose ID isn't in `usable` for their Unicode fallback
+    ///
+    /// Those without a fallback stay as they are and are listed in `unusable`.
+    pub fn apply_usable(&mut self, usable: &HashSet<EmojiId>) {
+        let emojis = &mut self.emojis;
+        let categories = [
+            ("status", &mut emojis.status),
+            ("confirmations", &mut emojis.confirmations),
+            ("emotions", &mut emojis.emotions),
+            ("technology", &mut emojis.technology),
+            ("actions", &mut emojis.actions),
+            ("interface", &mut emojis.interface),
+        ];
+        for (category, entries) in categories {
+            for (name, emoji) in entries.iter_mut() {
+                if emoji.custom_id().is_none_or(|id| usable.contains(&id)) {
+                    continue;
+                }
+                let Some(fallback) = emoji.fallback.clone() else {
+                    self.unusable.push(format!("{}.{}", category, name));
+                    continue;
+                };
+                emoji.reaction = ReactionType::Unicode(fallback.clone());
+                emoji.text = fallback;
+                self.substituted.push(format!("{}.{}", category, name));
+            }
+        }
+        self.substituted.sort();
+        self.unusable.sort();
+    }
+
+    /// `category.name` of the custom emojis currently shown as their fallback
+    pub fn substituted(&self) -> &[String] {
+        &self.substituted
+    }
+
+    /// `category.name` of the custom emojis the bot can't use and that have no fallback
+    pub fn unusable(&self) -> &[String] {
+        &self.unusable
+    }
     
     pub fn get_default_emoji(&self, situation: &str) -> Option<&String> {
         if let Some(default_ref) = self.emojis.defaults.get(situation) {
@@ -807,3 +1644,113 @@ impl EmojiManager {
         self.get_default_emoji("loading").map(|s| s.as_str()).unwrap_or("💤")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_locale_parses_and_falls_back_to_english() {
+        let lang = LanguageManager::new().expect("locale files should parse");
+        let locales: Vec<&str> = lang.locales().map(|(locale, _)| locale).collect();
+        assert_eq!(locales, ["en", "es", "pt-BR", "ru", "zh-CN", "fr"]);
+
+        // Translated keys come from the locale, missing ones from English
+        assert_eq!(lang.format_hello("es", "Ana"), "¡Hola, Ana!");
+        assert_eq!(lang.get("ru").ai.tools.confirm_button, lang.get("en").ai.tools.confirm_button);
+        assert_eq!(lang.get("es").safety.honeypot.footer, "safety-honeypot-v1");
+        assert_eq!(lang.get("xx").language.name, "English");
+    }
+
+    #[test]
+    fn missing_keys_are_filled_recursively() {
+        let mut value: toml::Value = toml::from_str("[a]\nx = \"mine\"").unwrap();
+        let base: toml::Value = toml::from_str("top = 1\n[a]\nx = \"base\"\ny = \"base\"").unwrap();
+        fill_missing(&mut value, &base);
+        assert_eq!(value["a"]["x"].as_str(), Some("mine"));
+        assert_eq!(value["a"]["y"].as_str(), Some("base"));
+        assert_eq!(value["top"].as_integer(), Some(1));
+    }
+
+    #[test]
+    fn discord_locales_resolve_to_loaded_ones() {
+        let lang = LanguageManager::new().unwrap();
+        assert_eq!(lang.find("es-ES"), Some("es"));
+        assert_eq!(lang.find("es-419"), Some("es"));
+        assert_eq!(lang.find("pt-BR"), Some("pt-BR"));
+        assert_eq!(lang.find("en-US"), Some("en"));
+        assert_eq!(lang.find("zh-TW"), None);
+        assert_eq!(lang.find("auto"), None);
+
+        // User choice, then the client locale, then the server default
+        assert_eq!(lang.resolve(Some("fr"), Some("es-ES"), Some("ru")), "fr");
+        assert_eq!(lang.resolve(None, Some("es-ES"), Some("ru")), "es");
+        assert_eq!(lang.resolve(None, Some("ja"), Some("ru")), "ru");
+        assert_eq!(lang.resolve(None, None, None), DEFAULT_LOCALE);
+    }
+    #[test]
+    fn command_localizations_follow_discord_rules() {
+        let lang = LanguageManager::new().unwrap();
+        let valid_name = regex::Regex::new(r"^[-_\p{Ll}\p{Lo}\p{N}]{1,32}$").unwrap();
+
+        fn check(info: &CommandInfo, path: &str, check_name: &dyn Fn(&str, &str)) {
+            check_name(&info.name, path);
+            assert!(info.description.chars().count() <= 100, "{path}: description too long");
+            for label in info.choices.values() {
+                assert!(!label.is_empty() && label.chars().count() <= 100, "{path}: bad choice label");
+            }
+            for (key, option) in &info.options {
+                check(option, &format!("{path}.{key}"), check_name);
+            }
+        }
+
+        for (locale, messages) in lang.locales() {
+            for (key, info) in &messages.commands {
+                // Context menu commands may use spaces and capitals
+                let context_menu = key == "userinfo";
+                let check_name = |name: &str, path: &str| {
+                    assert!(context_menu || valid_name.is_match(name), "{locale} {path}: invalid name {name:?}");
+                };
+                check(info, key, &check_name);
+            }
+        }
+
+        // Spanish is served to both Spanish Discord locales; English adds nothing
+        let names = lang.localizations(|locale| lang.command_info(locale, &["reminder", "time"]).map(|info| info.name.clone()));
+        assert_eq!(names, [("es-ES", "tiempo".to_string()), ("es-419", "tiempo".to_string())]);
+        assert!(lang.command_info("es", &["ai", "memory", "missing"]).is_none());
+    }
+    #[test]
+    fn emojis_parse_to_reactions_and_fall_back_when_unusable() {
+        let mut emojis = EmojiManager::new().expect("bot_emojis.toml should parse");
+        let up = emojis.get_reaction("status", "up").unwrap();
+        assert!(matches!(&up, ReactionType::Custom { name: Some(name), .. } if name == "up"));
+        assert_eq!(emojis.get_emoji("status", "up").unwrap(), "<:up:1400579660408029235>");
+
+        // Only `up` is usable: everything else shows its fallback
+        let usable = HashSet::from([EmojiId::new(1400579660408029235)]);
+        emojis.apply_usable(&usable);
+        assert_eq!(emojis.get_reaction("status", "up"), Some(up));
+        assert_eq!(emojis.get_reaction("status", "down"), Some(ReactionType::Unicode("⬇️".to_string())));
+        assert_eq!(emojis.success(), "✅");
+        assert!(emojis.substituted().contains(&"status.down".to_string()));
+        assert!(!emojis.substituted().contains(&"status.up".to_string()));
+        assert!(emojis.unusable().is_empty());
+
+        // Without a fallback an unusable emoji stays as it is and is reported
+        let content = EMBEDDED_EMOJIS.replace(
+            r#"down = { emoji = "<:down:1400579662161383594>", fallback = "⬇️" }"#,
+            r#"down = "<:down:1400579662161383594>""#,
+        );
+        let mut emojis = EmojiManager::parse(&content).unwrap();
+        emojis.apply_usable(&usable);
+        assert_eq!(emojis.get_emoji("status", "down").unwrap(), "<:down:1400579662161383594>");
+        assert_eq!(emojis.unusable(), ["status.down"]);
+        assert!(!emojis.substituted().contains(&"status.down".to_string()));
+
+        let spec = |emoji: &str, fallback: &str| EmojiSpec::WithFallback { emoji: emoji.to_string(), fallback: fallback.to_string() };
+        assert!(Emoji::try_from(spec("<:up:1>", "⬆️")).is_ok());
+        assert!(Emoji::try_from(spec("<:up:1>", "<:x:2>")).is_err());
+        assert!(Emoji::try_from(EmojiSpec::Plain("<:up:nope>".to_string())).is_err());
+    }
+}
@@ -2,10 +2,9 @@ use serenity::async_trait;
 use serenity::model::gateway::Ready;
 use serenity::model::prelude::*;
 use serenity::all::ComponentInteractionDataKind;
-use serenity::model::application::CommandType;
 use serenity::model::colour::Color;
 use serenity::prelude::*;
-use serenity::builder::{CreateCommand, CreateInteractionResponse, CreateInteractionResponseMessage, CreateEmbed, CreateMessage, CreateSelectMenu, CreateSelectMenuOption, CreateActionRow, CreateSelectMenuKind};
+use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage, CreateEmbed, CreateMessage, CreateThread, EditMessage, CreateSelectMenu, CreateSelectMenuOption, CreateActionRow, CreateSelectMenuKind};
 use serenity::Client;
 use std::env;
 use std::time::Instant;
@@ -20,11 +19,15 @@ mod commands;
 mod events;
 mod config;
 mod updater;
+mod auth;
 
-use lang::{LanguageManager, ImageManager, EmojiManager};
-use data::{DataManager, AIMessage, MessageRole};
-use ai::{AIManager, AIConfig};
+use lang::{LanguageManager, DEFAULT_LOCALE};
+use lang::assets::AssetLibrary;
+use lang::image_store;
+use data::{DataManager, AIMessage, ConversationContext, MessageRole};
+use ai::{AIManager, AIConfig, TokenUsage};
 use events::safety::SafetyService;
+use commands::registry;
 
 // Wrapper para Arc<Handler> que implementa EventHandler
 struct HandlerWrapper(Arc<Handler>);
@@ -54,12 +57,14 @@ impl EventHandler for HandlerWrapper {
     async fn reaction_remove(&self, ctx: Context, reaction: Reaction) {
         self.0.reaction_remove(ctx, reaction).await;
     }
+
+    async fn channel_delete(&self, _ctx: Context, channel: GuildChannel, _messages: Option<Vec<Message>>) {
+        commands::handle_commission_channel_deleted(channel.id, &self.0.data_manager);
+    }
 }
 
 struct Handler {
-    lang: LanguageManager,
-    images: ImageManager,
-    emojis: EmojiManager,
+    assets: AssetLibrary,
     data_manager: DataManager,
     ai_manager: AIManager,
     safety: SafetyService,
@@ -68,9 +73,7 @@ struct Handler {
 
 impl Handler {
     fn new() -> Result<Self, Box<dyn std::error::Error>> {
-        let lang = LanguageManager::new()?;
-        let images = ImageManager::new()?;
-        let emojis = EmojiManager::new()?;
+        let assets = AssetLibrary::new(lang::assets::assets_dir())?;
         let data_manager = DataManager::new()?;
         let safety = SafetyService::new()?;
         
@@ -80,9 +83,7 @@ impl Handler {
         let ai_config = AIConfig::default();
         let ai_manager = AIManager::new(ai_config)?;
         Ok(Handler { 
-            lang,
-            images,
-            emojis,
+            assets,
             data_manager,
             ai_manager,
             safety,
@@ -90,8 +91,43 @@ impl Handler {
         })
     }
     
-    /// Check if user summary should be analyzed and update if needed
+    /// Compress the oldest turns into the running summary once they exceed the history budget
+    async fn compact_conversation(&self, user_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(context) = self.data_manager.get_conversation_context(user_id) else {
+            return Ok(());
+        };
+        let overflow = self.ai_manager.overflowing_turns(&context);
+        if overflow == 0 {
+            return Ok(());
+        }
+
+        // Turns stay in context until a summary exists to replace them; new turns
+        // may arrive meanwhile, so the summarized ones are removed by identity
+        let summarized = context.get_all_messages()[..overflow].to_vec();
+        let (summary, usage) = self.ai_manager.summarize_turns(&context, overflow).await?;
+        self.record_background_usage(user_id, &context, usage);
+        let summary = summary.ok_or("No valid conversation summary in response")?;
+        let mut folded = false;
+        self.data_manager.update_data(|data| {
+            if let Some(user_context) = data.get_conversation_context_mut(user_id) {
+                folded = user_context.fold_into_summary(&summarized, summary);
+            }
+        })?;
+        if folded {
+            println!("Folded {} old messages into the conversation summary for user: {}", overflow, user_id);
+        }
+        Ok(())
+    }
+
+    /// Count the tokens of a summary call towards the user's token usage (not their message quota)
+    fn record_background_usage(&self, user_id: &str, context: &ConversationContext, usage: TokenUsage) {
+        if let Err(e) = self.data_manager.record_ai_tokens(user_id.to_string(), context.user_name.clone(), usage.prompt_tokens, usage.response_tokens) {
+            eprintln!("Error recording AI usage: {}", e);
+        }
+    }
+
     async fn check_and_analyze_user_summary(&self, user_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let assets = self.assets.current();
         // Check if summary analysis should be triggered (every 20 messages)
         let should_analyze = self.data_manager.increment_message_counter_and_check(user_id)?;
 
@@ -101,7 +137,11 @@ impl Handler {
             // Get fresh context for analysis
             if let Some(context) = self.data_manager.get_conversation_context(user_id) {
                 // Analyze the summary
-                match self.ai_manager.analyze_user_summary(&context, &self.lang).await {
+                let analysis = self.ai_manager.analyze_user_summary(&context, &assets.lang).await;
+                if let Ok((_, usage)) = &analysis {
+                    self.record_background_usage(user_id, &context, *usage);
+                }
+                match analysis.map(|(summary, _)| summary) {
                     Ok(Some(new_summary)) => {
                         println!("Updating user summary for: {}", user_id);
                         // Update the summary in the data manager
@@ -124,36 +164,12 @@ impl Handler {
         Ok(())
     }
     
-    fn get_uptime(&self) -> String {
-        let uptime = self.start_time.elapsed();
-        let days = uptime.as_secs() / 86400;
-        let hours = (uptime.as_secs() % 86400) / 3600;
-        let minutes = (uptime.as_secs() % 3600) / 60;
-        let seconds = uptime.as_secs() % 60;
-        
-        if days > 0 {
-            format!("{}d {}h {}m {}s", days, hours, minutes, seconds)
-        } else if hours > 0 {
-            format!("{}h {}m {}s", hours, minutes, seconds)
-        } else if minutes > 0 {
-            format!("{}m {}s", minutes, seconds)
-        } else {
-            format!("{}s", seconds)
-        }
-    }
-    
-    fn get_memory_usage() -> f64 {
-        // Get basic memory usage (this is a simplified version)
-        // In a real scenario, you might want to use a more sophisticated method
-        let _pid = std::process::id();
-        // For now, return a placeholder. You could integrate with system monitoring libraries
-        42.5 // MB placeholder
-    }
-
     /// Get data manager statistics
     fn get_data_stats(&self) -> String {
+        let assets = self.assets.current();
         let stats = self.data_manager.get_stats();
-        self.lang.format_ai_data_stats(
+        assets.lang.format_ai_data_stats(
+            DEFAULT_LOCALE,
             stats.button_messages_count,
             stats.conversations_count,
             stats.total_messages,
@@ -168,11 +184,12 @@ impl Handler {
 
     /// Check and send pending reminders
     async fn check_pending_reminders(&self, ctx: &Context) -> Result<(), Box<dyn std::error::Error>> {
+        let assets = self.assets.current();
         let pending_reminders = self.data_manager.get_pending_reminders();
         
         for reminder in pending_reminders {
             // Create reminder notification embed
-            let lang_msgs = self.lang.get();
+            let lang_msgs = assets.lang.get(self.reminder_locale(&assets.lang, &reminder.user_id));
             let embed = CreateEmbed::new()
                 .title(&lang_msgs.embeds.reminder_notification.title)
                 .description(&format!("{}\n\n**{}**", &lang_msgs.embeds.reminder_notification.description, reminder.message))
@@ -206,72 +223,358 @@ impl Handler {
         Ok(())
     }
 
-    /// Handle AI conversation in the designated channel or ticket channels
-    async fn handle_ai_message(&self, ctx: &Context, msg: &Message) -> Result<(), Box<dyn std::error::Error>> {
-        // Get or create conversation context for this user
+    /// Locale for replies to a user: their own choice, then their Discord client locale, then the server default
+    fn user_locale<'a>(&self, lang: &'a LanguageManager, user_id: UserId, guild_id: Option<GuildId>, discord_locale: Option<&str>) -> &'a str {
+        let user = self.data_manager.get_user_locale(&user_id.to_string());
+        let guild = guild_id.and_then(|guild_id| self.data_manager.get_guild_locale(&guild_id.to_string()));
+        lang.resolve(user.as_deref(), discord_locale, guild.as_deref())
+    }
+
+    /// Locale for posts everyone in a server sees
+    fn guild_locale<'a>(&self, lang: &'a LanguageManager, guild_id: Option<GuildId>) -> &'a str {
+        let guild = guild_id.and_then(|guild_id| self.data_manager.get_guild_locale(&guild_id.to_string()));
+        lang.resolve(None, None, guild.as_deref())
+    }
+
+    /// Locale of a reminder: the language its creator chose
+    fn reminder_locale<'a>(&self, lang: &'a LanguageManager, user_id: &str) -> &'a str {
+        lang.resolve(self.data_manager.get_user_locale(user_id).as_deref(), None, None)
+    }
+
+    /// Decide whether the assistant answers a message, and why
+    async fn ai_trigger(&self, ctx: &Context, msg: &Message) -> Option<ai::AITrigger> {
+        if self.ai_manager.should_process_message(
+            &msg.channel_id.to_string(),
+            msg.author.id.get(),
+            self.data_manager.get_ai_channel(),
+        ) {
+            return Some(ai::AITrigger::Channel);
+        }
+        if msg.author.id.get() == crate::config::OWNER_ID {
+            return None;
+        }
+
+        let Some(guild_id) = msg.guild_id else {
+            return self.data_manager.is_ai_dm_enabled().then_some(ai::AITrigger::DirectMessage);
+        };
+        let allowed = self.data_manager.get_ai_reply_channels(&guild_id.to_string());
+        if allowed.is_empty() {
+            return None;
+        }
+
+        let bot_id = ctx.cache.current_user().id;
+        let addressed = msg.mentions_user_id(bot_id)
+            || msg.referenced_message.as_ref().is_some_and(|reply| reply.author.id == bot_id);
+        if allowed.contains(&msg.channel_id.get()) {
+            return addressed.then_some(ai::AITrigger::Mention);
+        }
+
+        // Threads under an allowed channel: the bot's own carry on without mentions
+        match msg.channel_id.to_channel(ctx).await {
+            Ok(Channel::Guild(channel))
+                if channel.thread_metadata.is_some()
+                    && channel.parent_id.is_some_and(|parent| allowed.contains(&parent.get()))
+                    && (addressed || channel.owner_id == Some(bot_id)) =>
+            {
+                Some(ai::AITrigger::Thread)
+            }
+            _ => None,
+        }
+    }
+
+    /// Open a thread on a message that mentioned the bot, falling back to its channel
+    async fn open_ai_thread(&self, ctx: &Context, msg: &Message, lang: &LanguageManager, locale: &str) -> ChannelId {
+        let thread = CreateThread::new(lang.format_ai_thread_name(locale, &msg.author.name))
+            .auto_archive_duration(AutoArchiveDuration::OneHour);
+        match msg.channel_id.create_thread_from_message(&ctx.http, msg.id, thread).await {
+            Ok(thread) => thread.id,
+            Err(e) => {
+                eprintln!("Error opening AI reply thread, answering in the channel: {}", e);
+                msg.channel_id
+            }
+        }
+    }
+
+    /// Handle AI conversation in the designated channel, allowed channels, threads and DMs
+    async fn handle_ai_message(&self, ctx: &Context, msg: &Message, trigger: ai::AITrigger) -> Result<(), Box<dyn std::error::Error>> {
+        let assets = self.assets.current();
         let user_id = msg.author.id.to_string();
-        let context = self.data_manager.get_conversation_context(&user_id);
+        let locale = self.user_locale(&assets.lang, msg.author.id, msg.guild_id, None);
+
+        // The mention that summoned the bot isn't part of the question
+        let content = ai::strip_mention(&msg.content, ctx.cache.current_user().id.get());
+        if content.is_empty() {
+            return Ok(());
+        }
+
+        // Throttle before anything else so repeated messages can't flood the channel with notices
+        if let Err(limited) = self.ai_manager.check_rate_limit(msg.author.id.get()) {
+            if limited.should_notify() {
+                let retry_at = Utc::now().timestamp() + limited.retry_after().as_secs() as i64 + 1;
+                let notice = match limited {
+                    ai::RateLimited::UserCooldown { .. } => assets.lang.format_ai_limit_cooldown(locale, retry_at),
+                    ai::RateLimited::Global { .. } => assets.lang.format_ai_limit_busy(locale, retry_at),
+                };
+                self.send_ai_notice(ctx, msg, &assets.lang.get(locale).ai.limits.title, notice).await?;
+            }
+            return Ok(());
+        }
+
+        // Daily quota, from the most generous of the member's roles
+        let role_ids: Vec<String> = msg.member
+            .as_ref()
+            .map(|member| member.roles.iter().map(|role| role.to_string()).collect())
+            .unwrap_or_default();
+        let (used_today, daily_limit) = self.data_manager.get_ai_quota_status(&user_id, &role_ids);
+        if daily_limit > 0 && used_today >= daily_limit {
+            let reset_at = (Utc::now().date_naive() + chrono::Days::new(1))
+                .and_hms_opt(0, 0, 0)
+                .map(|midnight| midnight.and_utc().timestamp())
+                .unwrap_or_default();
+            self.send_ai_notice(ctx, msg, &assets.lang.get(locale).ai.limits.title, assets.lang.format_ai_limit_quota_reached(locale, daily_limit, reset_at)).await?;
+            return Ok(());
+        }
+
+        // Injection attempts never reach the model or the stored context
+        if let Some(flag) = ai::moderation::screen_input(&content) {
+            self.log_ai_moderation(msg, &flag);
+            let moderation = &assets.lang.get(locale).ai.moderation;
+            self.send_ai_notice(ctx, msg, &moderation.title, moderation.input_refused.clone()).await?;
+            return Ok(());
+        }
+
+        // Users who opted out of memory get replies without any stored context
+        let remember = !self.data_manager.is_ai_memory_opted_out(&user_id);
+
+        // Get or create conversation context for this user
+        let context = if remember {
+            self.data_manager.get_conversation_context(&user_id)
+        } else {
+            None
+        };
 
         // Add user message to conversation context
-        let user_message = AIMessage::new(MessageRole::User, msg.content.clone())
-            .with_channel(msg.channel_id.to_string())
-            .with_discord_message_id(msg.id.to_string());
-
-        self.data_manager.add_conversation_message_with_name(&user_id, &msg.author.name, user_message)?;
-
-        // Generate AI response
-        let response_builder = self.ai_manager.generate_response(
-            &msg.content,
-            &user_id,
-            context.as_ref(),
-            &self.emojis,
-            &self.lang,
-            &self.images,
-        ).await?;
-
-        // Build the embed and components
-        let embed = response_builder.build_embed(&self.images, &self.emojis, &msg.author.name, &self.lang);
-        let action_rows = response_builder.build_action_rows();
-
-        // Send the response
-        let mut message_builder = CreateMessage::new().embed(embed);
-        
-        for row in action_rows {
-            message_builder = message_builder.components(vec![row]);
+        if remember {
+            let user_message = AIMessage::new(MessageRole::User, content.clone())
+                .with_channel(msg.channel_id.to_string())
+                .with_discord_message_id(msg.id.to_string());
+
+            self.data_manager.add_conversation_message_with_name(&user_id, &msg.author.name, user_message)?;
         }
 
-        let sent_message = msg.channel_id.send_message(&ctx.http, message_builder).await?;
+        let persona = self.data_manager.get_ai_persona(&msg.channel_id.to_string());
+
+        // Mentions in open channels are answered in a thread so they don't flood the chat
+        let reply_channel = match trigger {
+            ai::AITrigger::Mention => self.open_ai_thread(ctx, msg, &assets.lang, locale).await,
+            ai::AITrigger::Channel | ai::AITrigger::Thread | ai::AITrigger::DirectMessage => msg.channel_id,
+        };
+
+        // Show the typing indicator until the reply is complete
+        let typing = reply_channel.start_typing(&ctx.http);
+
+        // Generate AI response, previewing it in an embed while it streams
+        let (chunk_tx, mut chunk_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+        // The error isn't Send, so only its text is kept while the preview runs alongside
+        let generation = async {
+            self.ai_manager.generate_response(
+                &content,
+                &user_id,
+                context.as_ref(),
+                persona.as_deref(),
+                &assets.emojis,
+                &assets.lang,
+                locale,
+                &assets.images,
+                chunk_tx,
+            ).await.map_err(|e| e.to_string())
+        };
+        let preview = async {
+            let edit_interval = Duration::from_millis(crate::config::AI_STREAM_EDIT_INTERVAL_MS);
+            let mut raw = String::new();
+            let mut shown = String::new();
+            let mut reply: Option<Message> = None;
+            let mut last_edit: Option<Instant> = None;
+            let mut withheld = false;
+
+            while let Some(chunk) = chunk_rx.recv().await {
+                raw.push_str(&chunk);
+                if withheld {
+                    continue;
+                }
+                // A reply leaking the instructions is taken down at once and refused when complete
+                if ai::moderation::review_preview(&raw).is_some() {
+                    withheld = true;
+                    if let Some(message) = reply.take() {
+                        if let Err(e) = message.delete(&ctx.http).await {
+                            eprintln!("Error deleting flagged AI reply preview: {}", e);
+                        }
+                    }
+                    continue;
+                }
+                if last_edit.is_some_and(|at| at.elapsed() < edit_interval) {
+                    continue;
+                }
+                let Some(content) = ai::partial_response_content(&raw) else {
+                    continue;
+                };
+                if content.trim().is_empty() || content == shown {
+                    continue;
+                }
+
+                // Embed descriptions are capped at 4096 characters
+                let (content, _) = ai::moderation::redact_output(&content);
+                let preview_text: String = format!("{} ▌", content).chars().take(4096).collect();
+                let embed = ai::AIResponseBuilder::new()
+                    .content(preview_text)
+                    .build_embed(&assets.images, &assets.emojis, &msg.author.name, &assets.lang, locale);
+                let result = match reply.as_mut() {
+                    Some(message) => message.edit(&ctx.http, EditMessage::new().embed(embed)).await,
+                    None => reply_channel
+                        .send_message(&ctx.http, CreateMessage::new().embed(embed))
+                        .await
+                        .map(|message| reply = Some(message)),
+                };
+                if let Err(e) = result {
+                    eprintln!("Error updating streamed AI reply: {}", e);
+                }
+                last_edit = Some(Instant::now());
+                shown = content;
+            }
+            reply
+        };
+        let (response, reply) = tokio::join!(generation, preview);
+        typing.stop();
+
+        let response_builder = match response {
+            Ok(builder) => builder,
+            Err(error) => {
+                // Don't leave a half-written reply behind
+                if let Some(message) = reply {
+                    let _ = message.delete(&ctx.http).await;
+                }
+                return Err(error.into());
+            }
+        };
+
+        if let Some(flag) = response_builder.get_moderation() {
+            self.log_ai_moderation(msg, flag);
+        }
+
+        // Count the reply towards the user's quota and token usage
+        let usage = response_builder.get_usage().unwrap_or_default();
+        if let Err(e) = self.data_manager.record_ai_usage(user_id.clone(), msg.author.name.clone(), usage.prompt_tokens, usage.response_tokens) {
+            eprintln!("Error recording AI usage: {}", e);
+        }
+
+        // A bare tool call comes without reply text; the tool's own messages stand in for it
+        if !response_builder.get_content().trim().is_empty() {
+            // Build the embed and components
+            let embed = response_builder.build_embed(&assets.images, &assets.emojis, &msg.author.name, &assets.lang, locale);
+            let action_rows = response_builder.build_action_rows();
+
+            // Send the final response, replacing the streamed preview if there is one
+            let sent_message = match reply {
+                Some(mut message) => {
+                    let mut edit_builder = EditMessage::new().embed(embed);
+                    if !action_rows.is_empty() {
+                        edit_builder = edit_builder.components(action_rows);
+                    }
+                    message.edit(&ctx.http, edit_builder).await?;
+                    message
+                }
+                None => {
+                    let mut message_builder = CreateMessage::new().embed(embed);
+
+                    for row in action_rows {
+                        message_builder = message_builder.components(vec![row]);
+                    }
+
+                    reply_channel.send_message(&ctx.http, message_builder).await?
+                }
+            };
+
+            // Save AI response to conversation context
+            if remember {
+                let ai_message = AIMessage::new(MessageRole::Assistant, response_builder.get_content().to_string())
+                    .with_channel(reply_channel.to_string())
+                    .with_discord_message_id(sent_message.id.to_string());
 
-        // Save AI response to conversation context
-        let ai_message = AIMessage::new(MessageRole::Assistant, response_builder.get_content().to_string())
-            .with_channel(msg.channel_id.to_string())
-            .with_discord_message_id(sent_message.id.to_string());
+                self.data_manager.add_conversation_message_with_name(&user_id, &msg.author.name, ai_message)?;
+            }
+        } else if let Some(message) = reply {
+            let _ = message.delete(&ctx.http).await;
+        }
 
-        self.data_manager.add_conversation_message_with_name(&user_id, &msg.author.name, ai_message)?;
+        // Act on requested bot actions (side effects wait for a confirmation button)
+        let tool_calls = response_builder.get_tool_calls();
+        if !tool_calls.is_empty() {
+            if let Err(e) = commands::handle_ai_tool_calls(ctx, msg, reply_channel, tool_calls, &self.data_manager, &assets.lang, locale, &assets.images, &assets.emojis).await {
+                eprintln!("Error handling AI tool calls: {}", e);
+            }
+        }
 
-        // Check if we should analyze user summary (every 20 messages)
-        if let Err(e) = self.check_and_analyze_user_summary(&user_id).await {
-            eprintln!("Error analyzing user summary: {}", e);
+        if remember {
+            // Fold turns past the history budget into the running summary
+            if let Err(e) = self.compact_conversation(&user_id).await {
+                eprintln!("Error compacting conversation: {}", e);
+            }
+
+            // Check if we should analyze user summary (every 20 messages)
+            if let Err(e) = self.check_and_analyze_user_summary(&user_id).await {
+                eprintln!("Error analyzing user summary: {}", e);
+            }
         }
 
-        // Ya no agregamos botones automáticamente, el sistema es más simple ahora
+        println!("{}", assets.lang.format_ai_response_generated(DEFAULT_LOCALE, &msg.author.name, &msg.channel_id.to_string()));
+        Ok(())
+    }
 
-        println!("{}", self.lang.format_ai_response_generated(&msg.author.name, &msg.channel_id.to_string()));
+    /// Keep a flagged AI exchange for owner review
+    fn log_ai_moderation(&self, msg: &Message, flag: &ai::ModerationFlag) {
+        println!("🛡️ AI {} {} for {}: {}", flag.stage.as_str(), flag.action.as_str(), msg.author.name, flag.reason);
+        let entry = data::AIModerationEntry {
+            user_id: msg.author.id.to_string(),
+            user_name: msg.author.name.clone(),
+            channel_id: msg.channel_id.to_string(),
+            stage: flag.stage.as_str().to_string(),
+            action: flag.action.as_str().to_string(),
+            reason: flag.reason.clone(),
+            message: msg.content.clone(),
+            response: flag.output.clone(),
+            created_at: Utc::now(),
+        };
+        if let Err(e) = self.data_manager.log_ai_moderation(entry) {
+            eprintln!("Error logging flagged AI exchange: {}", e);
+        }
+    }
+
+    /// Reply with a rate limit, quota or moderation notice
+    async fn send_ai_notice(&self, ctx: &Context, msg: &Message, title: &str, description: String) -> Result<(), Box<dyn std::error::Error>> {
+        let embed = CreateEmbed::new()
+            .title(title)
+            .description(description)
+            .color(Color::ORANGE);
+        msg.channel_id
+            .send_message(&ctx.http, CreateMessage::new().embed(embed).reference_message(msg))
+            .await?;
         Ok(())
     }
 
     /// Handle reaction added to a message
     async fn reaction_add(&self, ctx: Context, reaction: Reaction) {
+        let assets = self.assets.current();
         // Handle feedback system reactions
-        if let Err(e) = commands::handle_feedback_reaction_add(&ctx, &reaction, &self.data_manager, &self.lang, &self.emojis).await {
+        if let Err(e) = commands::handle_feedback_reaction_add(&ctx, &reaction, &self.data_manager, &assets.lang, self.guild_locale(&assets.lang, reaction.guild_id), &assets.emojis).await {
             eprintln!("Error handling feedback reaction add: {}", e);
         }
     }
 
     /// Handle reaction removed from a message
     async fn reaction_remove(&self, ctx: Context, reaction: Reaction) {
+        let assets = self.assets.current();
         // Handle feedback system reactions
-        if let Err(e) = commands::handle_feedback_reaction_remove(&ctx, &reaction, &self.data_manager, &self.lang, &self.emojis).await {
+        if let Err(e) = commands::handle_feedback_reaction_remove(&ctx, &reaction, &self.data_manager, &assets.lang, self.guild_locale(&assets.lang, reaction.guild_id), &assets.emojis).await {
             eprintln!("Error handling feedback reaction remove: {}", e);
         }
     }
@@ -280,118 +583,45 @@ impl Handler {
 #[async_trait]
 impl EventHandler for Handler {
     async fn ready(&self, ctx: Context, ready: Ready) {
-        println!("{}", self.lang.format_bot_connected(&ready.user.name));
+        let assets = self.assets.current();
+        println!("{}", assets.lang.format_bot_connected(DEFAULT_LOCALE, &ready.user.name));
 
         // Clear pending update state after successful Discord connection
         if let Err(e) = updater::clear_state() {
             eprintln!("Warning: Failed to clear update state: {}", e);
         }
 
+        let guild_ids: Vec<GuildId> = ready.guilds.iter().map(|guild| guild.id).collect();
+        if let Err(e) = self.assets.check_emojis(&ctx.http, &guild_ids).await {
+            eprintln!("⚠️  Emoji check failed, keeping the configured emojis: {}", e);
+        }
+
         self.safety
             .ready(
                 &ctx,
                 ready.user.id,
                 &self.data_manager,
-                &self.lang,
-                &self.images,
+                &assets.lang,
+                &assets.images,
             )
             .await;
+
+        commands::reconcile_commissions(&ctx, &self.data_manager).await;
         
-        let lang_msgs = self.lang.get();
-        let commands = vec![
-            CreateCommand::new(&lang_msgs.commands.ping.name)
-                .description(&lang_msgs.commands.ping.description),
-            CreateCommand::new(&lang_msgs.commands.info.name)
-                .description(&lang_msgs.commands.info.description),
-            CreateCommand::new(&lang_msgs.commands.hello.name)
-                .description(&lang_msgs.commands.hello.description),
-            CreateCommand::new(&lang_msgs.commands.help.name)
-                .description(&lang_msgs.commands.help.description),
-            CreateCommand::new(&lang_msgs.commands.images.name)
-                .description(&lang_msgs.commands.images.description),
-            CreateCommand::new(&lang_msgs.commands.stats.name)
-                .description(&lang_msgs.commands.stats.description),
-            CreateCommand::new(&lang_msgs.commands.userinfo.name)
-                .kind(CommandType::User),
-            CreateCommand::new(&lang_msgs.commands.purge.name)
-                .description(&lang_msgs.commands.purge.description)
-                .add_option(serenity::builder::CreateCommandOption::new(
-                    serenity::model::application::CommandOptionType::Integer,
-                    "amount",
-                    "Number of messages to delete (1-100)",
-                ).required(true)),
-            CreateCommand::new(&lang_msgs.commands.reminder.name)
-                .description(&lang_msgs.commands.reminder.description)
-                .add_option(serenity::builder::CreateCommandOption::new(
-                    serenity::model::application::CommandOptionType::String,
-                    "time",
-                    "Time until reminder (e.g., 5m, 2h, 1d)",
-                ).required(true))
-                .add_option(serenity::builder::CreateCommandOption::new(
-                    serenity::model::application::CommandOptionType::String,
-                    "message",
-                    "Reminder message",
-                ).required(true))
-                .add_option(serenity::builder::CreateCommandOption::new(
-                    serenity::model::application::CommandOptionType::String,
-                    "visibility",
-                    "Who can see the reminder notification",
-                ).required(false)
-                .add_string_choice("public", "public")
-                .add_string_choice("private", "private"))
-                .add_option(serenity::builder::CreateCommandOption::new(
-                    serenity::model::application::CommandOptionType::String,
-                    "mention_type",
-                    "Who to mention when the reminder is sent",
-                ).required(false)
-                .add_string_choice("none", "none")
-                .add_string_choice("creator", "creator")
-                .add_string_choice("everyone", "everyone"))
-                .add_option(serenity::builder::CreateCommandOption::new(
-                    serenity::model::application::CommandOptionType::Boolean,
-                    "has_status",
-                    "Enable status tracking with dropdown menu",
-                ).required(false)),
-            CreateCommand::new(&lang_msgs.commands.commission_setup.name)
-                .description(&lang_msgs.commands.commission_setup.description),
-            CreateCommand::new(&lang_msgs.commands.commission_close.name)
-                .description(&lang_msgs.commands.commission_close.description),
-            CreateCommand::new(&lang_msgs.commands.ticket_setup.name)
-                .description(&lang_msgs.commands.ticket_setup.description),
-            CreateCommand::new(&lang_msgs.commands.ticket_close.name)
-                .description(&lang_msgs.commands.ticket_close.description),
-            CreateCommand::new(&lang_msgs.commands.feedback_setup.name)
-                .description(&lang_msgs.commands.feedback_setup.description),
-            CreateCommand::new("update")
-                .description("Check for and apply bot updates (owner only)"),
-            CreateCommand::new("github_channel")
-                .description("Set the GitHub activity announcements channel (owner only)")
-                .add_option(serenity::builder::CreateCommandOption::new(
-                    serenity::model::application::CommandOptionType::Channel,
-                    "channel",
-                    "Channel for GitHub announcements",
-                ).required(true)),
-            CreateCommand::new("ai_channel")
-                .description("Set the AI conversation channel (owner only)")
-                .add_option(serenity::builder::CreateCommandOption::new(
-                    serenity::model::application::CommandOptionType::Channel,
-                    "channel",
-                    "Channel for AI conversations",
-                ).required(true)),
-        ];
-
-        let _ = Command::set_global_commands(&ctx.http, commands).await;
+        commands::sync::sync_commands(&ctx.http, &assets.lang, &self.data_manager).await;
     }
 
     async fn message(&self, ctx: Context, msg: Message) {
+        let assets = self.assets.current();
         if self
             .safety
             .handle_message(
                 &ctx,
                 &msg,
                 &self.data_manager,
-                &self.lang,
-                &self.images,
+                &assets.lang,
+                self.user_locale(&assets.lang, msg.author.id, msg.guild_id, None),
+                &assets.images,
             )
             .await
         {
@@ -405,22 +635,16 @@ impl EventHandler for Handler {
 
         // Check if this is a message in the feedback channel
         if commands::is_feedback_channel(&msg.channel_id.to_string()) {
-            if let Err(e) = commands::handle_feedback_message(&ctx, &msg, &self.data_manager, &self.lang, &self.images, &self.emojis).await {
+            if let Err(e) = commands::handle_feedback_message(&ctx, &msg, &self.data_manager, &assets.lang, self.guild_locale(&assets.lang, msg.guild_id), &assets.images, &assets.emojis).await {
                 eprintln!("Error handling feedback message: {}", e);
             }
             return;
         }
 
-        // Check if this is a message in the AI channel (tickets excluded)
-        let should_process = self.ai_manager.should_process_message(
-            &msg.channel_id.to_string(),
-            msg.author.id.get(),
-            self.data_manager.get_ai_channel(),
-        );
-            
-        if should_process {
-            if let Err(e) = self.handle_ai_message(&ctx, &msg).await {
-                eprintln!("{}", self.lang.format_ai_error_generating(&e.to_string()));
+        // Answer in the AI channel, when mentioned or replied to in allowed channels, and in DMs
+        if let Some(trigger) = self.ai_trigger(&ctx, &msg).await {
+            if let Err(e) = self.handle_ai_message(&ctx, &msg, trigger).await {
+                eprintln!("{}", assets.lang.format_ai_error_generating(DEFAULT_LOCALE, &e.to_string()));
             }
         }
 
@@ -436,313 +660,34 @@ impl EventHandler for Handler {
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        let assets = self.assets.current();
         match interaction {
             Interaction::Command(command) => {
-            let start_time = Instant::now();
-            let lang_msgs = self.lang.get();
-            
-            match command.data.name.as_str() {
-                "ping" => {
-                    // Calculate latency
-                    let latency = start_time.elapsed().as_millis() as u64;
-                    let uptime = self.get_uptime();
-                    let memory = Self::get_memory_usage();
-                    
-                    let embed = CreateEmbed::new()
-                        .title(&format!("{} {}", self.emojis.success(), lang_msgs.embeds.ping.title))
-                        .description(&lang_msgs.embeds.ping.description)
-                        .color(Color::from_rgb(0, 255, 127)) // Spring green
-                        .field(
-                            &format!("{} {}", self.emojis.get_emoji("status", "up").unwrap_or(&"📶".to_string()), lang_msgs.embeds.ping.latency_field),
-                            self.lang.format_ping_embed_latency(latency),
-                            true,
-                        )
-                        .field(
-                            &format!("{} {}", self.emojis.get_emoji("status", "on").unwrap_or(&"🟢".to_string()), lang_msgs.embeds.ping.uptime_field),
-                            self.lang.format_ping_embed_uptime(&uptime),
-                            true,
-                        )
-                        .field(
-                            &format!("{} {}", self.emojis.get_emoji("technology", "console").unwrap_or(&"💻".to_string()), lang_msgs.embeds.ping.memory_field),
-                            self.lang.format_ping_embed_memory(memory),
-                            true,
-                        )
-                        .thumbnail(
-                            self.images.get_default_image("ping_embed")
-                                .unwrap_or(&lang_msgs.embeds.ping.thumbnail)
-                        )
-                        .footer(serenity::builder::CreateEmbedFooter::new(&lang_msgs.embeds.ping.footer))
-                        .timestamp(Utc::now());
-                    
-                    let data = CreateInteractionResponseMessage::new().embed(embed);
-                    let builder = CreateInteractionResponse::Message(data);
-                    let _ = command.create_response(&ctx.http, builder).await;
-                },
-                "images" => {
-                    // Create images gallery embed
-                    let categories = self.images.list_categories();
-                    let mut total_images = 0;
-                    let mut description = lang_msgs.embeds.images.description.clone();
-                    description.push_str("\n\n");
-                    
-                    for category in &categories {
-                        let images_in_category = self.images.list_images_in_category(category);
-                        total_images += images_in_category.len();
-                        description.push_str(&format!("**{}**: {} images\n", 
-                            category.to_uppercase(), images_in_category.len()));
-                    }
-                    
-                    // Use a random avatar image for thumbnail
-                    let thumbnail_url = self.images.get_random_avatar()
-                        .or_else(|| self.images.get_image("avatar", "pointing"))
-                        .unwrap_or(&lang_msgs.embeds.ping.thumbnail);
-                    
-                    let embed = CreateEmbed::new()
-                        .title(&lang_msgs.embeds.images.title)
-                        .description(description)
-                        .color(Color::from_rgb(138, 43, 226)) // Blue violet
-                        .thumbnail(thumbnail_url)
-                        .footer(serenity::builder::CreateEmbedFooter::new(
-                            &self.lang.format_images_embed_footer(total_images)
-                        ))
-                        .timestamp(Utc::now());
-                    
-                    let data = CreateInteractionResponseMessage::new().embed(embed);
-                    let builder = CreateInteractionResponse::Message(data);
-                    let _ = command.create_response(&ctx.http, builder).await;
-                },
-                "User Info" => {
-                    // Get target user from context menu
-                    let target_user = if let Some(target_id) = command.data.target_id {
-                        // For user context menu commands, convert target_id to UserId first
-                        let user_id = target_id.to_user_id();
-                        user_id.to_user(&ctx.http).await.unwrap_or_else(|_| command.user.clone())
-                    } else {
-                        command.user.clone()
-                    };
-
-                    // Get member info if in a guild
-                    let member_info = if let Some(guild_id) = command.guild_id {
-                        guild_id.member(&ctx.http, target_user.id).await.ok()
-                    } else {
-                        None
-                    };
-
-                    // Format roles
-                    let roles_text = if let Some(ref member) = member_info {
-                        if member.roles.is_empty() {
-                            lang_msgs.embeds.userinfo.no_roles.clone()
-                        } else {
-                            member.roles.iter()
-                                .take(10) // Limit roles to prevent embed overflow
-                                .map(|role_id| format!("<@&{}>", role_id))
-                                .collect::<Vec<_>>()
-                                .join(", ")
-                        }
-                    } else {
-                        lang_msgs.embeds.userinfo.no_roles.clone()
-                    };
-
-                    // Format account creation date
-                    let account_created = format!("<t:{}:F>", target_user.created_at().timestamp());
-                    
-                    // Format server join date
-                    let server_joined = if let Some(ref member) = member_info {
-                        if let Some(joined_at) = member.joined_at {
-                            format!("<t:{}:F>", joined_at.timestamp())
-                        } else {
-                            "Unknown".to_string()
-                        }
-                    } else {
-                        "Not in server".to_string()
-                    };
-
-                    // Get user avatar or default
-                    let user_avatar = target_user.avatar_url().unwrap_or_else(|| target_user.default_avatar_url());
-
-                    // Get thumbnail from bot images
-                    let thumbnail_url = self.images.get_default_image("userinfo_embed")
-                        .or_else(|| {
-                            // Try to resolve the thumbnail reference from lang file
-                            self.images.get_image("talking", &lang_msgs.embeds.userinfo.thumbnail)
-                        })
-                        .unwrap_or(&lang_msgs.embeds.userinfo.thumbnail);
-
-                    let embed = CreateEmbed::new()
-                        .title(&format!("{} {}", self.emojis.get_emoji("interface", "stats").unwrap_or(&"📊".to_string()), lang_msgs.embeds.userinfo.title))
-                        .description(&self.lang.format_userinfo_embed_description(&target_user.name))
-                        .color(Color::from_rgb(105, 90, 205)) // Our purple theme
-                        .field(
-                            &format!("{} {}", self.emojis.get_emoji("interface", "list").unwrap_or(&"🆔".to_string()), lang_msgs.embeds.userinfo.user_id_field),
-                            target_user.id.to_string(),
-                            true,
-                        )
-                        .field(
-                            &format!("{} {}", self.emojis.get_emoji("interface", "star").unwrap_or(&"⭐".to_string()), lang_msgs.embeds.userinfo.account_created_field),
-                            account_created,
-                            true,
-                        )
-                        .field(
-                            &format!("{} {}", self.emojis.get_emoji("emotions", "heart").unwrap_or(&"💖".to_string()), lang_msgs.embeds.userinfo.server_joined_field),
-                            server_joined,
-                            true,
-                        )
-                        .field(
-                            &format!("{} {}", self.emojis.get_emoji("interface", "staff").unwrap_or(&"👥".to_string()), lang_msgs.embeds.userinfo.roles_field),
-                            roles_text,
-                            false,
-                        );
-
-                    // Add AI summary if available
-                    let ai_summary_text = if let Some(context) = self.data_manager.get_conversation_context(&target_user.id.to_string()) {
-                        if context.has_summary() {
-                            context.get_summary().to_string()
-                        } else {
-                            lang_msgs.embeds.userinfo.no_ai_summary.clone()
-                        }
-                    } else {
-                        lang_msgs.embeds.userinfo.no_ai_summary.clone()
-                    };
-
-                    let embed = embed
-                        .field(
-                            &format!("{} {}", self.emojis.get_emoji("emotions", "happy").unwrap_or(&"🤖".to_string()), lang_msgs.embeds.userinfo.ai_summary_field),
-                            ai_summary_text,
-                            false,
-                        )
-                        .thumbnail(thumbnail_url)
-                        .image(user_avatar)
-                        .footer(serenity::builder::CreateEmbedFooter::new(
-                            &self.lang.format_userinfo_embed_footer(&command.user.name)
-                        ))
-                        .timestamp(Utc::now());
-
-                    let data = CreateInteractionResponseMessage::new().embed(embed);
-                    let builder = CreateInteractionResponse::Message(data);
-                    let _ = command.create_response(&ctx.http, builder).await;
-                },
-                "stats" => {
-                    // Handle stats command
-                    if let Err(e) = commands::handle_stats_command(&ctx, &command, &self.data_manager, &self.lang).await {
-                        eprintln!("Error handling stats command: {}", e);
-                        let data = CreateInteractionResponseMessage::new()
-                            .content("Error retrieving bot statistics.");
-                        let builder = CreateInteractionResponse::Message(data);
-                        let _ = command.create_response(&ctx.http, builder).await;
-                    }
-                },
-                "purge" => {
-                    // Handle purge command
-                    if let Err(e) = commands::handle_purge_command(&ctx, &command, &self.lang).await {
-                        eprintln!("Error handling purge command: {}", e);
-                        let data = CreateInteractionResponseMessage::new()
-                            .content("Error executing purge command.");
-                        let builder = CreateInteractionResponse::Message(data);
-                        let _ = command.create_response(&ctx.http, builder).await;
-                    }
-                },
-                "reminder" => {
-                    // Handle reminder command
-                    if let Err(e) = commands::handle_reminder_command(&ctx, &command, &self.data_manager, &self.lang, &self.images, &self.emojis).await {
-                        eprintln!("Error handling reminder command: {}", e);
-                        let data = CreateInteractionResponseMessage::new()
-                            .content("Error creating reminder.");
-                        let builder = CreateInteractionResponse::Message(data);
-                        let _ = command.create_response(&ctx.http, builder).await;
-                    }
-                },
-                "commission_setup" => {
-                    // Handle commission setup command
-                    if let Err(e) = commands::handle_commission_setup_command(&ctx, &command, &self.data_manager, &self.lang, &self.images, &self.emojis).await {
-                        eprintln!("Error handling commission setup command: {}", e);
-                        let data = CreateInteractionResponseMessage::new()
-                            .content("Error setting up commission system.");
-                        let builder = CreateInteractionResponse::Message(data);
-                        let _ = command.create_response(&ctx.http, builder).await;
-                    }
-                },
-                "commission_close" => {
-                    // Handle commission close command
-                    if let Err(e) = commands::handle_commission_close_command(&ctx, &command, &self.data_manager, &self.lang).await {
-                        eprintln!("Error handling commission close command: {}", e);
-                        let data = CreateInteractionResponseMessage::new()
-                            .content("Error closing commission.");
-                        let builder = CreateInteractionResponse::Message(data);
-                        let _ = command.create_response(&ctx.http, builder).await;
-                    }
-                },
-                "ticket_setup" => {
-                    // Handle ticket setup command
-                    if let Err(e) = commands::handle_ticket_setup_command(&ctx, &command, &self.data_manager, &self.lang, &self.images, &self.emojis).await {
-                        eprintln!("Error handling ticket setup command: {}", e);
-                        let data = CreateInteractionResponseMessage::new()
-                            .content("Error setting up ticket system.");
-                        let builder = CreateInteractionResponse::Message(data);
-                        let _ = command.create_response(&ctx.http, builder).await;
-                    }
-                },
-                "ticket_close" => {
-                    // Handle ticket close command
-                    if let Err(e) = commands::handle_ticket_close_command(&ctx, &command, &self.data_manager, &self.lang).await {
-                        eprintln!("Error handling ticket close command: {}", e);
-                        let data = CreateInteractionResponseMessage::new()
-                            .content("Error closing ticket.");
-                        let builder = CreateInteractionResponse::Message(data);
-                        let _ = command.create_response(&ctx.http, builder).await;
-                    }
-                },
-                "feedback_setup" => {
-                    // Handle feedback setup command
-                    if let Err(e) = commands::handle_feedback_setup_command(&ctx, &command, &self.data_manager, &self.lang, &self.images, &self.emojis).await {
-                        eprintln!("Error handling feedback setup command: {}", e);
-                        let data = CreateInteractionResponseMessage::new()
-                            .content("Error setting up feedback system.");
-                        let builder = CreateInteractionResponse::Message(data);
-                        let _ = command.create_response(&ctx.http, builder).await;
-                    }
-                },
-                "update" => {
-                    // Handle update command
-                    if let Err(e) = commands::handle_update_command(&ctx, &command).await {
-                        eprintln!("Error handling update command: {}", e);
-                    }
-                },
-                "github_channel" => {
-                    if let Err(e) = commands::handle_github_channel_command(&ctx, &command, &self.data_manager).await {
-                        eprintln!("Error handling github_channel command: {}", e);
-                    }
-                },
-                "ai_channel" => {
-                    if let Err(e) = commands::handle_ai_channel_command(&ctx, &command, &self.data_manager).await {
-                        eprintln!("Error handling ai_channel command: {}", e);
-                    }
-                },
-                _ => {
-                    let content = match command.data.name.as_str() {
-                        "info" => lang_msgs.responses.info.clone(),
-                        "hello" => self.lang.format_hello(&command.user.name),
-                        "help" => {
-                            // Handle new help command with dropdown
-                            if let Err(e) = commands::handle_help_command(&ctx, &command, &self.lang).await {
-                                eprintln!("Error handling help command: {}", e);
-                                lang_msgs.responses.help.clone()
-                            } else {
-                                return; // Successfully handled
-                            }
-                        },
-                        _ => lang_msgs.responses.unknown_command.clone(),
-                    };
-                    
-                    let data = CreateInteractionResponseMessage::new().content(content);
-                    let builder = CreateInteractionResponse::Message(data);
-                    let _ = command.create_response(&ctx.http, builder).await;
+                let locale = self.user_locale(&assets.lang, command.user.id, command.guild_id, Some(&command.locale));
+                let invocation = registry::Invocation {
+                    ctx: &ctx,
+                    command: &command,
+                    library: &self.assets,
+                    assets: &assets,
+                    data_manager: &self.data_manager,
+                    ai_manager: &self.ai_manager,
+                    locale,
+                    received: Instant::now(),
+                    started: self.start_time,
+                };
+                registry::dispatch(&invocation).await;
+            },
+            Interaction::Autocomplete(autocomplete) => {
+                let locale = self.user_locale(&assets.lang, autocomplete.user.id, autocomplete.guild_id, Some(&autocomplete.locale));
+                if let Err(e) = registry::autocomplete(&ctx, &autocomplete, &self.data_manager, &assets.lang, locale).await {
+                    eprintln!("Error handling autocomplete for {}: {}", autocomplete.data.name, e);
                 }
-            }
             },
             Interaction::Component(component) => {
+                let locale = self.user_locale(&assets.lang, component.user.id, component.guild_id, Some(&component.locale));
                 if self
                     .safety
-                    .handle_component(&ctx, &component, &self.data_manager, &self.lang)
+                    .handle_component(&ctx, &component, &self.data_manager, &assets.lang, locale)
                     .await
                 {
                     return;
@@ -750,36 +695,49 @@ impl EventHandler for Handler {
                 // Handle dropdown menu and button interactions
                 match component.data.custom_id.as_str() {
                     "help_select" => {
-                        if let Err(e) = commands::handle_help_selection(&ctx, &component, &self.lang).await {
+                        if let Err(e) = commands::handle_help_selection(&ctx, &component, &assets.lang, locale).await {
                             eprintln!("Error handling help selection: {}", e);
                         }
                     },
                     "help_back" => {
-                        if let Err(e) = commands::handle_help_back(&ctx, &component, &self.lang).await {
+                        if let Err(e) = commands::handle_help_back(&ctx, &component, &self.data_manager, &assets.lang, locale).await {
                             eprintln!("Error handling help back: {}", e);
                         }
                     },
                     "commission_create" => {
                         // Handle commission creation button
-                        if let Err(e) = commands::handle_commission_create(&ctx, &component, &self.data_manager, &self.lang, &self.images, &self.emojis).await {
+                        if let Err(e) = commands::handle_commission_create(&ctx, &component, &self.data_manager, &assets.lang, locale, &assets.images, &assets.emojis).await {
                             eprintln!("Error handling commission creation: {}", e);
                         }
                     },
+                    commands::AI_TOOL_CONFIRM_ID | commands::AI_TOOL_CANCEL_ID => {
+                        // Handle confirmation of actions requested by the AI assistant
+                        if let Err(e) = commands::handle_ai_tool_confirmation(&ctx, &component, &self.data_manager, &assets.lang, locale, &assets.images).await {
+                            eprintln!("Error handling AI tool confirmation: {}", e);
+                        }
+                    },
+                    custom_id if custom_id.starts_with(commands::COMMISSION_QUOTE_ACCEPT_ID)
+                        || custom_id.starts_with(commands::COMMISSION_QUOTE_DECLINE_ID) => {
+                        // Handle quote accept / decline buttons
+                        if let Err(e) = commands::handle_commission_quote_response(&ctx, &component, &self.data_manager, &assets.lang, locale).await {
+                            eprintln!("Error handling commission quote response: {}", e);
+                        }
+                    },
                     custom_id if custom_id.starts_with("commission_close_") => {
                         // Handle commission close button
-                        if let Err(e) = commands::handle_commission_close(&ctx, &component, &self.data_manager, &self.lang, &self.images, &self.emojis).await {
+                        if let Err(e) = commands::handle_commission_close(&ctx, &component, &self.data_manager, &assets.lang, locale, &assets.images, &assets.emojis).await {
                             eprintln!("Error handling commission close: {}", e);
                         }
                     },
                     "ticket_create" => {
                         // Handle ticket creation button
-                        if let Err(e) = commands::handle_ticket_create(&ctx, &component, &self.data_manager, &self.lang, &self.images, &self.emojis).await {
+                        if let Err(e) = commands::handle_ticket_create(&ctx, &component, &self.data_manager, &assets.lang, &assets.images, &assets.emojis).await {
                             eprintln!("Error handling ticket creation: {}", e);
                         }
                     },
                     custom_id if custom_id.starts_with("ticket_close_") => {
                         // Handle ticket close button
-                        if let Err(e) = commands::handle_ticket_close(&ctx, &component, &self.data_manager, &self.lang, &self.images, &self.emojis).await {
+                        if let Err(e) = commands::handle_ticket_close(&ctx, &component, &self.data_manager, &assets.lang, &assets.images, &assets.emojis).await {
                             eprintln!("Error handling ticket close: {}", e);
                         }
                     },
@@ -802,13 +760,13 @@ impl EventHandler for Handler {
                             // Get reminder details to preserve them
                             if let Some(reminder_data) = self.data_manager.get_reminder(reminder_id) {
                                 // Get bell emoji and thumbnail
-                                let bell_emoji = self.emojis.get_emoji("interface", "bell").map_or("🔔", |v| v);
-                                let thumbnail_url = self.images.get_image("reactions", "wow_alert")
-                                    .or_else(|| self.images.get_default_image("success"))
+                                let bell_emoji = assets.emojis.get_emoji("interface", "bell").map_or("🔔", |v| v);
+                                let thumbnail_url = assets.images.get_image("reactions", "wow_alert")
+                                    .or_else(|| assets.images.get_default_image("success"))
                                     .unwrap_or(&"https://cdn.discordapp.com/embed/avatars/0.png".to_string())
                                     .clone();
                                 
-                                let lang_msgs = self.lang.get();
+                                let lang_msgs = assets.lang.get(locale);
                                 let title_with_emoji = format!("{} {}", bell_emoji, &lang_msgs.embeds.reminder_notification.title);
                                 
                                 // Update the message to remove dropdown and show status while preserving original content
@@ -879,6 +837,21 @@ impl EventHandler for Handler {
                     }
                 }
             },
+            Interaction::Modal(modal) => {
+                let locale = self.user_locale(&assets.lang, modal.user.id, modal.guild_id, Some(&modal.locale));
+                match modal.data.custom_id.as_str() {
+                    commands::COMMISSION_INTAKE_MODAL_ID => {
+                        // Handle commission intake form submission
+                        if let Err(e) = commands::handle_commission_intake_submit(&ctx, &modal, &self.data_manager, &assets.lang, locale, &assets.images, &assets.emojis).await {
+                            eprintln!("Error handling commission intake: {}", e);
+                        }
+                    },
+                    _ => {
+                        // Unknown modal submission
+                        eprintln!("Unknown modal interaction: {}", modal.data.custom_id);
+                    }
+                }
+            },
             _ => {} // Other interaction types
         }
     }
@@ -894,16 +867,17 @@ impl EventHandler for Handler {
 
 /// Check and send pending reminders (standalone function for background task)
 async fn check_and_send_reminders(handler: &Handler, http: &Arc<serenity::http::Http>) -> Result<(), Box<dyn std::error::Error>> {
+    let assets = handler.assets.current();
     let pending_reminders = handler.data_manager.get_pending_reminders();
     
     for reminder in pending_reminders {
         // Create reminder notification embed
-        let lang_msgs = handler.lang.get();
+        let lang_msgs = assets.lang.get(handler.reminder_locale(&assets.lang, &reminder.user_id));
         
         // Get bell emoji and wow_alert image
-        let bell_emoji = handler.emojis.get_emoji("interface", "bell").map_or("🔔", |v| v);
-        let thumbnail_url = handler.images.get_image("reactions", "wow_alert")
-            .or_else(|| handler.images.get_default_image("success"))
+        let bell_emoji = assets.emojis.get_emoji("interface", "bell").map_or("🔔", |v| v);
+        let thumbnail_url = assets.images.get_image("reactions", "wow_alert")
+            .or_else(|| assets.images.get_default_image("success"))
             .unwrap_or(&"https://cdn.discordapp.com/embed/avatars/0.png".to_string())
             .clone();
         
@@ -1001,7 +975,7 @@ async fn check_and_send_reminders(handler: &Handler, http: &Arc<serenity::http::
 
 #[tokio::main]
 async fn main() {
-    // Handle --version and --self-check before any initialization, dotenv, or token access.
+    // Handle --version, --self-check and --check-lang before any initialization or token access.
     if let Some(arg) = std::env::args_os().nth(1) {
         use std::ffi::OsStr;
 
@@ -1014,6 +988,19 @@ async fn main() {
             println!("v{}", env!("CARGO_PKG_VERSION"));
             return;
         }
+
+        if arg == OsStr::new("--check-lang") {
+            // Checks the files the bot would load: `--check-lang [dir]`, default $ASSETS_DIR (from .env too) or "."
+            dotenv::dotenv().ok();
+            let dir = std::env::args_os().nth(2).map(std::path::PathBuf::from).unwrap_or_else(lang::assets::assets_dir);
+            let issues = lang::check::check_sources(&lang::assets::lang_sources(&dir));
+            for issue in &issues {
+                println!("{}", issue);
+            }
+            let errors = issues.iter().filter(|issue| issue.severity == lang::check::Severity::Error).count();
+            println!("{} error(s), {} warning(s)", errors, issues.len() - errors);
+            std::process::exit(if errors > 0 { 1 } else { 0 });
+        }
     }
 
     dotenv::dotenv().ok();
@@ -1037,7 +1024,8 @@ async fn main() {
         | GatewayIntents::GUILDS
         | GatewayIntents::GUILD_MEMBERS
         | GatewayIntents::GUILD_MODERATION
-        | GatewayIntents::GUILD_MESSAGE_REACTIONS;
+        | GatewayIntents::GUILD_MESSAGE_REACTIONS
+        | GatewayIntents::DIRECT_MESSAGES;
     
     // Create Arc for sharing between client and background task
     let handler_arc = Arc::new(handler);
@@ -1064,6 +1052,22 @@ async fn main() {
         }
     });
 
+    // Pick up edited asset and persona files; command names come from the lang files
+    let http_assets = client.http.clone();
+    let handler_for_assets = Arc::clone(&handler_arc);
+    tokio::spawn(async move {
+        let mut interval = interval(Duration::from_secs(lang::assets::POLL_INTERVAL_SECS));
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            handler_for_assets.ai_manager.personas().refresh();
+            if handler_for_assets.assets.refresh() {
+                let assets = handler_for_assets.assets.current();
+                commands::sync::sync_commands(&http_assets, &assets.lang, &handler_for_assets.data_manager).await;
+            }
+        }
+    });
+
     // Start GitHub activity feed task
     let http_github = client.http.clone();
     let handler_for_github = Arc::clone(&handler_arc);
@@ -1086,6 +1090,35 @@ async fn main() {
         }
     });
 
+    // Report broken image URLs while uploaded local images are kept on freshly signed URLs
+    let storage_channel = image_store::storage_channel();
+    let handler_for_checks = Arc::clone(&handler_arc);
+    tokio::spawn(async move {
+        let assets = &handler_for_checks.assets;
+        let problems = image_store::check_image_urls(&assets.current().images, assets.dir(), storage_channel.is_some()).await;
+        for problem in &problems {
+            eprintln!("⚠️  Image {}", problem);
+        }
+    });
+
+    let http_images = client.http.clone();
+    let handler_for_images = Arc::clone(&handler_arc);
+    tokio::spawn(async move {
+        let assets = &handler_for_images.assets;
+        let Some(channel_id) = storage_channel else {
+            return;
+        };
+        let mut interval = interval(Duration::from_secs(image_store::REFRESH_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            match image_store::sync_images(&http_images, assets, &handler_for_images.data_manager, channel_id).await {
+                Ok(0) => {}
+                Ok(count) => println!("🖼️  {} images served from local files", count),
+                Err(e) => eprintln!("Image storage error: {}", e),
+            }
+        }
+    });
+
     // Start auto-update task (release builds only)
     #[cfg(not(debug_assertions))]
     {